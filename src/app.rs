@@ -1,16 +1,26 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEventKind};
 use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
+use std::collections::{HashMap, HashSet};
 use std::io::Stdout;
+use std::sync::mpsc::{Receiver, TryRecvError};
 use std::time::{Duration, Instant};
 
 use crate::apply;
+use crate::clock;
+use crate::backend::{self, Backend};
+use crate::config::Config;
+use crate::events;
+use crate::hooks;
+use crate::keys::{Action, Keymap};
 use crate::layout::{self, Direction, LayoutMonitor};
-use crate::monitor::{self, MonitorInfo};
+use crate::monitor::{self, AvailableMode, MonitorInfo};
 use crate::preset;
+use crate::theme::Theme;
+use crate::ui::canvas_pane::{CanvasTransform, CanvasView};
 
 const SCALES: &[f32] = &[1.0, 1.2, 1.5, 2.0, 3.0];
 const SLIDE_STEP: i32 = 50;
-const CONFIRM_DURATION: Duration = Duration::from_secs(10);
+const RESIZE_STEP: i32 = 8;
 
 struct DragState {
     monitor_idx: usize,
@@ -31,10 +41,67 @@ pub enum Overlay {
     Presets {
         selected: usize,
         names: Vec<String>,
+        trusted: Vec<String>,  // Names of presets that skip the confirm countdown
+        details: Vec<String>,  // Summary line per name, e.g. "3 monitors · saved 2024-01-05"
         saving: bool,
         input: String,
+        renaming: Option<String>,  // Preset being renamed while `saving`; None saves a new one
+    },
+    ExternalChange {
+        confirming_pull: bool,     // Waiting for y/n before discarding unsaved edits
+        since: Vec<MonitorInfo>,   // Live state before the change; `changes` is relative to this
+        changes: Vec<String>,      // What changed, one line per monitor
+    },
+    VerifyMonitor {
+        pending: Vec<String>,  // Newly enabled monitors still to check; the first is being asked about
+        countdown_start: Instant,
+        duration: Duration,
+        ready_for_input: bool,
+    },
+    ModePicker {
+        selected: usize,  // Index into the selected monitor's available_modes
+    },
+    MirrorPicker {
+        selected: usize,       // 0 is "don't mirror"; i > 0 picks targets[i - 1]
+        targets: Vec<String>,  // Enabled monitors the selected one can mirror
+    },
+    ScaleInput {
+        input: String,  // Scale typed so far for the selected monitor
+    },
+    Jump {
+        input: String,  // Name prefix typed so far; selection follows it
+    },
+    /// Typing into the list filter (`App::filter`); the list narrows as it changes.
+    Filter,
+    WorkspaceInput {
+        input: String,  // Workspace list being typed for the selected monitor, e.g. "1-5,10"
+    },
+    ReservedInput {
+        input: String,  // Reserved area being typed for the selected monitor, e.g. "30 0 0 0"
+    },
+    /// Waiting for h/j/k/l to pick which edge of the selected monitor to line up.
+    Align,
+    /// Bit depth and color management keys for the selected monitor.
+    Color,
+    Disconnected {
+        names: Vec<String>,  // Monitors being enabled that are no longer connected
+        trusted: bool,       // Carried through to the apply if the user goes ahead
+    },
+    PresetPreview {
+        changes: Vec<String>,               // One line per monitor the preset changes vs. the live state
+        previous: Vec<MonitorInfo>,         // Editor state to restore if the preset is cancelled
+        preset: Option<(usize, String)>,    // Becomes current_preset once applied; None for "recent"
+        trusted: bool,
     },
-    ExternalChange,
+}
+
+impl Overlay {
+    /// Whether the overlay holds the keyboard and mouse: polling for outside changes and
+    /// clicks on the panes wait until it closes. The external change prompt is itself the
+    /// result of a poll, so it keeps polling.
+    pub fn is_modal(&self) -> bool {
+        !matches!(self, Overlay::None | Overlay::ExternalChange { .. })
+    }
 }
 
 pub struct App {
@@ -44,49 +111,146 @@ pub struct App {
     pub status_msg: String,
     pub changed: bool,
     pub show_all_monitors: bool,
+    /// Draw the canvas with monitor sizes compressed toward each other (not to scale).
+    pub equalize_canvas: bool,
+    /// Display-only zoom and pan of the layout pane (never touches monitor positions).
+    pub canvas_view: CanvasView,
+    pub config: Config,
     initial_state: Vec<MonitorInfo>,
     prev_state: Option<Vec<MonitorInfo>>,
     pub list_area: Rect,
+    /// Position in the visible monitors of the first one shown when the list overflows.
+    pub list_offset: usize,
+    /// Rows each listed monitor took up in the last draw.
+    pub list_heights: Vec<u16>,
     pub canvas_area: Rect,
     drag: Option<DragState>,
     last_poll: Instant,
     external_state: Vec<MonitorInfo>,
     last_apply: Option<Instant>,  // Track when we last applied changes
+    /// When the last successful apply (or its confirmation) happened, for the status bar:
+    /// the instant for "Ns ago" and the wall-clock time it happened at.
+    pub last_applied_at: Option<(Instant, String)>,
+    /// Position in `list_presets()` and name of the preset loaded last; `n`/`N` step from here.
+    pub current_preset: Option<(usize, String)>,
+    /// Applies wait for confirmation; `c` toggles this for the rest of the session,
+    /// starting from the opposite of `auto_confirm`.
+    pub require_confirm: bool,
+    /// Dashboard mode (`--monitor`): follow the live state, never edit or apply.
+    pub read_only: bool,
+    /// Why a pre/post apply hook failed during the last apply, if one did.
+    pub hook_error: Option<String>,
+    /// The last apply's `post_apply` hook, still running on its own thread; a message
+    /// arrives if it fails.
+    post_apply_hook: Option<Receiver<String>>,
+    /// Hyprland monitor events, when its event socket is available.
+    events: Option<Receiver<()>>,
+    /// Reads and applies monitor layouts (Hyprland or wlr-randr).
+    backend: Box<dyn Backend>,
+    /// Available modes per monitor name, fetched the first time they are needed.
+    mode_cache: HashMap<String, Vec<AvailableMode>>,
+    /// What each key does in the main view: the defaults plus config.toml's `[keys]`.
+    keymap: Keymap,
+    /// Colors to draw with: the default palette plus config.toml's `[theme]`.
+    pub theme: Theme,
+    /// Monitors whose screen was turned off (DPMS) this session. Never saved: it isn't
+    /// part of the layout, and the screens come back on with any Hyprland restart.
+    pub dpms_off: HashSet<String>,
+    /// Quit was pressed once with unapplied changes; pressing it again right away quits.
+    pub quit_pending: bool,
+    /// Only list monitors whose name or description contains this (case-insensitive).
+    pub filter: String,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(config: Config, config_error: Option<String>, read_only: bool) -> Self {
         // Always fetch all monitors, we'll filter display based on show_all_monitors flag
-        let mut monitors = monitor::fetch_monitors_all();
+        let backend = backend::select(&config);
+        let mut monitors = backend.fetch_monitors(false);
 
-        // Restore workspace assignments from most recent save
+        // Restore workspace assignments, the primary monitor, color management, reserved
+        // areas and fullscreen-only VRR (which hyprctl reports as off outside fullscreen)
+        // from most recent save
         if let Some(recent) = preset::load_recent() {
             for config in &recent {
                 if let Some(m) = monitors.iter_mut().find(|m| m.name == config.name) {
                     if !config.workspaces.is_empty() {
                         m.workspaces = config.workspaces.clone();
                     }
+                    m.primary = config.primary;
+                    m.cm = config.cm.clone();
+                    m.reserved = config.reserved;
+                    if config.vrr == 2 && m.vrr == 0 {
+                        m.vrr = 2;
+                    }
+                    m.vrr_set = config.vrr_set;
                 }
             }
         }
 
-        let initial_state = monitors.clone();
+        // Seed from monitors.conf when monitui wrote it: after a fresh boot, before Hyprland
+        // has reloaded it, the live state can still be missing its scale and transform.
+        // The read-only dashboard shows the live state as it is.
         let external_state = monitors.clone();
+        if !read_only {
+            apply::merge_conf_rules(&mut monitors, &apply::read_monitors_conf());
+        }
+        let differs_from_live = !monitors_equal(&external_state, &monitors);
+        let initial_state = monitors.clone();
+        let auto_confirm = config.auto_confirm;
+        let mut section_errors = Vec::new();
+        let keymap = Keymap::from_config(&config.keys).unwrap_or_else(|e| {
+            section_errors.push(format!("[keys]: {} — using the default keys", e));
+            Keymap::default()
+        });
+        let theme = Theme::from_config(&config.theme).unwrap_or_else(|e| {
+            section_errors.push(format!("[theme]: {} — using the default colors", e));
+            Theme::default()
+        });
         App {
             monitors,
             selected: 0,
             overlay: Overlay::None,
-            status_msg: "Welcome to monitui".to_string(),
-            changed: false,
-            show_all_monitors: false,
+            status_msg: if let Some(e) = config_error {
+                format!("Error in config.toml: {}", e)
+            } else if !section_errors.is_empty() {
+                format!("Error in config.toml: {}", section_errors.join("; "))
+            } else if let Some(note) = config.cell_aspect_note() {
+                note
+            } else if differs_from_live {
+                "Loaded monitors.conf, which differs from the live layout — press y to apply it".to_string()
+            } else {
+                "Welcome to monitui".to_string()
+            },
+            changed: differs_from_live,
+            show_all_monitors: config.show_all_monitors,
+            equalize_canvas: false,
+            canvas_view: CanvasView::default(),
+            config,
             initial_state,
             prev_state: None,
             list_area: Rect::default(),
+            list_offset: 0,
+            list_heights: Vec::new(),
             canvas_area: Rect::default(),
             drag: None,
             last_poll: Instant::now(),
             external_state,
             last_apply: None,
+            last_applied_at: None,
+            current_preset: None,
+            require_confirm: !auto_confirm,
+            read_only,
+            hook_error: None,
+            post_apply_hook: None,
+            events: events::subscribe(),
+            backend,
+            mode_cache: HashMap::new(),
+            keymap,
+            theme,
+            dpms_off: HashSet::new(),
+            quit_pending: false,
+            filter: String::new(),
         }
     }
 
@@ -94,21 +258,38 @@ impl App {
         loop {
             terminal.draw(|f| crate::ui::draw(f, self))?;
 
-            // Poll for external configuration changes every 3 seconds
+            // Poll for external configuration changes every 3 seconds, and right away when
+            // Hyprland reports a monitor event (timed polls still catch changes made
+            // without one, such as `hyprctl keyword monitor`)
             // Continue polling during ExternalChange to get latest state
             // But NEVER interrupt Confirm countdown or Presets menu
-            // Also enforce grace period after apply/confirm/revert (5 seconds for Hyprland to stabilize)
+            // During the grace period after apply/confirm/revert (5 seconds for Hyprland to
+            // stabilize) polls only track the live state, so settling jitter never prompts
             let in_grace_period = self.last_apply
                 .map(|t| t.elapsed() < Duration::from_secs(5))
                 .unwrap_or(false);
 
-            let should_poll = self.last_poll.elapsed() >= Duration::from_secs(3)
-                && !matches!(self.overlay, Overlay::Confirm { .. } | Overlay::Presets { .. })
-                && !in_grace_period;
+            let event_fired = self.events.as_ref().is_some_and(|rx| rx.try_iter().count() > 0);
+            let should_poll = (event_fired || self.last_poll.elapsed() >= Duration::from_secs(self.config.poll_interval))
+                && !self.overlay.is_modal();
 
             if should_poll {
                 self.last_poll = Instant::now();
-                self.check_external_changes();
+                self.check_external_changes(in_grace_period);
+            }
+
+            if let Some(rx) = &self.post_apply_hook {
+                match rx.try_recv() {
+                    Ok(e) => {
+                        self.hook_error = Some(match self.hook_error.take() {
+                            Some(pre) => format!("{}; {}", pre, e),
+                            None => e,
+                        });
+                        self.post_apply_hook = None;
+                    }
+                    Err(TryRecvError::Disconnected) => self.post_apply_hook = None,
+                    Err(TryRecvError::Empty) => {}
+                }
             }
 
             if let Overlay::Confirm { countdown_start, duration, ready_for_input } = &self.overlay {
@@ -131,15 +312,28 @@ impl App {
                 }
             }
 
+            if let Overlay::VerifyMonitor { countdown_start, duration, ready_for_input, .. } = &mut self.overlay {
+                if !*ready_for_input && countdown_start.elapsed() >= Duration::from_millis(200) {
+                    *ready_for_input = true;
+                }
+
+                // No answer counts as "can't see it"
+                if countdown_start.elapsed() >= *duration {
+                    self.answer_verify(false);
+                    continue;
+                }
+            }
+
             let poll_timeout = match &self.overlay {
-                Overlay::Confirm { .. } => Duration::from_millis(100),
+                Overlay::Confirm { .. } | Overlay::VerifyMonitor { .. } => Duration::from_millis(100),
                 _ => Duration::from_millis(200),
             };
 
             if crossterm::event::poll(poll_timeout)? {
                 match event::read()? {
-                    Event::Key(key) => {
-                        if key.kind == KeyEventKind::Press && !self.handle_key(key) {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        let keep_running = self.handle_key(key);
+                        if !keep_running {
                             return Ok(());
                         }
                     }
@@ -164,10 +358,67 @@ impl App {
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> bool {
+        // Only the very next key can confirm a quit
+        let quit_pending = std::mem::take(&mut self.quit_pending);
+
         match &self.overlay {
             Overlay::Confirm { .. } => return self.handle_confirm_key(key),
-            Overlay::ExternalChange => {
-                return self.handle_external_change_key(key);
+            Overlay::VerifyMonitor { ready_for_input, .. } => {
+                if *ready_for_input {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => self.answer_verify(true),
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => self.answer_verify(false),
+                        _ => {}
+                    }
+                }
+                return true;
+            }
+            Overlay::ExternalChange { .. } => {
+                return self.handle_external_change_key(key, quit_pending);
+            }
+            Overlay::ModePicker { .. } => {
+                self.handle_mode_picker_key(key);
+                return true;
+            }
+            Overlay::Disconnected { .. } => {
+                self.handle_disconnected_key(key);
+                return true;
+            }
+            Overlay::MirrorPicker { .. } => {
+                self.handle_mirror_picker_key(key);
+                return true;
+            }
+            Overlay::PresetPreview { .. } => {
+                self.handle_preset_preview_key(key);
+                return true;
+            }
+            Overlay::ScaleInput { .. } => {
+                self.handle_scale_input_key(key);
+                return true;
+            }
+            Overlay::WorkspaceInput { .. } => {
+                self.handle_workspace_input_key(key);
+                return true;
+            }
+            Overlay::ReservedInput { .. } => {
+                self.handle_reserved_input_key(key);
+                return true;
+            }
+            Overlay::Jump { .. } => {
+                self.handle_jump_key(key);
+                return true;
+            }
+            Overlay::Filter => {
+                self.handle_filter_key(key);
+                return true;
+            }
+            Overlay::Align => {
+                self.handle_align_key(key);
+                return true;
+            }
+            Overlay::Color => {
+                self.handle_color_key(key);
+                return true;
             }
             Overlay::Presets { saving, .. } => {
                 if *saving {
@@ -180,86 +431,145 @@ impl App {
             Overlay::None => {}
         }
 
-        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        // Esc drops an active filter before it gets to mean quit
+        if key.code == KeyCode::Esc && !self.filter.is_empty() {
+            self.set_filter(String::new());
+            return true;
+        }
 
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => return false,
+        let Some(action) = self.keymap.action(&key) else { return true; };
+
+        // Read-only mode only lets you look around
+        if self.read_only && !action.is_read_only() {
+            return true;
+        }
+
+        match action {
+            Action::Quit if self.changed && !quit_pending => {
+                self.quit_pending = true;
+                self.status_msg = "Unsaved changes — press q again to quit, y to apply".to_string();
+            }
+            Action::Quit => return false,
 
-            // Tab cycles monitor selection (only through visible monitors)
-            KeyCode::Tab => {
-                let visible = self.visible_monitors();
+            // Tab cycles monitor selection (only through listed monitors)
+            Action::NextMonitor => {
+                let visible = self.listed_monitors();
                 if !visible.is_empty() {
                     let current_pos = visible.iter().position(|&i| i == self.selected);
                     let next_pos = match current_pos {
                         Some(pos) => (pos + 1) % visible.len(),
                         None => 0,
                     };
-                    self.selected = visible[next_pos];
+                    self.select(visible[next_pos]);
                 }
             }
-            KeyCode::BackTab => {
-                let visible = self.visible_monitors();
+            Action::PrevMonitor => {
+                let visible = self.listed_monitors();
                 if !visible.is_empty() {
                     let current_pos = visible.iter().position(|&i| i == self.selected);
                     let next_pos = match current_pos {
                         Some(pos) => if pos == 0 { visible.len() - 1 } else { pos - 1 },
                         None => visible.len() - 1,
                     };
-                    self.selected = visible[next_pos];
+                    self.select(visible[next_pos]);
                 }
             }
 
-            // hjkl / arrows: move monitors (shift = snap to far side)
-            KeyCode::Char('h') | KeyCode::Left if !shift => {
-                self.canvas_move(Direction::Left, false);
-            }
-            KeyCode::Char('j') | KeyCode::Down if !shift => {
-                self.canvas_move(Direction::Down, false);
-            }
-            KeyCode::Char('k') | KeyCode::Up if !shift => {
-                self.canvas_move(Direction::Up, false);
-            }
-            KeyCode::Char('l') | KeyCode::Right if !shift => {
-                self.canvas_move(Direction::Right, false);
-            }
-            KeyCode::Char('H') | KeyCode::Left if shift => self.canvas_move(Direction::Left, true),
-            KeyCode::Char('J') | KeyCode::Down if shift => self.canvas_move(Direction::Down, true),
-            KeyCode::Char('K') | KeyCode::Up if shift => self.canvas_move(Direction::Up, true),
-            KeyCode::Char('L') | KeyCode::Right if shift => self.canvas_move(Direction::Right, true),
-
-            KeyCode::Char('p') => self.open_presets(),
-            KeyCode::Char('y') | KeyCode::Char(' ') | KeyCode::Enter => self.apply(),
+            // Resize the selected monitor's mode
+            Action::ResizeLeft => self.resize_selected(-RESIZE_STEP, 0),
+            Action::ResizeRight => self.resize_selected(RESIZE_STEP, 0),
+            Action::ResizeUp => self.resize_selected(0, -RESIZE_STEP),
+            Action::ResizeDown => self.resize_selected(0, RESIZE_STEP),
+
+            // Move monitors (snap = to the far side)
+            Action::MoveLeft => self.canvas_move(Direction::Left, false),
+            Action::MoveDown => self.canvas_move(Direction::Down, false),
+            Action::MoveUp => self.canvas_move(Direction::Up, false),
+            Action::MoveRight => self.canvas_move(Direction::Right, false),
+            Action::SnapLeft => self.canvas_move(Direction::Left, true),
+            Action::SnapDown => self.canvas_move(Direction::Down, true),
+            Action::SnapUp => self.canvas_move(Direction::Up, true),
+            Action::SnapRight => self.canvas_move(Direction::Right, true),
+
+            // Display-only zoom and pan of the layout pane
+            Action::ZoomIn => self.canvas_view.zoom_in(),
+            Action::ZoomOut => self.canvas_view.zoom_out(),
+            Action::ResetView => self.canvas_view = CanvasView::default(),
+            Action::PanLeft => self.canvas_view.pan(-1, 0),
+            Action::PanDown => self.canvas_view.pan(0, 1),
+            Action::PanUp => self.canvas_view.pan(0, -1),
+            Action::PanRight => self.canvas_view.pan(1, 0),
+
+            Action::Jump => self.overlay = Overlay::Jump { input: String::new() },
+            Action::Filter => self.overlay = Overlay::Filter,
+            Action::Align => self.overlay = Overlay::Align,
+            Action::Color => self.open_color(),
+            Action::Presets => self.open_presets(),
+            Action::NextPreset => self.cycle_preset(true),
+            Action::PrevPreset => self.cycle_preset(false),
+            Action::Apply => self.apply(),
+            Action::ApplyNow => self.apply_with(true),
 
             // Monitor config keys
-            KeyCode::Char('d') => {
-                if !self.monitors[self.selected].disabled {
-                    self.monitors[self.selected].disabled = true;
-                    self.changed = true;
-                    self.status_msg = format!("Disabled {}", self.monitors[self.selected].name);
+            Action::Disable if !self.monitors[self.selected].disabled => {
+                let others_enabled = self.monitors.iter().enumerate()
+                    .any(|(i, m)| i != self.selected && !m.disabled);
+                if !others_enabled {
+                    self.status_msg = format!(
+                        "Error: {} is the only enabled monitor — disabling it would leave no display",
+                        self.monitors[self.selected].name
+                    );
+                    return true;
                 }
+                self.monitors[self.selected].disabled = true;
+                self.changed = true;
+                self.normalize_layout();
+                self.status_msg = format!("Disabled {}", self.monitors[self.selected].name);
             }
-            KeyCode::Char('e') => {
-                if self.monitors[self.selected].disabled {
-                    self.monitors[self.selected].disabled = false;
-                    self.changed = true;
-                    self.apply_layout_adjustments();  // Auto-snap to avoid overlaps
-                    self.status_msg = format!("Enabled {}", self.monitors[self.selected].name);
-                }
+            Action::Enable if self.monitors[self.selected].disabled => {
+                self.monitors[self.selected].disabled = false;
+                self.changed = true;
+                self.apply_layout_adjustments();  // Auto-snap to avoid overlaps
+                self.status_msg = format!("Enabled {}", self.monitors[self.selected].name);
             }
-            KeyCode::Char('s') => self.cycle_scale(),
-            KeyCode::Char('+') | KeyCode::Char('=') => self.scale_up(),
-            KeyCode::Char('-') => self.scale_down(),
-            KeyCode::Char('z') => {
+            Action::Disable | Action::Enable => {}
+            Action::Solo => self.solo_selected(),
+            Action::CycleScale => self.cycle_scale(),
+            Action::ScaleLock => self.toggle_scale_lock(),
+            Action::ExactScale => self.open_scale_input(),
+            Action::ScaleUp => self.scale_up(),
+            Action::ScaleDown => self.scale_down(),
+            Action::Discard => self.discard_edits(),
+            Action::CycleResolution => {
+                self.load_modes();
                 self.monitors[self.selected].cycle_resolution();
                 self.changed = true;
                 self.apply_layout_adjustments();
                 self.status_msg = format!(
                     "{}: {}",
                     self.monitors[self.selected].name,
-                    self.monitors[self.selected].resolution_string()
+                    self.monitors[self.selected].resolution_string(self.config.refresh_decimals)
                 );
             }
-            KeyCode::Char('r') | KeyCode::Char('R') => {
+            Action::ModePicker => self.open_mode_picker(),
+            Action::CycleRefresh => {
+                self.load_modes();
+                let decimals = self.config.refresh_decimals.max(2);
+                let m = &mut self.monitors[self.selected];
+                if m.disabled { return true; }
+                self.status_msg = match m.cycle_refresh() {
+                    Ok(rate) => {
+                        self.changed = true;
+                        format!("{}: {}", m.name, monitor::format_refresh(rate, decimals))
+                    }
+                    Err(e) => e,
+                };
+            }
+            Action::Mirror => self.open_mirror_picker(),
+            Action::Dpms => self.toggle_dpms(),
+            Action::Origin => self.move_to_origin(),
+            Action::AutoSnap => self.reconnect_layout(),
+            Action::Rotate => {
                 self.monitors[self.selected].cycle_rotation();
                 self.changed = true;
                 self.apply_layout_adjustments();
@@ -269,74 +579,272 @@ impl App {
                     self.monitors[self.selected].rotation_string()
                 );
             }
-            KeyCode::Char('t') => self.toggle_show_all(),
-            KeyCode::Char(c) if c.is_ascii_digit() && c != '0' => {
-                let ws = c as u32 - '0' as u32;
-                for (i, m) in self.monitors.iter_mut().enumerate() {
-                    if i != self.selected {
-                        m.workspaces.retain(|&w| w != ws);
+            Action::Flip => {
+                self.monitors[self.selected].toggle_flip();
+                self.changed = true;
+                self.apply_layout_adjustments();
+                self.status_msg = format!(
+                    "{}: rotation {}",
+                    self.monitors[self.selected].name,
+                    self.monitors[self.selected].rotation_string()
+                );
+            }
+            Action::ToggleAll => self.toggle_show_all(),
+            Action::ToggleView => self.toggle_equalize_canvas(),
+            Action::ToggleConfirm => self.toggle_require_confirm(),
+            Action::Baseline => {
+                // Treat the current layout as the new normal for diffs and reverts
+                self.initial_state = self.monitors.clone();
+                self.changed = false;
+                self.status_msg = "Marked current layout as baseline".to_string();
+            }
+            Action::RemoveWorkspace(ws) => {
+                let m = &mut self.monitors[self.selected];
+                if let Some(pos) = m.workspaces.iter().position(|&w| w == ws) {
+                    m.workspaces.remove(pos);
+                    self.changed = true;
+                    self.status_msg = format!("Removed WS {} from {}", ws, m.name);
+                } else {
+                    self.status_msg = format!("WS {} isn't assigned to {}", ws, m.name);
+                }
+            }
+            Action::Workspace(ws) => {
+                match monitor::assign_workspace(&mut self.monitors, self.selected, ws) {
+                    Ok(true) => {
+                        self.changed = true;
+                        self.status_msg = format!("Assigned WS {} to {}", ws, self.monitors[self.selected].name);
                     }
+                    Ok(false) => {}
+                    Err(e) => self.status_msg = e,
                 }
+            }
+            Action::WorkspaceInput => self.open_workspace_input(),
+            Action::ReservedInput => self.open_reserved_input(),
+            Action::Primary => self.toggle_primary(),
+            Action::Vrr => {
                 let m = &mut self.monitors[self.selected];
-                if !m.workspaces.contains(&ws) {
-                    m.workspaces.push(ws);
-                    m.workspaces.sort();
-                    self.changed = true;
-                    self.status_msg = format!("Assigned WS {} to {}", ws, m.name);
+                if m.disabled {
+                    self.status_msg = format!("{} is disabled — enable it to change its VRR", m.name);
+                    return true;
                 }
+                m.cycle_vrr();
+                self.changed = true;
+                self.status_msg = format!("{}: {}", m.name, m.vrr_string());
             }
-            KeyCode::Char('W') => {
+            Action::ClearWorkspaces => {
                 self.monitors[self.selected].workspaces.clear();
                 self.changed = true;
                 self.status_msg = format!("Cleared workspaces from {}", self.monitors[self.selected].name);
             }
-            _ => {}
         }
         true
     }
 
+    /// Change the selected monitor, moving Hyprland focus along with it if configured.
+    fn select(&mut self, idx: usize) {
+        if idx == self.selected {
+            return;
+        }
+        self.selected = idx;
+        if self.config.focus_follows_selection && !self.monitors[idx].disabled {
+            apply::focus_monitor(&self.monitors[idx].name);
+        }
+    }
+
+    /// Disable every monitor except the selected one, enabling it if needed, so a single
+    /// monitor can be tested on its own. The selected monitor always stays on.
+    fn solo_selected(&mut self) {
+        let selected = self.selected;
+        let mut disabled = 0;
+        for (i, m) in self.monitors.iter_mut().enumerate() {
+            if i == selected {
+                m.disabled = false;
+                // Its mirror target is about to be turned off
+                m.mirror_of = None;
+            } else if !m.disabled {
+                m.disabled = true;
+                disabled += 1;
+            }
+        }
+        self.changed = true;
+        self.apply_layout_adjustments();
+        self.status_msg = format!(
+            "Only {} enabled ({} other monitor{} disabled)",
+            self.monitors[selected].name, disabled, if disabled == 1 { "" } else { "s" }
+        );
+    }
+
+    /// Turn the selected monitor's screen off, or back on, with DPMS. Unlike disabling,
+    /// the monitor keeps its place in the layout and its workspaces.
+    fn toggle_dpms(&mut self) {
+        let m = &self.monitors[self.selected];
+        if m.disabled {
+            self.status_msg = format!("{} is disabled — enable it to turn its screen on", m.name);
+            return;
+        }
+        let name = m.name.clone();
+        let off = self.dpms_off.contains(&name) || m.no_signal();
+        if !off {
+            let others_lit = self.monitors.iter().enumerate().any(|(i, m)| {
+                i != self.selected && !m.disabled && !m.no_signal()
+                    && !m.name.starts_with("HEADLESS-") && !self.dpms_off.contains(&m.name)
+            });
+            if !others_lit {
+                self.status_msg = format!("Error: {} is the only screen on — turning it off would leave nothing to see", name);
+                return;
+            }
+        }
+        if let Err(e) = apply::set_dpms(&name, off) {
+            self.status_msg = format!("Error: {}", e);
+            return;
+        }
+        if off {
+            self.dpms_off.remove(&name);
+            self.monitors[self.selected].dpms_status = Some(true);
+            self.status_msg = format!("{}: screen on", name);
+        } else {
+            self.dpms_off.insert(name.clone());
+            self.status_msg = format!("{}: screen off (still enabled; O turns it back on)", name);
+        }
+    }
+
+    /// Throw away every edit since the baseline (launch, the last kept apply, or `b`)
+    /// without applying anything.
+    fn discard_edits(&mut self) {
+        if !self.changed {
+            self.status_msg = "No pending edits to discard".to_string();
+            return;
+        }
+        self.monitors = self.initial_state.clone();
+        self.changed = false;
+        let visible = self.visible_monitors();
+        if !visible.contains(&self.selected) {
+            self.selected = visible.first().copied().unwrap_or(0);
+        }
+        self.status_msg = "Discarded pending edits".to_string();
+    }
+
+    /// Type a name prefix to jump to the first listed monitor it matches.
+    fn handle_jump_key(&mut self, key: KeyEvent) {
+        let Overlay::Jump { input } = &mut self.overlay else { return; };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => { input.pop(); }
+            KeyCode::Enter | KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                return;
+            }
+            _ => return,
+        }
+
+        let input = input.clone();
+        let prefix = input.to_lowercase();
+        if prefix.is_empty() { return; }
+        let found = self.listed_monitors().into_iter()
+            .find(|&i| self.monitors[i].name.to_lowercase().starts_with(&prefix));
+        match found {
+            Some(i) => {
+                self.select(i);
+                self.status_msg = format!("Selected {}", self.monitors[i].name);
+            }
+            None => self.status_msg = format!("No monitor starting with '{}'", input),
+        }
+    }
+
+    /// Type to narrow the list. Enter keeps the filter, Esc clears it.
+    fn handle_filter_key(&mut self, key: KeyEvent) {
+        let mut filter = self.filter.clone();
+        match key.code {
+            KeyCode::Char(c) => filter.push(c),
+            KeyCode::Backspace => { filter.pop(); }
+            KeyCode::Enter => {
+                self.overlay = Overlay::None;
+                return;
+            }
+            KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                filter.clear();
+            }
+            _ => return,
+        }
+        self.set_filter(filter);
+    }
+
+    /// Change the list filter, moving the selection onto the first match if it no longer
+    /// matches. With nothing matching, the selection stays where it was.
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        let listed = self.listed_monitors();
+        if !listed.contains(&self.selected) {
+            if let Some(&first) = listed.first() {
+                self.select(first);
+            }
+        }
+        self.status_msg = if self.filter.is_empty() {
+            "Filter cleared".to_string()
+        } else if listed.is_empty() {
+            format!("No monitor matches '{}'", self.filter)
+        } else {
+            format!("{} of {} monitors match '{}'", listed.len(), self.visible_monitors().len(), self.filter)
+        };
+    }
+
     fn canvas_move(&mut self, dir: Direction, snap: bool) {
         let mut layout_monitors = self.build_layout_monitors();
         if layout_monitors.is_empty() { return; }
 
-        let enabled_idx = self.monitors.iter()
-            .take(self.selected + 1)
-            .filter(|m| !m.disabled)
-            .count()
-            .saturating_sub(1);
-
-        if enabled_idx >= layout_monitors.len() { return; }
+        let name = &self.monitors[self.selected].name;
+        let Some(enabled_idx) = layout_monitors.iter().position(|lm| &lm.id == name) else { return; };
 
         let orig_x = layout_monitors[enabled_idx].x;
         let orig_y = layout_monitors[enabled_idx].y;
 
         if snap {
-            layout::snap_to_far_side(&mut layout_monitors, enabled_idx, dir);
+            layout::snap_to_far_side(&mut layout_monitors, enabled_idx, dir, self.config.gap);
         } else {
-            layout::move_monitor(&mut layout_monitors, enabled_idx, dir, SLIDE_STEP);
+            layout::move_monitor(&mut layout_monitors, enabled_idx, dir, SLIDE_STEP, self.config.gap);
         }
 
-        layout::auto_snap_all(&mut layout_monitors);
-        layout::resolve_overlaps(&mut layout_monitors, enabled_idx, orig_x, orig_y);
-        layout::normalize(&mut layout_monitors);
+        layout::auto_snap_all(&mut layout_monitors, self.config.gap);
+        layout::resolve_overlaps(&mut layout_monitors, enabled_idx, orig_x, orig_y, self.config.gap);
+        self.normalize(&mut layout_monitors);
         self.apply_layout_to_monitors(&layout_monitors);
         self.changed = true;
         self.status_msg = "Layout updated".to_string();
     }
 
     fn build_layout_monitors(&self) -> Vec<LayoutMonitor> {
+        // Mirrored monitors sit on top of their target, so they stay out of layout moves
         self.monitors.iter()
-            .filter(|m| !m.disabled)
-            .map(|m| LayoutMonitor {
-                id: m.name.clone(),
-                x: m.x,
-                y: m.y,
-                w: m.logical_width(),
-                h: m.logical_height(),
-            })
+            .filter(|m| !m.disabled && m.mirror_of.is_none())
+            .map(MonitorInfo::layout_rect)
             .collect()
     }
 
+    /// Shift enabled monitors so the layout starts at (0, 0), keeping their relative
+    /// positions, so the editor never shows negative coordinates (unless a primary
+    /// monitor pins the layout, or `normalize_origin` is off). Returns the shift.
+    fn normalize_layout(&mut self) -> (i32, i32) {
+        let mut layout_monitors = self.build_layout_monitors();
+        let shift = self.normalize(&mut layout_monitors);
+        self.apply_layout_to_monitors(&layout_monitors);
+        shift
+    }
+
+    /// Normalize a layout, except that a primary monitor is kept at (0, 0) even if
+    /// that leaves others at negative coordinates, and that with `normalize_origin`
+    /// off the layout stays where it is. Returns the shift applied.
+    fn normalize(&self, layout_monitors: &mut [LayoutMonitor]) -> (i32, i32) {
+        let primary = self.monitors.iter()
+            .find(|m| m.primary)
+            .and_then(|p| layout_monitors.iter().position(|lm| lm.id == p.name));
+        match primary {
+            Some(idx) => layout::anchor_at_origin(layout_monitors, idx),
+            None if self.config.normalize_origin => layout::normalize(layout_monitors),
+            None => (0, 0),
+        }
+    }
+
     fn apply_layout_to_monitors(&mut self, layout: &[LayoutMonitor]) {
         for lm in layout {
             if let Some(m) = self.monitors.iter_mut().find(|m| m.name == lm.id) {
@@ -350,76 +858,164 @@ impl App {
         let mut layout_monitors = self.build_layout_monitors();
         if layout_monitors.is_empty() { return; }
 
-        let enabled_idx = self.monitors.iter()
-            .take(self.selected + 1)
-            .filter(|m| !m.disabled)
-            .count()
-            .saturating_sub(1);
-
-        if enabled_idx >= layout_monitors.len() { return; }
+        let name = &self.monitors[self.selected].name;
+        let Some(enabled_idx) = layout_monitors.iter().position(|lm| &lm.id == name) else { return; };
 
         let orig_x = layout_monitors[enabled_idx].x;
         let orig_y = layout_monitors[enabled_idx].y;
 
-        layout::auto_snap_all(&mut layout_monitors);
-        layout::resolve_overlaps(&mut layout_monitors, enabled_idx, orig_x, orig_y);
-        layout::normalize(&mut layout_monitors);
+        layout::auto_snap_all(&mut layout_monitors, self.config.gap);
+        layout::resolve_overlaps(&mut layout_monitors, enabled_idx, orig_x, orig_y, self.config.gap);
+        self.normalize(&mut layout_monitors);
         self.apply_layout_to_monitors(&layout_monitors);
     }
 
-    fn apply_layout_snap_all(&mut self) {
+    fn handle_align_key(&mut self, key: KeyEvent) {
+        self.overlay = Overlay::None;
+        let dir = match key.code {
+            KeyCode::Char('h') | KeyCode::Left => Direction::Left,
+            KeyCode::Char('j') | KeyCode::Down => Direction::Down,
+            KeyCode::Char('k') | KeyCode::Up => Direction::Up,
+            KeyCode::Char('l') | KeyCode::Right => Direction::Right,
+            _ => return,
+        };
+        self.align_selected(dir);
+    }
+
+    fn handle_color_key(&mut self, key: KeyEvent) {
+        let m = &mut self.monitors[self.selected];
+        match key.code {
+            KeyCode::Char('b') => m.toggle_bitdepth(),
+            KeyCode::Char('c') => m.cycle_cm(),
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('C') | KeyCode::Char('q') => {
+                self.overlay = Overlay::None;
+                return;
+            }
+            _ => return,
+        }
+        self.changed = true;
+        self.status_msg = format!("{}: {}", m.name, m.color_string());
+    }
+
+    /// Line up one edge of the selected monitor with the nearest monitor in `dir`.
+    fn align_selected(&mut self, dir: Direction) {
         let mut layout_monitors = self.build_layout_monitors();
-        if layout_monitors.is_empty() { return; }
+        let name = self.monitors[self.selected].name.clone();
+        let Some(enabled_idx) = layout_monitors.iter().position(|lm| lm.id == name) else {
+            self.status_msg = format!("{} isn't placed in the layout", name);
+            return;
+        };
 
-        layout::auto_snap_all(&mut layout_monitors);
-        layout::normalize(&mut layout_monitors);
+        let edge = match dir {
+            Direction::Left => "left",
+            Direction::Right => "right",
+            Direction::Up => "top",
+            Direction::Down => "bottom",
+        };
+        let Some(neighbor) = layout::align_edge(&mut layout_monitors, enabled_idx, dir, self.config.gap) else {
+            self.status_msg = format!("No monitor to align {}'s {} edge with", name, edge);
+            return;
+        };
+        let neighbor = layout_monitors[neighbor].id.clone();
+        layout::auto_snap_all(&mut layout_monitors, self.config.gap);
+        self.normalize(&mut layout_monitors);
         self.apply_layout_to_monitors(&layout_monitors);
+        self.changed = true;
+        self.status_msg = format!("Aligned {}'s {} edge with {}", name, edge, neighbor);
     }
 
-    // --- Mouse ---
-
-    fn terminal_to_monitor_coords(&self, col: u16, row: u16) -> Option<(f64, f64)> {
-        if col < self.canvas_area.x || col >= self.canvas_area.x + self.canvas_area.width
-            || row < self.canvas_area.y || row >= self.canvas_area.y + self.canvas_area.height
-        {
-            return None;
+    /// Make the selected monitor the primary one (at 0x0), or stop it being primary.
+    fn toggle_primary(&mut self) {
+        let m = &self.monitors[self.selected];
+        let name = m.name.clone();
+        if m.primary {
+            self.monitors[self.selected].primary = false;
+            self.status_msg = format!("{} is no longer primary", name);
+        } else if m.disabled || m.mirror_of.is_some() {
+            self.status_msg = format!("{} can't be primary while it's disabled or mirroring", name);
+            return;
+        } else {
+            for (i, m) in self.monitors.iter_mut().enumerate() {
+                m.primary = i == self.selected;
+            }
+            self.status_msg = format!("{} is primary and stays at 0x0", name);
         }
+        self.changed = true;
+        self.apply_layout_adjustments();
+    }
+
+    /// Move the selected monitor to (0, 0), or as close as it fits, and re-snap the rest.
+    fn move_to_origin(&mut self) {
+        let mut layout_monitors = self.build_layout_monitors();
+        let name = self.monitors[self.selected].name.clone();
+        let Some(enabled_idx) = layout_monitors.iter().position(|lm| lm.id == name) else {
+            self.status_msg = format!("{} isn't placed in the layout", name);
+            return;
+        };
+
+        layout_monitors[enabled_idx].x = 0;
+        layout_monitors[enabled_idx].y = 0;
+        layout::auto_snap_all(&mut layout_monitors, self.config.gap);
+        layout::resolve_overlaps(&mut layout_monitors, enabled_idx, 0, 0, self.config.gap);
+        self.normalize(&mut layout_monitors);
+        self.apply_layout_to_monitors(&layout_monitors);
+        self.changed = true;
+
+        let m = &self.monitors[self.selected];
+        self.status_msg = if m.x == 0 && m.y == 0 {
+            format!("{} moved to the origin", name)
+        } else {
+            format!("{} moved to {}x{}, the closest free spot to the origin", name, m.x, m.y)
+        };
+    }
+
+    /// Snap floating monitors back against the others and push apart any overlaps,
+    /// so the layout can be applied.
+    fn reconnect_layout(&mut self) {
+        let mut layout_monitors = self.build_layout_monitors();
+        if layout_monitors.is_empty() { return; }
 
-        let enabled: Vec<_> = self.monitors.iter().filter(|m| !m.disabled).collect();
-        if enabled.is_empty() { return None; }
+        layout::auto_snap_all(&mut layout_monitors, self.config.gap);
+        for i in 0..layout_monitors.len() {
+            let (x, y) = (layout_monitors[i].x, layout_monitors[i].y);
+            layout::resolve_overlaps(&mut layout_monitors, i, x, y, self.config.gap);
+        }
+        self.normalize(&mut layout_monitors);
+        self.apply_layout_to_monitors(&layout_monitors);
+        self.changed = true;
+        self.status_msg = if layout::is_layout_connected(&layout_monitors, self.config.gap) {
+            "Snapped every monitor into one layout".to_string()
+        } else {
+            "Couldn't connect every monitor — drag the floating one next to another".to_string()
+        };
+    }
 
-        let min_x = enabled.iter().map(|m| m.x).min().unwrap_or(0);
-        let max_x = enabled.iter().map(|m| m.x + m.logical_width()).max().unwrap_or(1920);
-        let min_y = enabled.iter().map(|m| m.y).min().unwrap_or(0);
-        let max_y = enabled.iter().map(|m| m.y + m.logical_height()).max().unwrap_or(1080);
+    fn apply_layout_snap_all(&mut self) {
+        let mut layout_monitors = self.build_layout_monitors();
+        if layout_monitors.is_empty() { return; }
 
-        let content_w = (max_x - min_x) as f64;
-        let content_h = (max_y - min_y) as f64;
-        if content_w <= 0.0 || content_h <= 0.0 { return None; }
+        layout::auto_snap_all(&mut layout_monitors, self.config.gap);
+        self.normalize(&mut layout_monitors);
+        self.apply_layout_to_monitors(&layout_monitors);
+    }
 
-        let inner_w = self.canvas_area.width.saturating_sub(2) as f64;
-        let inner_h = self.canvas_area.height.saturating_sub(2) as f64;
-        let click_x = (col - self.canvas_area.x).saturating_sub(1) as f64;
-        let click_y = (row - self.canvas_area.y).saturating_sub(1) as f64;
+    // --- Mouse ---
 
-        let char_aspect = 2.0;
-        let eff_w = inner_w;
-        let eff_h = inner_h * char_aspect;
-        let scale_x = eff_w / content_w;
-        let scale_y = eff_h / content_h;
-        let scale = scale_x.min(scale_y);
-        let scaled_w = content_w * scale;
-        let scaled_h = content_h * scale;
-        let pad_x = (eff_w - scaled_w) / 2.0;
-        let pad_y = (eff_h - scaled_h) / 2.0;
+    /// The canvas transform for the current layout, matching what the canvas pane draws.
+    fn canvas_transform(&self) -> Option<CanvasTransform> {
+        let enabled: Vec<_> = self.visible_monitors().into_iter()
+            .map(|i| &self.monitors[i])
+            .filter(|m| !m.disabled && m.mirror_of.is_none())
+            .collect();
+        CanvasTransform::new(&enabled, self.canvas_area, self.equalize_canvas, self.canvas_view, self.config.cell_aspect)
+    }
 
-        let mon_x = min_x as f64 + (click_x - pad_x) / scale;
-        let mon_y = min_y as f64 + (click_y * char_aspect - pad_y) / scale;
-        Some((mon_x, mon_y))
+    fn terminal_to_monitor_coords(&self, col: u16, row: u16) -> Option<(f64, f64)> {
+        self.canvas_transform()?.terminal_to_layout(col, row)
     }
 
     fn handle_mouse_down(&mut self, col: u16, row: u16) {
-        if matches!(self.overlay, Overlay::Confirm { .. } | Overlay::Presets { .. }) {
+        if self.overlay.is_modal() {
             return;
         }
 
@@ -427,13 +1023,20 @@ impl App {
         if col >= self.list_area.x && col < self.list_area.x + self.list_area.width
             && row >= self.list_area.y && row < self.list_area.y + self.list_area.height
         {
-            let content_y = row.saturating_sub(self.list_area.y + 1);
+            // Rows inside the border, counted from the first item scrolled into view;
+            // clicks on the border or below the last item select nothing
+            if row == self.list_area.y || row + 1 >= self.list_area.y + self.list_area.height {
+                return;
+            }
+            let content_y = row - self.list_area.y - 1;
+            let rows = self.list_area.height - 2;
             let mut y_offset = 0u16;
-            for i in self.visible_monitors() {
-                let m = &self.monitors[i];
-                let item_height: u16 = if m.disabled { 2 } else { 4 };
-                if content_y >= y_offset && content_y < y_offset + item_height {
-                    self.selected = i;
+            for (i, &item_height) in self.listed_monitors().into_iter().zip(&self.list_heights).skip(self.list_offset) {
+                if y_offset + item_height > rows {
+                    break;  // Not drawn: the list only shows items that fit whole
+                }
+                if content_y < y_offset + item_height {
+                    self.select(i);
                     return;
                 }
                 y_offset += item_height;
@@ -443,24 +1046,28 @@ impl App {
 
         // Check canvas pane click — start drag if monitor hit
         if let Some((mon_x, mon_y)) = self.terminal_to_monitor_coords(col, row) {
-            let enabled: Vec<_> = self.monitors.iter().enumerate()
-                .filter(|(_, m)| !m.disabled)
+            let enabled: Vec<_> = self.visible_monitors().into_iter()
+                .map(|i| (i, &self.monitors[i]))
+                .filter(|(_, m)| !m.disabled && m.mirror_of.is_none())
                 .collect();
 
-            for &(i, ref m) in &enabled {
+            for &(i, m) in &enabled {
                 let mx = m.x as f64;
                 let my = m.y as f64;
                 let mw = m.logical_width() as f64;
                 let mh = m.logical_height() as f64;
                 if mon_x >= mx && mon_x < mx + mw && mon_y >= my && mon_y < my + mh {
-                    self.selected = i;
-                    self.drag = Some(DragState {
+                    let drag = DragState {
                         monitor_idx: i,
                         offset_x: mon_x - mx,
                         offset_y: mon_y - my,
                         orig_x: m.x,
                         orig_y: m.y,
-                    });
+                    };
+                    self.select(i);
+                    if !self.read_only {
+                        self.drag = Some(drag);
+                    }
                     return;
                 }
             }
@@ -482,22 +1089,19 @@ impl App {
             self.monitors[idx].x = new_x;
             self.monitors[idx].y = new_y;
             self.changed = true;
+            let (dx, dy) = self.normalize_layout();
+            if let Some(drag) = &mut self.drag {
+                // Keep the fallback position in the same coordinates as everything else
+                drag.orig_x += dx;
+                drag.orig_y += dy;
+            }
         }
     }
 
     fn handle_mouse_up(&mut self) {
         if let Some(drag) = self.drag.take() {
-            let enabled_idx = self.monitors.iter()
-                .take(drag.monitor_idx + 1)
-                .filter(|m| !m.disabled)
-                .count()
-                .saturating_sub(1);
-
-            let mut layout_monitors = self.build_layout_monitors();
-            if enabled_idx < layout_monitors.len() {
-                layout::auto_snap_all(&mut layout_monitors);
-                layout::resolve_overlaps(&mut layout_monitors, enabled_idx, drag.orig_x, drag.orig_y);
-                layout::normalize(&mut layout_monitors);
+            if let Some((mut layout_monitors, _)) = self.snapped_layout(&drag) {
+                self.normalize(&mut layout_monitors);
                 self.apply_layout_to_monitors(&layout_monitors);
             }
             self.changed = true;
@@ -505,6 +1109,30 @@ impl App {
         }
     }
 
+    /// The layout a drag would snap to if released now, and the dragged monitor's index in it.
+    fn snapped_layout(&self, drag: &DragState) -> Option<(Vec<LayoutMonitor>, usize)> {
+        let name = &self.monitors[drag.monitor_idx].name;
+        let mut layout_monitors = self.build_layout_monitors();
+        let idx = layout_monitors.iter().position(|lm| &lm.id == name)?;
+        layout::auto_snap_all(&mut layout_monitors, self.config.gap);
+        layout::resolve_overlaps(&mut layout_monitors, idx, drag.orig_x, drag.orig_y, self.config.gap);
+        Some((layout_monitors, idx))
+    }
+
+    /// Where monitors will land when the current drag is released, as (x, y, width, height)
+    /// for each one that snapping moves. Usually that is the dragged monitor, but auto-snap
+    /// can pull a neighbour over to it instead.
+    pub fn drag_snap_targets(&self) -> Vec<(i32, i32, i32, i32)> {
+        let Some((layout_monitors, _)) = self.drag.as_ref().and_then(|d| self.snapped_layout(d)) else {
+            return Vec::new();
+        };
+        let current = self.build_layout_monitors();
+        layout_monitors.iter().zip(&current)
+            .filter(|(target, now)| (target.x, target.y) != (now.x, now.y))
+            .map(|(target, _)| (target.x, target.y, target.w, target.h))
+            .collect()
+    }
+
     // --- Confirm ---
 
     fn handle_confirm_key(&mut self, key: KeyEvent) -> bool {
@@ -521,14 +1149,16 @@ impl App {
 
         match key.code {
             KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char(' ') | KeyCode::Enter => {
-                self.overlay = Overlay::None;
-                // Confirmed — update the initial state to this new config
-                self.initial_state = self.monitors.clone();
-                self.external_state = self.monitors.clone();
-                self.prev_state = None;
-                self.last_apply = Some(Instant::now());  // Extend grace period
-                preset::save_recent(&self.monitors);
-                self.status_msg = "Configuration saved!".to_string();
+                self.keep_applied();
+            }
+            // Keep it and go straight to naming it as a preset
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                self.keep_applied();
+                self.open_presets();
+                if let Overlay::Presets { saving, .. } = &mut self.overlay {
+                    *saving = true;
+                }
+                self.status_msg = format!("Kept! Name a preset to save it as. {}", apply::summary(&self.monitors));
             }
             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                 self.revert_changes();
@@ -539,12 +1169,25 @@ impl App {
         true
     }
 
+    /// Keep the applied configuration: it becomes the new baseline and the most recent apply.
+    fn keep_applied(&mut self) {
+        self.overlay = Overlay::None;
+        self.initial_state = self.monitors.clone();
+        self.external_state = self.monitors.clone();
+        self.prev_state = None;
+        self.last_apply = Some(Instant::now());  // Extend grace period
+        self.last_applied_at = Some((Instant::now(), clock::time_of_day(clock::now())));
+        preset::save_recent(&self.monitors);
+        preset::save_last_known_good(&self.monitors);
+        self.status_msg = format!("Configuration saved! {}", apply::summary(&self.monitors));
+    }
+
     fn revert_changes(&mut self) {
         // Revert to the state before apply (prev_state), or initial state as fallback
         let revert_to = self.prev_state.take()
             .unwrap_or_else(|| self.initial_state.clone());
         self.monitors = revert_to;
-        match apply::apply_monitors(&self.monitors) {
+        match self.apply_to_backend() {
             Ok(()) => {
                 // Update external state to reflect the revert, so we don't trigger false external change detection
                 self.external_state = self.monitors.clone();
@@ -562,27 +1205,35 @@ impl App {
 
     fn open_presets(&mut self) {
         let names = preset::list_presets();
+        let presets: Vec<_> = names.iter().map(|name| preset::load_preset(name).ok()).collect();
+        let trusted = names.iter()
+            .zip(&presets)
+            .filter(|(_, p)| p.as_ref().is_some_and(|p| p.trusted))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let details = presets.iter()
+            .map(|p| p.as_ref().map(preset::Preset::summary).unwrap_or_default())
+            .collect();
         self.overlay = Overlay::Presets {
             selected: 0,
             names,
+            trusted,
+            details,
             saving: false,
             input: String::new(),
+            renaming: None,
         };
     }
 
     fn handle_preset_key(&mut self, key: KeyEvent) {
-        if let Overlay::Presets { selected, names, .. } = &mut self.overlay {
+        if let Overlay::Presets { selected, names, trusted, .. } = &mut self.overlay {
             let total = 1 + names.len();
             match key.code {
-                KeyCode::Char('j') | KeyCode::Down => {
-                    if *selected < total.saturating_sub(1) {
-                        *selected += 1;
-                    }
+                KeyCode::Char('j') | KeyCode::Down if *selected < total.saturating_sub(1) => {
+                    *selected += 1;
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if *selected > 0 {
-                        *selected -= 1;
-                    }
+                KeyCode::Char('k') | KeyCode::Up if *selected > 0 => {
+                    *selected -= 1;
                 }
                 KeyCode::Char('y') | KeyCode::Char(' ') | KeyCode::Enter => {
                     let sel = *selected;
@@ -590,9 +1241,18 @@ impl App {
                     self.load_preset_entry(sel, &names_clone);
                 }
                 KeyCode::Char('s') => {
-                    if let Overlay::Presets { saving, input, .. } = &mut self.overlay {
+                    if let Overlay::Presets { saving, input, renaming, .. } = &mut self.overlay {
                         *saving = true;
                         *input = String::new();
+                        *renaming = None;
+                    }
+                }
+                KeyCode::Char('r') if *selected > 0 && *selected <= names.len() => {
+                    let name = names[*selected - 1].clone();
+                    if let Overlay::Presets { saving, input, renaming, .. } = &mut self.overlay {
+                        *saving = true;
+                        *input = name.clone();
+                        *renaming = Some(name);
                     }
                 }
                 KeyCode::Char('d') => {
@@ -604,6 +1264,35 @@ impl App {
                         self.open_presets();
                     }
                 }
+                KeyCode::Char('c') if *selected > 0 && *selected <= names.len() => {
+                    let name = names[*selected - 1].clone();
+                    match preset::duplicate_preset(&name) {
+                        Ok(copy) => {
+                            self.open_presets();
+                            if let Overlay::Presets { selected, names, .. } = &mut self.overlay {
+                                *selected = names.iter().position(|n| *n == copy).map_or(0, |i| i + 1);
+                            }
+                            self.status_msg = format!("Copied preset {} to {}", name, copy);
+                        }
+                        Err(e) => self.status_msg = format!("Error copying preset: {}", e),
+                    }
+                }
+                KeyCode::Char('t') if *selected > 0 && *selected <= names.len() => {
+                    let name = names[*selected - 1].clone();
+                    let trust = !trusted.contains(&name);
+                    match preset::set_trusted(&name, trust) {
+                        Ok(()) => {
+                            if trust {
+                                trusted.push(name.clone());
+                                self.status_msg = format!("{}: trusted, loads without confirmation", name);
+                            } else {
+                                trusted.retain(|n| *n != name);
+                                self.status_msg = format!("{}: untrusted, loads with confirmation", name);
+                            }
+                        }
+                        Err(e) => self.status_msg = format!("Error updating preset: {}", e),
+                    }
+                }
                 KeyCode::Esc => {
                     self.overlay = Overlay::None;
                 }
@@ -621,7 +1310,7 @@ impl App {
     }
 
     fn handle_save_key(&mut self, key: KeyEvent) {
-        if let Overlay::Presets { input, .. } = &mut self.overlay {
+        if let Overlay::Presets { input, renaming, .. } = &mut self.overlay {
             match key.code {
                 KeyCode::Char(c) => {
                     input.push(c);
@@ -629,17 +1318,35 @@ impl App {
                 KeyCode::Backspace => {
                     input.pop();
                 }
-                KeyCode::Enter => {
-                    if !input.is_empty() {
-                        let name = input.clone();
-                        match preset::save_preset(&name, &self.monitors) {
-                            Ok(()) => self.status_msg = format!("Saved preset: {}", name),
-                            Err(e) => self.status_msg = format!("Error saving: {}", e),
+                KeyCode::Enter if !input.is_empty() && renaming.is_some() => {
+                    let old = renaming.clone().unwrap_or_default();
+                    let new = match preset::rename_preset(&old, input) {
+                        Ok(new) => new,
+                        Err(e) => {
+                            // Stay in the dialog so the name can be fixed
+                            self.status_msg = format!("Error renaming: {}", e);
+                            return;
+                        }
+                    };
+                    self.open_presets();
+                    if let Overlay::Presets { selected, names, .. } = &mut self.overlay {
+                        let idx = names.iter().position(|n| *n == new);
+                        *selected = idx.map_or(0, |i| i + 1);
+                        if self.current_preset.as_ref().is_some_and(|(_, name)| *name == old) {
+                            self.current_preset = idx.map(|i| (i, new.clone()));
                         }
-                        self.overlay = Overlay::None;
                     }
+                    self.status_msg = format!("Renamed preset {} to {}", old, new);
                 }
-                KeyCode::Esc => {
+                KeyCode::Enter if !input.is_empty() => {
+                    let name = input.clone();
+                    match preset::save_preset(&name, &self.monitors) {
+                        Ok(()) => self.status_msg = format!("Saved preset: {}", name),
+                        Err(e) => self.status_msg = format!("Error saving: {}", e),
+                    }
+                    self.overlay = Overlay::None;
+                }
+                KeyCode::Esc => {
                     if let Overlay::Presets { saving, .. } = &mut self.overlay {
                         *saving = false;
                     }
@@ -652,11 +1359,7 @@ impl App {
     fn load_preset_entry(&mut self, idx: usize, names: &[String]) {
         if idx == 0 {
             if let Some(configs) = preset::load_recent() {
-                preset::apply_preset_to_monitors(&mut self.monitors, &configs);
-                self.apply_layout_snap_all();  // Auto-snap after loading preset
-                self.changed = true;
-                self.overlay = Overlay::None;
-                self.apply();  // Auto-apply preset
+                self.load_configs(&configs, false, None);
             } else {
                 self.status_msg = "No recent configuration found".to_string();
                 self.overlay = Overlay::None;
@@ -664,13 +1367,7 @@ impl App {
         } else if idx <= names.len() {
             let name = &names[idx - 1];
             match preset::load_preset(name) {
-                Ok(p) => {
-                    preset::apply_preset_to_monitors(&mut self.monitors, &p.monitors);
-                    self.apply_layout_snap_all();  // Auto-snap after loading preset
-                    self.changed = true;
-                    self.overlay = Overlay::None;
-                    self.apply();  // Auto-apply preset
-                }
+                Ok(p) => self.load_configs(&p.monitors, p.trusted, Some((idx - 1, name.clone()))),
                 Err(e) => {
                     self.status_msg = format!("Error loading preset: {}", e);
                     self.overlay = Overlay::None;
@@ -681,26 +1378,136 @@ impl App {
         }
     }
 
+    /// Load preset configs into the editor, unless that would leave no display, and show
+    /// what they change before applying. Trusted presets, and every preset with
+    /// `auto_apply_presets`, skip the preview; trusted ones also skip the confirm countdown.
+    fn load_configs(&mut self, configs: &[preset::MonitorConfig], trusted: bool, preset: Option<(usize, String)>) {
+        self.overlay = Overlay::None;
+        self.load_modes();  // Needed to restore the presets' chosen modes
+        let mut loaded = self.monitors.clone();
+        preset::apply_preset_to_monitors(&mut loaded, configs);
+        if let Err(e) = apply::validate_monitors(&loaded) {
+            self.status_msg = format!("Error: preset not loaded — {}", e);
+            return;
+        }
+        let previous = std::mem::replace(&mut self.monitors, loaded);
+        self.apply_layout_snap_all();  // Auto-snap after loading preset
+
+        if trusted || self.config.auto_apply_presets {
+            self.apply_preset(preset, trusted);
+            return;
+        }
+        let changes = apply::describe_changes(&self.external_state, &self.monitors, self.config.refresh_decimals);
+        self.overlay = Overlay::PresetPreview { changes, previous, preset, trusted };
+    }
+
+    fn apply_preset(&mut self, preset: Option<(usize, String)>, trusted: bool) {
+        self.current_preset = preset;
+        self.changed = true;
+        self.apply_with(trusted);
+    }
+
+    fn handle_preset_preview_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char(' ') | KeyCode::Enter => {
+                let Overlay::PresetPreview { preset, trusted, .. } = std::mem::replace(&mut self.overlay, Overlay::None) else { return; };
+                self.apply_preset(preset, trusted);
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                let Overlay::PresetPreview { previous, .. } = std::mem::replace(&mut self.overlay, Overlay::None) else { return; };
+                self.monitors = previous;
+                self.status_msg = "Preset not loaded".to_string();
+            }
+            _ => {}
+        }
+    }
+
+    /// Load and apply the next (or previous) saved preset, wrapping around.
+    fn cycle_preset(&mut self, forward: bool) {
+        let names = preset::list_presets();
+        if names.is_empty() {
+            self.status_msg = "No saved presets".to_string();
+            return;
+        }
+        let len = names.len();
+        let next = match (&self.current_preset, forward) {
+            (Some((i, _)), true) => (i + 1) % len,
+            (Some((i, _)), false) => (i + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        };
+        self.load_preset_entry(next + 1, &names);
+    }
+
     // --- Apply ---
 
     fn apply(&mut self) {
+        self.apply_with(false);
+    }
+
+    /// Apply the edits. Unless `trusted`, they must be confirmed (and any newly
+    /// enabled monitors verified) before they are kept.
+    fn apply_with(&mut self, trusted: bool) {
         if !self.changed {
             self.status_msg = "No changes to apply".to_string();
             return;
         }
+
+        // Hyprland accepts floating monitors, but the cursor then can't reach them directly
+        if !layout::is_layout_connected(&self.build_layout_monitors(), self.config.gap) {
+            self.status_msg = "Layout has a disconnected monitor — press 'a' to auto-snap".to_string();
+            return;
+        }
+
+        // Enabling an output that has since been unplugged fails or does nothing
+        let enabling = self.newly_enabled();
+        if !enabling.is_empty() {
+            let missing = monitor::disconnected(&enabling, &self.backend.fetch_monitors(false));
+            if !missing.is_empty() {
+                self.status_msg = format!("{} appears disconnected — enabling may fail", missing.join(", "));
+                self.overlay = Overlay::Disconnected { names: missing, trusted };
+                return;
+            }
+        }
+        self.apply_now(trusted);
+    }
+
+    /// Send the editor's monitors to the backend, running the configured hooks around it.
+    fn apply_to_backend(&mut self) -> Result<(), String> {
+        let (failures, post_apply) = hooks::apply_in_background(self.backend.as_ref(), &self.monitors, &self.config)?;
+        self.hook_error = (!failures.is_empty()).then(|| failures.join("; "));
+        self.post_apply_hook = post_apply;
+        Ok(())
+    }
+
+    /// Apply without the pre-apply checks.
+    fn apply_now(&mut self, trusted: bool) {
         self.prev_state = Some(self.initial_state.clone());
-        match apply::apply_monitors(&self.monitors) {
+        match self.apply_to_backend() {
             Ok(()) => {
                 // Update external state to reflect our changes, so we don't trigger false external change detection
                 self.external_state = self.monitors.clone();
                 self.last_apply = Some(Instant::now());  // Start grace period
-                self.overlay = Overlay::Confirm {
-                    countdown_start: Instant::now(),
-                    duration: CONFIRM_DURATION,
-                    ready_for_input: false,  // Will become true after a brief delay
-                };
-                self.status_msg = "Applied — confirm to keep".to_string();
+                self.last_applied_at = Some((Instant::now(), clock::time_of_day(clock::now())));
                 self.changed = false;
+
+                if trusted || !self.require_confirm {
+                    self.keep_applied();
+                    self.status_msg = format!("Applied and saved! {}", apply::summary(&self.monitors));
+                    return;
+                }
+
+                let newly_enabled = if self.config.verify_enabled {
+                    self.newly_enabled()
+                } else {
+                    Vec::new()
+                };
+
+                if newly_enabled.is_empty() {
+                    self.start_confirm();
+                } else {
+                    self.start_verify(newly_enabled);
+                }
             }
             Err(e) => {
                 self.status_msg = format!("Error applying: {}", e);
@@ -709,38 +1516,407 @@ impl App {
         }
     }
 
-    // --- Scale ---
+    /// Monitors enabled in the editor that were disabled in the baseline.
+    fn newly_enabled(&self) -> Vec<String> {
+        self.monitors.iter()
+            .filter(|m| !m.disabled)
+            .filter(|m| self.initial_state.iter().any(|i| i.name == m.name && i.disabled))
+            .map(|m| m.name.clone())
+            .collect()
+    }
 
-    fn cycle_scale(&mut self) {
+    fn handle_disconnected_key(&mut self, key: KeyEvent) {
+        let Overlay::Disconnected { trusted, .. } = self.overlay else { return; };
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.overlay = Overlay::None;
+                self.apply_now(trusted);
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                self.status_msg = "Apply cancelled".to_string();
+            }
+            _ => {}
+        }
+    }
+
+    fn start_confirm(&mut self) {
+        self.overlay = Overlay::Confirm {
+            countdown_start: Instant::now(),
+            duration: Duration::from_secs(self.config.confirm_duration),
+            ready_for_input: false,  // Will become true after a brief delay
+        };
+        self.status_msg = "Applied — confirm to keep".to_string();
+    }
+
+    // --- Verify newly enabled monitors ---
+
+    fn start_verify(&mut self, pending: Vec<String>) {
+        apply::flash_monitor(&pending[0]);
+        self.status_msg = format!("Can you see {}?", pending[0]);
+        self.overlay = Overlay::VerifyMonitor {
+            pending,
+            countdown_start: Instant::now(),
+            duration: Duration::from_secs(self.config.confirm_duration),
+            ready_for_input: false,
+        };
+    }
+
+    /// Record the answer for the monitor being verified. A monitor the user
+    /// can't see is disabled again right away so it can't leave them stranded.
+    fn answer_verify(&mut self, visible: bool) {
+        let mut pending = match &self.overlay {
+            Overlay::VerifyMonitor { pending, .. } => pending.clone(),
+            _ => return,
+        };
+        let name = pending.remove(0);
+
+        if !visible {
+            let mut without = self.monitors.clone();
+            if let Some(m) = without.iter_mut().find(|m| m.name == name) {
+                m.disabled = true;
+            }
+            // Nothing else may be left on (e.g. the laptop was turned off in the same edit),
+            // so go back to the layout from before the apply rather than to no display
+            if let Err(e) = apply::validate_monitors(&without) {
+                self.revert_changes();
+                self.status_msg = format!("Can't just disable {}: {} — reverted the whole change", name, e);
+                return;
+            }
+            self.monitors = without;
+            match self.apply_to_backend() {
+                Ok(()) => {
+                    self.external_state = self.monitors.clone();
+                    self.last_apply = Some(Instant::now());
+                }
+                Err(e) => {
+                    self.status_msg = format!("Error disabling {}: {}", name, e);
+                }
+            }
+        }
+
+        if pending.is_empty() {
+            self.start_confirm();
+            if !visible {
+                self.status_msg = format!("{} disabled again — confirm to keep the rest", name);
+            }
+        } else {
+            self.start_verify(pending);
+        }
+    }
+
+    // --- Mode picker ---
+
+    /// Fill in the monitors' available modes, which are left out of polls to keep them
+    /// cheap. They are fetched the first time any editing needs them, then cached until
+    /// the next pull from the system.
+    fn load_modes(&mut self) {
+        if self.monitors.iter().any(|m| !self.mode_cache.contains_key(&m.name)) {
+            for m in self.backend.fetch_monitors(true) {
+                self.mode_cache.insert(m.name, m.available_modes);
+            }
+            // Don't fetch again for monitors that have gone away
+            for m in &self.monitors {
+                self.mode_cache.entry(m.name.clone()).or_default();
+            }
+        }
+        for m in &mut self.monitors {
+            m.available_modes = self.mode_cache[&m.name].clone();
+        }
+    }
+
+    fn open_mode_picker(&mut self) {
+        self.load_modes();
+        let m = &self.monitors[self.selected];
+        if m.disabled { return; }
+        if m.available_modes.is_empty() {
+            self.status_msg = format!("{}: no modes reported", m.name);
+            return;
+        }
+        self.overlay = Overlay::ModePicker {
+            selected: m.current_mode_index().unwrap_or(0),
+        };
+    }
+
+    fn handle_mode_picker_key(&mut self, key: KeyEvent) {
+        let Overlay::ModePicker { selected } = &mut self.overlay else { return; };
+        let total = self.monitors[self.selected].available_modes.len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if *selected + 1 < total => *selected += 1,
+            KeyCode::Char('k') | KeyCode::Up if *selected > 0 => *selected -= 1,
+            KeyCode::PageDown => *selected = (*selected + 10).min(total.saturating_sub(1)),
+            KeyCode::PageUp => *selected = selected.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => *selected = 0,
+            KeyCode::End | KeyCode::Char('G') => *selected = total.saturating_sub(1),
+            KeyCode::Char('y') | KeyCode::Char(' ') | KeyCode::Enter => {
+                let idx = *selected;
+                self.overlay = Overlay::None;
+                self.monitors[self.selected].select_mode(idx);
+                self.changed = true;
+                self.apply_layout_adjustments();
+                self.status_msg = format!(
+                    "{}: {}",
+                    self.monitors[self.selected].name,
+                    self.monitors[self.selected].resolution_string(self.config.refresh_decimals)
+                );
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.overlay = Overlay::None,
+            _ => {}
+        }
+    }
+
+    // --- Mirroring ---
+
+    fn open_mirror_picker(&mut self) {
+        let m = &self.monitors[self.selected];
+        if m.disabled { return; }
+        let targets: Vec<String> = self.monitors.iter()
+            .filter(|t| t.name != m.name && !t.disabled && t.mirror_of.is_none())
+            .map(|t| t.name.clone())
+            .collect();
+        if targets.is_empty() && m.mirror_of.is_none() {
+            self.status_msg = format!("{}: no other enabled monitor to mirror", m.name);
+            return;
+        }
+        let selected = m.mirror_of.as_ref()
+            .and_then(|name| targets.iter().position(|t| t == name))
+            .map_or(0, |i| i + 1);
+        self.overlay = Overlay::MirrorPicker { selected, targets };
+    }
+
+    fn handle_mirror_picker_key(&mut self, key: KeyEvent) {
+        let Overlay::MirrorPicker { selected, targets } = &mut self.overlay else { return; };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if *selected < targets.len() => *selected += 1,
+            KeyCode::Char('k') | KeyCode::Up if *selected > 0 => *selected -= 1,
+            KeyCode::Char('y') | KeyCode::Char(' ') | KeyCode::Enter => {
+                let target = selected.checked_sub(1).map(|i| targets[i].clone());
+                self.overlay = Overlay::None;
+                let name = self.monitors[self.selected].name.clone();
+                // Anything mirroring this monitor can't follow it into a mirror chain
+                if target.is_some() {
+                    for m in self.monitors.iter_mut().filter(|m| m.mirror_of.as_ref() == Some(&name)) {
+                        m.mirror_of = None;
+                    }
+                }
+                self.monitors[self.selected].mirror_of = target.clone();
+                self.changed = true;
+                self.apply_layout_snap_all();
+                self.status_msg = match target {
+                    Some(t) => format!("{} mirrors {}", name, t),
+                    None => format!("{} no longer mirrors", name),
+                };
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.overlay = Overlay::None,
+            _ => {}
+        }
+    }
+
+    // --- Resize ---
+
+    fn resize_selected(&mut self, dw: i32, dh: i32) {
+        self.load_modes();
         let m = &mut self.monitors[self.selected];
         if m.disabled { return; }
-        let idx = SCALES.iter().position(|&s| (s - m.scale).abs() < 0.01).unwrap_or(0);
-        let next = (idx + 1) % SCALES.len();
-        m.scale = SCALES[next];
+        m.resize(dw, dh);
+        self.status_msg = if m.custom_mode {
+            format!(
+                "{}: {}x{} (custom size — apply fails unless the monitor supports this mode)",
+                m.name, m.width, m.height
+            )
+        } else {
+            format!("{}: {}", m.name, m.resolution_string(self.config.refresh_decimals))
+        };
         self.changed = true;
-        self.status_msg = format!("{}: scale {:.2}x", m.name, m.scale);
+        self.apply_layout_adjustments();
+    }
+
+    // --- Scale ---
+
+    fn cycle_scale(&mut self) {
+        if self.scale_locked() || self.monitors[self.selected].disabled { return; }
+        let idx = nearest_scale(self.monitors[self.selected].scale);
+        self.set_scale(SCALES[(idx + 1) % SCALES.len()]);
     }
 
     fn scale_up(&mut self) {
-        let m = &mut self.monitors[self.selected];
-        if m.disabled { return; }
-        let idx = SCALES.iter().position(|&s| (s - m.scale).abs() < 0.01).unwrap_or(0);
+        if self.scale_locked() || self.monitors[self.selected].disabled { return; }
+        let idx = nearest_scale(self.monitors[self.selected].scale);
         if idx < SCALES.len() - 1 {
-            m.scale = SCALES[idx + 1];
-            self.changed = true;
-            self.status_msg = format!("{}: scale {:.2}x", m.name, m.scale);
+            self.set_scale(SCALES[idx + 1]);
         }
     }
 
     fn scale_down(&mut self) {
-        let m = &mut self.monitors[self.selected];
-        if m.disabled { return; }
-        let idx = SCALES.iter().position(|&s| (s - m.scale).abs() < 0.01).unwrap_or(0);
+        if self.scale_locked() || self.monitors[self.selected].disabled { return; }
+        let idx = nearest_scale(self.monitors[self.selected].scale);
         if idx > 0 {
-            m.scale = SCALES[idx - 1];
-            self.changed = true;
-            self.status_msg = format!("{}: scale {:.2}x", m.name, m.scale);
+            self.set_scale(SCALES[idx - 1]);
+        }
+    }
+
+    /// Set the selected monitor's scale, rounded the way Hyprland will if
+    /// `scale_strategy` asks for it, so the editor shows what will actually happen.
+    fn set_scale(&mut self, scale: f32) {
+        let m = &mut self.monitors[self.selected];
+        m.scale = scale;
+        m.scale = m.effective_scale(self.config.scale_strategy);
+        self.status_msg = if (m.scale - scale).abs() > 0.001 {
+            format!("{}: scale {:.2}x (Hyprland rounds {:.2}x to this)", m.name, m.scale, scale)
+        } else {
+            format!("{}: scale {:.2}x", m.name, m.scale)
+        };
+        self.changed = true;
+        self.apply_layout_adjustments();  // The monitor's logical size changed
+    }
+
+    fn open_scale_input(&mut self) {
+        if self.scale_locked() || self.monitors[self.selected].disabled { return; }
+        self.overlay = Overlay::ScaleInput { input: String::new() };
+    }
+
+    fn handle_scale_input_key(&mut self, key: KeyEvent) {
+        let Overlay::ScaleInput { input } = &mut self.overlay else { return; };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => { input.pop(); }
+            KeyCode::Enter => match monitor::parse_scale(input) {
+                Ok(scale) => {
+                    self.overlay = Overlay::None;
+                    self.set_scale(scale);
+                }
+                Err(e) => self.status_msg = format!("Invalid scale: {}", e),
+            },
+            KeyCode::Esc => self.overlay = Overlay::None,
+            _ => {}
+        }
+    }
+
+    fn open_color(&mut self) {
+        let m = &self.monitors[self.selected];
+        if m.disabled {
+            self.status_msg = format!("{} is disabled — enable it to change its color settings", m.name);
+            return;
+        }
+        self.overlay = Overlay::Color;
+    }
+
+    fn open_workspace_input(&mut self) {
+        let m = &self.monitors[self.selected];
+        if m.disabled {
+            self.status_msg = format!("{} is disabled — enable it before assigning workspaces", m.name);
+            return;
+        }
+        let input = monitor::format_workspaces(&m.workspaces);
+        self.preview_workspaces(&input);
+        self.overlay = Overlay::WorkspaceInput { input };
+    }
+
+    fn handle_workspace_input_key(&mut self, key: KeyEvent) {
+        let Overlay::WorkspaceInput { input } = &mut self.overlay else { return; };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => { input.pop(); }
+            KeyCode::Enter => {
+                let name = self.monitors[self.selected].name.clone();
+                match monitor::parse_workspaces(input)
+                    .and_then(|ws| monitor::set_workspaces(&mut self.monitors, self.selected, ws))
+                {
+                    Ok(()) => {
+                        self.overlay = Overlay::None;
+                        self.changed = true;
+                        let ws = &self.monitors[self.selected].workspaces;
+                        self.status_msg = if ws.is_empty() {
+                            format!("Cleared workspaces from {}", name)
+                        } else {
+                            format!("Assigned WS {} to {}", monitor::format_workspaces(ws), name)
+                        };
+                    }
+                    Err(e) => self.status_msg = format!("Invalid workspaces: {}", e),
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                self.status_msg = "Workspaces unchanged".to_string();
+                return;
+            }
+            _ => return,
+        }
+        let input = input.clone();
+        self.preview_workspaces(&input);
+    }
+
+    fn open_reserved_input(&mut self) {
+        let m = &self.monitors[self.selected];
+        if m.disabled {
+            self.status_msg = format!("{} is disabled — enable it before reserving space on it", m.name);
+            return;
+        }
+        self.overlay = Overlay::ReservedInput { input: monitor::format_reserved(m.reserved) };
+    }
+
+    fn handle_reserved_input_key(&mut self, key: KeyEvent) {
+        let Overlay::ReservedInput { input } = &mut self.overlay else { return; };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => { input.pop(); }
+            KeyCode::Enter => match monitor::parse_reserved(input) {
+                Ok(reserved) => {
+                    self.overlay = Overlay::None;
+                    let m = &mut self.monitors[self.selected];
+                    if m.reserved != reserved {
+                        m.reserved = reserved;
+                        self.changed = true;
+                    }
+                    self.status_msg = match reserved {
+                        Some(_) => format!("{}: reserved {} (top bottom left right)", m.name, monitor::format_reserved(reserved)),
+                        None => format!("{}: no reserved area", m.name),
+                    };
+                }
+                Err(e) => self.status_msg = format!("Invalid reserved area: {}", e),
+            },
+            KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                self.status_msg = "Reserved area unchanged".to_string();
+            }
+            _ => {}
+        }
+    }
+
+    /// Show what the typed workspace list parses to, before it is committed.
+    fn preview_workspaces(&mut self, input: &str) {
+        self.status_msg = match monitor::parse_workspaces(input) {
+            Ok(ws) if ws.is_empty() => "No workspaces".to_string(),
+            Ok(ws) => format!("WS {}", ws.iter().map(u32::to_string).collect::<Vec<_>>().join(", ")),
+            Err(e) => format!("Invalid workspaces: {}", e),
+        };
+    }
+
+    /// True (with a status message) if the selected monitor's scale is locked.
+    fn scale_locked(&mut self) -> bool {
+        let name = &self.monitors[self.selected].name;
+        if self.config.scale_locked.contains(name) {
+            self.status_msg = format!("{}: scale locked", name);
+            return true;
         }
+        false
+    }
+
+    fn toggle_scale_lock(&mut self) {
+        let name = self.monitors[self.selected].name.clone();
+        let locked = if let Some(pos) = self.config.scale_locked.iter().position(|n| *n == name) {
+            self.config.scale_locked.remove(pos);
+            false
+        } else {
+            self.config.scale_locked.push(name.clone());
+            true
+        };
+        self.status_msg = match self.config.save_scale_locks() {
+            Ok(()) if locked => format!("{}: scale locked", name),
+            Ok(()) => format!("{}: scale unlocked", name),
+            Err(e) => format!("Error saving scale lock: {}", e),
+        };
     }
 
     fn toggle_show_all(&mut self) {
@@ -749,22 +1925,48 @@ impl App {
         // Just toggle the visibility flag - don't reload to preserve edits
         // Ensure selection is valid for visible monitors
         let visible_monitors = self.visible_monitors();
-        if visible_monitors.is_empty() {
-            self.selected = 0;
-        } else if self.selected >= self.monitors.len() {
+        if visible_monitors.is_empty() || self.selected >= self.monitors.len() {
             self.selected = 0;
         } else if !self.is_monitor_visible(self.selected) {
             // Selected monitor is now hidden, select first visible
             self.selected = visible_monitors[0];
         }
 
-        self.status_msg = if self.show_all_monitors {
-            "Showing all monitors (including HEADLESS)".to_string()
+        self.config.show_all_monitors = self.show_all_monitors;
+        self.status_msg = match self.config.save_show_all_monitors() {
+            Ok(()) if self.show_all_monitors => "Showing all monitors (including HEADLESS)".to_string(),
+            Ok(()) => "Showing active monitors only".to_string(),
+            Err(e) => format!("Error saving show_all_monitors: {}", e),
+        };
+    }
+
+    fn toggle_equalize_canvas(&mut self) {
+        self.equalize_canvas = !self.equalize_canvas;
+        self.status_msg = if self.equalize_canvas {
+            "Equalized view: monitor sizes are not to scale".to_string()
         } else {
-            "Showing active monitors only".to_string()
+            "To-scale view".to_string()
         };
     }
 
+    fn toggle_require_confirm(&mut self) {
+        self.require_confirm = !self.require_confirm;
+        self.status_msg = if self.require_confirm {
+            "Confirmation on: applies revert unless kept".to_string()
+        } else {
+            "Confirmation off: applies are kept immediately".to_string()
+        };
+    }
+
+    /// Scroll the list just far enough that the selected monitor is fully shown, without
+    /// leaving empty rows at the bottom. Runs before each draw, so every way of changing
+    /// the selection (or resizing the terminal) is covered.
+    pub fn scroll_list_to_selected(&mut self) {
+        let rows = self.list_area.height.saturating_sub(2);
+        let selected = self.listed_monitors().iter().position(|&i| i == self.selected);
+        self.list_offset = list_offset(&self.list_heights, rows, self.list_offset, selected);
+    }
+
     /// Returns indices of visible monitors based on show_all_monitors flag
     fn visible_monitors(&self) -> Vec<usize> {
         self.monitors
@@ -775,6 +1977,15 @@ impl App {
             .collect()
     }
 
+    /// The visible monitors the list shows: those matching the filter, if one is set.
+    /// The canvas still draws every visible monitor.
+    pub fn listed_monitors(&self) -> Vec<usize> {
+        self.visible_monitors()
+            .into_iter()
+            .filter(|&i| matches_filter(&self.monitors[i], &self.filter))
+            .collect()
+    }
+
     fn is_monitor_visible(&self, index: usize) -> bool {
         if index >= self.monitors.len() {
             return false;
@@ -793,26 +2004,44 @@ impl App {
 
     // --- External Change Detection ---
 
-    fn check_external_changes(&mut self) {
+    fn check_external_changes(&mut self, in_grace_period: bool) {
         // Always fetch all monitors to match our internal storage
-        let current_external = monitor::fetch_monitors_all();
-
-        // Compare with last known external state
-        if !monitors_equal(&self.external_state, &current_external) {
-            // If already showing ExternalChange overlay, just update silently to latest state
-            // This ensures user acts on the most recent change, not stale data
-            if matches!(self.overlay, Overlay::ExternalChange) {
-                self.external_state = current_external;
+        let current_external = self.backend.fetch_monitors(false);
+        let previous = self.external_state.clone();
+
+        // Compare with last known external state, which is always updated to the latest
+        // so the user acts on the most recent change, not stale data
+        if track_external_state(&mut self.external_state, current_external, in_grace_period) {
+            // Nothing to protect in read-only mode, so just follow the system
+            if self.read_only {
+                self.pull_external_state();
+                self.status_msg = format!("Monitor configuration changed: {}", apply::summary(&self.monitors));
+                return;
+            }
+
+            let decimals = self.config.refresh_decimals;
+            if let Overlay::ExternalChange { since, changes, .. } = &mut self.overlay {
+                // Already showing: list everything that changed since it opened
+                *changes = apply::describe_changes(since, &self.external_state, decimals);
             } else {
                 // New external change detected, show overlay
-                self.external_state = current_external;
-                self.overlay = Overlay::ExternalChange;
+                let changes = apply::describe_changes(&previous, &self.external_state, decimals);
+                self.overlay = Overlay::ExternalChange { confirming_pull: false, since: previous, changes };
                 self.status_msg = "External monitor configuration change detected!".to_string();
             }
         }
     }
 
-    fn handle_external_change_key(&mut self, key: KeyEvent) -> bool {
+    fn handle_external_change_key(&mut self, key: KeyEvent, quit_pending: bool) -> bool {
+        if let Overlay::ExternalChange { confirming_pull: confirming @ true, .. } = &mut self.overlay {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.pull_external_state(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => *confirming = false,
+                _ => {}
+            }
+            return true;
+        }
+
         match key.code {
             KeyCode::Char('o') | KeyCode::Char('O') => {
                 // Override - keep current edits, ignore external change
@@ -822,13 +2051,18 @@ impl App {
                 self.status_msg = "Keeping your current configuration (override) - press 'y' to reapply".to_string();
             }
             KeyCode::Char('p') | KeyCode::Char('P') => {
-                // Pull - reload from external state
-                self.monitors = self.external_state.clone();
-                self.initial_state = self.external_state.clone();
-                self.changed = false;
-                self.overlay = Overlay::None;
-                self.selected = self.selected.min(self.monitors.len().saturating_sub(1));
-                self.status_msg = "Pulled latest configuration from system".to_string();
+                if self.changed && self.config.confirm_pull {
+                    // Unsaved edits would be lost - ask first
+                    if let Overlay::ExternalChange { confirming_pull, .. } = &mut self.overlay {
+                        *confirming_pull = true;
+                    }
+                } else {
+                    self.pull_external_state();
+                }
+            }
+            KeyCode::Char('q') | KeyCode::Esc if self.changed && !quit_pending => {
+                self.quit_pending = true;
+                self.status_msg = "Unsaved changes — press q again to quit, o to keep them".to_string();
             }
             KeyCode::Char('q') | KeyCode::Esc => {
                 // Quit application
@@ -838,6 +2072,78 @@ impl App {
         }
         true
     }
+
+    /// Pull - reload from external state, discarding any unsaved edits
+    fn pull_external_state(&mut self) {
+        self.mode_cache.clear();  // A different monitor may be plugged in under the same name
+        let primary = self.monitors.iter().find(|m| m.primary).map(|m| m.name.clone());
+        let fullscreen_vrr: Vec<String> = self.monitors.iter()
+            .filter(|m| m.vrr == 2)
+            .map(|m| m.name.clone())
+            .collect();
+        let kept: Vec<_> = self.monitors.iter()
+            .map(|m| (m.name.clone(), m.cm.clone(), m.reserved))
+            .collect();
+        self.monitors = self.external_state.clone();
+        // Hyprland doesn't know which monitor is primary, nor report color management,
+        // our reserved areas or fullscreen-only VRR outside fullscreen, so keep our choice
+        for m in &mut self.monitors {
+            m.primary = Some(&m.name) == primary.as_ref();
+            if let Some((_, cm, reserved)) = kept.iter().find(|(name, _, _)| *name == m.name) {
+                m.cm = cm.clone();
+                m.reserved = *reserved;
+            }
+            if m.vrr == 0 && fullscreen_vrr.contains(&m.name) {
+                m.vrr = 2;
+            }
+        }
+        self.initial_state = self.external_state.clone();
+        self.changed = false;
+        self.overlay = Overlay::None;
+        self.selected = self.selected.min(self.monitors.len().saturating_sub(1));
+        self.status_msg = "Pulled latest configuration from system".to_string();
+    }
+}
+
+/// Whether the list filter `filter` keeps `m`: its name or description contains it,
+/// ignoring case. An empty filter keeps everything.
+fn matches_filter(m: &MonitorInfo, filter: &str) -> bool {
+    let filter = filter.to_lowercase();
+    m.name.to_lowercase().contains(&filter) || m.description.to_lowercase().contains(&filter)
+}
+
+/// First item to show in a list of items with `heights` in `rows` rows, starting from
+/// `offset` and moving as little as possible so `selected` fits and no rows go unused.
+fn list_offset(heights: &[u16], rows: u16, offset: usize, selected: Option<usize>) -> usize {
+    let span = |from: usize, to: usize| heights[from..=to].iter().sum::<u16>();
+    let mut offset = offset.min(heights.len().saturating_sub(1));
+    if let Some(selected) = selected {
+        offset = offset.min(selected);
+        while offset < selected && span(offset, selected) > rows {
+            offset += 1;
+        }
+    }
+    while offset > 0 && span(offset - 1, heights.len() - 1) <= rows {
+        offset -= 1;
+    }
+    offset
+}
+
+/// Index of the `SCALES` entry closest to `scale`, so cycling works from any scale.
+fn nearest_scale(scale: f32) -> usize {
+    (0..SCALES.len())
+        .min_by(|&a, &b| (SCALES[a] - scale).abs().total_cmp(&(SCALES[b] - scale).abs()))
+        .unwrap_or(0)
+}
+
+/// Replace the last known external state with a fresh poll. Returns true if it is an
+/// external change worth reporting. Within the grace period after our own apply,
+/// differences are taken on silently: Hyprland is still settling on what we applied,
+/// and adopting its result keeps that jitter from surfacing once the grace period ends.
+fn track_external_state(known: &mut Vec<MonitorInfo>, current: Vec<MonitorInfo>, in_grace_period: bool) -> bool {
+    let changed = !monitors_equal(known, &current);
+    *known = current;
+    changed && !in_grace_period
 }
 
 /// Compare two monitor lists for equality (ignores workspaces which change frequently)
@@ -907,7 +2213,11 @@ fn monitors_equal(a: &[MonitorInfo], b: &[MonitorInfo]) -> bool {
             // Keep strict position checks for disabled monitors.
             return false;
         }
-        if m1.scale != m2.scale {
+        if !m1.same_scale(m2.scale) {
+            return false;
+        }
+        // Hyprland reports e.g. 59.97 for a nominal 60Hz, so compare to one decimal
+        if (m1.refresh_rate * 10.0).round() != (m2.refresh_rate * 10.0).round() {
             return false;
         }
         if m1.disabled != m2.disabled {
@@ -920,3 +2230,104 @@ fn monitors_equal(a: &[MonitorInfo], b: &[MonitorInfo]) -> bool {
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_monitor(name: &str, x: i32) -> MonitorInfo {
+        MonitorInfo {
+            name: name.to_string(),
+            x,
+            dpms_status: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unchanged_poll_is_not_a_change() {
+        let mut known = vec![test_monitor("DP-1", 0), test_monitor("DP-2", 1920)];
+        let current = known.clone();
+        assert!(!track_external_state(&mut known, current, false));
+    }
+
+    #[test]
+    fn test_external_change_is_reported() {
+        let mut known = vec![test_monitor("DP-1", 0), test_monitor("DP-2", 1920)];
+        let moved = vec![test_monitor("DP-1", 0), test_monitor("DP-2", 1922)];
+        assert!(track_external_state(&mut known, moved.clone(), false));
+        assert_eq!(known, moved);
+    }
+
+    #[test]
+    fn test_jitter_in_grace_period_is_absorbed() {
+        let mut known = vec![test_monitor("DP-1", 0), test_monitor("DP-2", 1920)];
+        let settled = vec![test_monitor("DP-1", 0), test_monitor("DP-2", 1922)];
+
+        // Hyprland nudges a monitor right after our apply: taken on silently...
+        assert!(!track_external_state(&mut known, settled.clone(), true));
+        // ...so the same state doesn't show up as a change once the grace period is over
+        assert!(!track_external_state(&mut known, settled, false));
+    }
+
+    #[test]
+    fn test_refresh_rate_change_is_detected() {
+        let a = vec![test_monitor("DP-1", 0)];
+        let mut b = a.clone();
+        b[0].refresh_rate = 144.0;
+        assert!(!monitors_equal(&a, &b));
+    }
+
+    #[test]
+    fn test_hyprland_adjusted_scale_is_not_a_change() {
+        // monitors.conf keeps the requested 1.3, Hyprland reports the 1.333333 it picked
+        let mut a = vec![test_monitor("DP-1", 0)];
+        a[0].scale = 1.3;
+        let mut b = a.clone();
+        b[0].scale = 1.333_333_3;
+        assert!(monitors_equal(&a, &b));
+        b[0].scale = 1.5;
+        assert!(!monitors_equal(&a, &b));
+    }
+
+    #[test]
+    fn test_refresh_rate_noise_is_ignored() {
+        let a = vec![test_monitor("DP-1", 0)];
+        let mut b = a.clone();
+        b[0].refresh_rate = 59.97;
+        assert!(monitors_equal(&a, &b));
+    }
+
+    #[test]
+    fn test_uniform_shift_is_not_a_change() {
+        let mut known = vec![test_monitor("DP-1", 0), test_monitor("DP-2", 1920)];
+        let shifted = vec![test_monitor("DP-1", 100), test_monitor("DP-2", 2020)];
+        assert!(!track_external_state(&mut known, shifted, false));
+    }
+
+    #[test]
+    fn test_filter_matches_name_or_description() {
+        let mut m = test_monitor("HEADLESS-2", 0);
+        m.description = "Dell U2720Q".to_string();
+        assert!(matches_filter(&m, ""));
+        assert!(matches_filter(&m, "headless"));
+        assert!(matches_filter(&m, "u27"));
+        assert!(!matches_filter(&m, "DP-"));
+    }
+
+    #[test]
+    fn test_list_offset_keeps_selection_in_view() {
+        // Three monitors of 5 rows each and a disabled one (2 rows) in 12 rows
+        let heights = [5, 5, 2, 5];
+        assert_eq!(list_offset(&heights, 12, 0, Some(0)), 0);
+        assert_eq!(list_offset(&heights, 12, 0, Some(2)), 0);
+        assert_eq!(list_offset(&heights, 12, 0, Some(3)), 1);
+        // Moving back up scrolls only as far as needed
+        assert_eq!(list_offset(&heights, 12, 2, Some(1)), 1);
+        // Staying put while the selection is visible
+        assert_eq!(list_offset(&heights, 12, 1, Some(2)), 1);
+        // Everything fits: no offset
+        assert_eq!(list_offset(&heights, 40, 2, Some(3)), 0);
+        assert_eq!(list_offset(&[], 12, 3, None), 0);
+    }
+}
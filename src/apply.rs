@@ -1,6 +1,8 @@
-use crate::monitor::MonitorInfo;
+use crate::config::{Config, ScaleStrategy};
+use crate::layout::{self, LayoutMonitor};
+use crate::monitor::{self, MonitorInfo};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 fn monitors_conf_path() -> PathBuf {
@@ -10,13 +12,23 @@ fn monitors_conf_path() -> PathBuf {
         .join("monitors.conf")
 }
 
-/// Generate monitors.conf content from current monitor state.
-fn generate_monitors_conf(monitors: &[MonitorInfo]) -> String {
-    let mut lines = Vec::new();
-    lines.push("# Managed by monitui — https://github.com/nathaniel-fargo/monitui".to_string());
-    lines.push("# Manual edits will be overwritten on next apply.".to_string());
-    lines.push("# Disabled monitors are not persisted; they are applied at runtime only.".to_string());
-    lines.push(String::new());
+/// Where a hand-written monitors.conf is copied before monitui first overwrites it.
+fn backup_path() -> PathBuf {
+    monitors_conf_path().with_file_name("monitors.conf.monitui.bak")
+}
+
+/// First line of every monitors.conf monitui writes.
+const MANAGED_HEADER: &str = "# Managed by monitui";
+
+/// Generate monitors.conf content from current monitor state, as `monitor = ...` lines
+/// or, with `use_monitorv2`, as `monitorv2 { ... }` blocks.
+fn generate_monitors_conf(monitors: &[MonitorInfo], config: &Config) -> String {
+    let mut lines = vec![
+        format!("{} — https://github.com/nathaniel-fargo/monitui", MANAGED_HEADER),
+        "# Manual edits will be overwritten on next apply.".to_string(),
+        "# Disabled monitors are not persisted; they are applied at runtime only.".to_string(),
+        String::new(),
+    ];
 
     for m in monitors {
         if m.disabled {
@@ -24,74 +36,549 @@ fn generate_monitors_conf(monitors: &[MonitorInfo]) -> String {
         }
         let mode = m.mode_string();
         let pos = format!("{}x{}", m.x, m.y);
-        let scale = format_scale(m.scale);
-        let transform = format!("transform, {}", m.transform);
-        lines.push(format!("monitor = {}, {}, {}, {}, {}", m.name, mode, pos, scale, transform));
+        let scale = format_scale(m.effective_scale(config.scale_strategy));
+        if config.use_monitorv2 {
+            lines.push("monitorv2 {".to_string());
+            lines.push(format!("    output = {}", m.name));
+            lines.push(format!("    mode = {}", mode));
+            lines.push(format!("    position = {}", pos));
+            lines.push(format!("    scale = {}", scale));
+            lines.push(match &m.mirror_of {
+                Some(target) => format!("    mirror = {}", target),
+                None => format!("    transform = {}", m.transform),
+            });
+            for (key, value) in rule_options(m) {
+                lines.push(format!("    {} = {}", key, value));
+            }
+            lines.push("}".to_string());
+            continue;
+        }
+        let extra = match &m.mirror_of {
+            Some(target) => format!("mirror, {}", target),
+            None => format!("transform, {}", m.transform),
+        };
+        let options: String = rule_options(m).iter().map(|(key, value)| format!(", {}, {}", key, value)).collect();
+        lines.push(format!("monitor = {}, {}, {}, {}, {}{}", m.name, mode, pos, scale, extra, options));
+    }
+
+    // Reserved areas are separate rules that sit on top of the monitor's own, in either format
+    let reserved_rules: Vec<String> = monitors.iter()
+        .filter(|m| !m.disabled)
+        .filter_map(|m| {
+            let (top, bottom, left, right) = m.reserved?;
+            Some(format!("monitor = {}, addreserved, {}, {}, {}, {}", m.name, top, bottom, left, right))
+        })
+        .collect();
+    if !reserved_rules.is_empty() {
+        lines.push(String::new());
+        lines.extend(reserved_rules);
+    }
+
+    // Rules rather than one-off moves, so a workspace lands on its monitor even when it's
+    // created later (Hyprland ignores moving a workspace that doesn't exist yet)
+    let workspace_rules: Vec<String> = monitors.iter()
+        .filter(|m| !m.disabled)
+        .flat_map(|m| m.workspaces.iter().map(move |ws| format!("workspace = {}, monitor:{}", ws, m.name)))
+        .collect();
+    if !workspace_rules.is_empty() {
+        lines.push(String::new());
+        lines.extend(workspace_rules);
     }
 
     lines.push(String::new());
     lines.join("\n")
 }
 
+/// Trailing key/value options of a monitor rule. VRR is set when it is on or was chosen
+/// for the monitor; bit depth and color management only when they differ from Hyprland's
+/// defaults.
+fn rule_options(m: &MonitorInfo) -> Vec<(&'static str, String)> {
+    let mut options = Vec::new();
+    if m.vrr != 0 || m.vrr_set {
+        options.push(("vrr", m.vrr.to_string()));
+    }
+    if m.bitdepth == 10 {
+        options.push(("bitdepth", "10".to_string()));
+    }
+    if let Some(cm) = &m.cm {
+        options.push(("cm", cm.clone()));
+    }
+    options
+}
+
+/// Check that a layout is safe to apply: at least one output (physical or HEADLESS)
+/// must stay enabled, otherwise applying it would leave nothing to display on.
+pub fn validate_monitors(monitors: &[MonitorInfo]) -> Result<(), String> {
+    if monitors.iter().all(|m| m.disabled) {
+        return Err("This would disable every monitor, leaving no display".to_string());
+    }
+    for m in monitors.iter().filter(|m| !m.disabled) {
+        if let Some(target) = &m.mirror_of {
+            if !monitors.iter().any(|t| &t.name == target && !t.disabled) {
+                return Err(format!("{} mirrors {}, which is not enabled", m.name, target));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// One-line description of a layout: how many monitors are on, the size of the
+/// desktop they span (in logical pixels), and which are disabled.
+pub fn summary(monitors: &[MonitorInfo]) -> String {
+    let enabled: Vec<LayoutMonitor> = monitors.iter()
+        .filter(|m| !m.disabled)
+        .map(MonitorInfo::layout_rect)
+        .collect();
+
+    let mut summary = format!(
+        "{} monitor{} on",
+        enabled.len(),
+        if enabled.len() == 1 { "" } else { "s" }
+    );
+    if let Some((_, _, w, h)) = layout::bounding_box(&enabled) {
+        summary.push_str(&format!(", {}x{} desktop", w, h));
+    }
+    let disabled: Vec<&str> = monitors.iter()
+        .filter(|m| m.disabled)
+        .map(|m| m.name.as_str())
+        .collect();
+    if !disabled.is_empty() {
+        summary.push_str(&format!(", disabled: {}", disabled.join(", ")));
+    }
+    summary
+}
+
+/// One line per monitor that differs between `before` and `after` (matched by name),
+/// e.g. "DP-1: 1920x1080@60Hz → 2560x1440@144Hz, scale 1.00x → 1.50x" or "DP-2: removed".
+/// Disabled monitors that disappear aren't reported, as Hyprland may stop listing them.
+pub fn describe_changes(before: &[MonitorInfo], after: &[MonitorInfo], refresh_decimals: u8) -> Vec<String> {
+    let mut lines = Vec::new();
+    for old in before {
+        if !old.disabled && !after.iter().any(|m| m.name == old.name) {
+            lines.push(format!("{}: removed", old.name));
+        }
+    }
+    for new in after {
+        let Some(old) = before.iter().find(|m| m.name == new.name) else {
+            lines.push(format!("{}: added, {}", new.name, new.resolution_string(refresh_decimals)));
+            continue;
+        };
+        if new.disabled {
+            if !old.disabled {
+                lines.push(format!("{}: disabled", new.name));
+            }
+            continue;
+        }
+
+        let mut changes = Vec::new();
+        if old.disabled {
+            changes.push("enabled".to_string());
+        }
+        let (old_res, new_res) = (old.resolution_string(refresh_decimals), new.resolution_string(refresh_decimals));
+        if old.disabled || (old.width, old.height, old.refresh_rate) != (new.width, new.height, new.refresh_rate) {
+            changes.push(if old.disabled { new_res } else { format!("{} → {}", old_res, new_res) });
+        }
+        if old.disabled || (old.x, old.y) != (new.x, new.y) {
+            changes.push(format!("position {}x{}", new.x, new.y));
+        }
+        if (old.scale - new.scale).abs() > 0.001 {
+            changes.push(format!("scale {:.2}x → {:.2}x", old.scale, new.scale));
+        }
+        if old.transform != new.transform {
+            changes.push(format!("rotation {} → {}", old.rotation_string(), new.rotation_string()));
+        }
+        if (old.bitdepth, &old.cm) != (new.bitdepth, &new.cm) {
+            changes.push(format!("{} → {}", old.color_string(), new.color_string()));
+        }
+        if old.vrr != new.vrr {
+            changes.push(format!("{} → {}", old.vrr_string(), new.vrr_string()));
+        }
+        if old.reserved != new.reserved {
+            changes.push(match new.reserved {
+                Some(_) => format!("reserved {}", monitor::format_reserved(new.reserved)),
+                None => "no reserved area".to_string(),
+            });
+        }
+        if old.primary != new.primary {
+            changes.push(if new.primary { "primary" } else { "no longer primary" }.to_string());
+        }
+        if old.mirror_of != new.mirror_of {
+            changes.push(match &new.mirror_of {
+                Some(target) => format!("mirrors {}", target),
+                None => "stops mirroring".to_string(),
+            });
+        }
+        if !changes.is_empty() {
+            lines.push(format!("{}: {}", new.name, changes.join(", ")));
+        }
+    }
+    lines
+}
+
 /// Apply monitor configuration via hyprctl AND write monitors.conf.
-pub fn apply_monitors(monitors: &[MonitorInfo]) -> Result<(), String> {
-    // Write monitors.conf first so persisted state does not include disabled outputs.
+/// If Hyprland rejects part of it, the previous monitors.conf and live layout are put
+/// back so the outputs aren't left half-configured.
+pub fn apply_monitors(monitors: &[MonitorInfo], config: &Config) -> Result<(), String> {
     let conf_path = monitors_conf_path();
-    let content = generate_monitors_conf(monitors);
+    backup_hand_written(&conf_path, &backup_path())?;
+    let previous_conf = fs::read_to_string(&conf_path).ok();
+    let previous_live = pinned(monitor::fetch_monitors_all(false));
+
+    // Write monitors.conf first so persisted state does not include disabled outputs.
+    let content = generate_monitors_conf(monitors, config);
     fs::write(&conf_path, &content)
         .map_err(|e| format!("Failed to write {}: {}", conf_path.display(), e))?;
 
+    if let Err(e) = reload_and_run(monitors, config) {
+        return Err(match roll_back(&conf_path, previous_conf.as_deref(), &previous_live, config) {
+            Ok(()) => format!("{} (rolled back to the previous layout)", e),
+            Err(rollback) => format!("{}; rolling back also failed: {}", e, rollback),
+        });
+    }
+
+    if config.notify {
+        Command::new("notify-send")
+            .args(["monitui", "Monitor configuration applied"])
+            .output()
+            .ok();
+    }
+
+    Ok(())
+}
+
+/// Reload the written monitors.conf and apply the runtime rules for `monitors`.
+fn reload_and_run(monitors: &[MonitorInfo], config: &Config) -> Result<(), String> {
     // Reload Hyprland configuration so file-backed state is active first.
-    let reload_output = Command::new("hyprctl")
+    // The keywords below set the same rules, so this can be skipped on request.
+    if !config.skip_reload {
+        hyprctl_reload()?;
+    }
+
+    // Then apply runtime state (including temporary disables) on top of the persisted config,
+    // all in one hyprctl call so the outputs don't flicker through each intermediate layout.
+    run_batch(&batch_commands(monitors, config.scale_strategy))
+}
+
+fn run_batch(commands: &[String]) -> Result<(), String> {
+    let output = Command::new("hyprctl")
+        .args(["--batch", &commands.join(" ; ")])
+        .output()
+        .map_err(|e| format!("Failed to run hyprctl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "hyprctl --batch failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    match batch_failure(commands, &String::from_utf8_lossy(&output.stdout)) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Live monitors with their current mode pinned, so re-applying them sets that exact
+/// mode rather than "preferred".
+fn pinned(mut monitors: Vec<MonitorInfo>) -> Vec<MonitorInfo> {
+    for m in &mut monitors {
+        m.custom_mode = true;
+    }
+    monitors
+}
+
+/// Undo a failed apply: put the old monitors.conf back (or remove the new one if there
+/// was none) and re-issue the rules for the layout that was live before.
+fn roll_back(conf: &Path, previous_conf: Option<&str>, previous_live: &[MonitorInfo], config: &Config) -> Result<(), String> {
+    match previous_conf {
+        Some(content) => fs::write(conf, content),
+        None => fs::remove_file(conf),
+    }
+    .map_err(|e| format!("Failed to restore {}: {}", conf.display(), e))?;
+    if previous_live.is_empty() {
+        return Err("the previous layout couldn't be read".to_string());
+    }
+    if !config.skip_reload {
+        hyprctl_reload()?;
+    }
+    run_batch(&batch_commands(previous_live, ScaleStrategy::Exact))
+}
+
+/// Copy `conf` to `backup` unless it is missing or was written by monitui, so the
+/// user's own monitors.conf survives the first apply.
+fn backup_hand_written(conf: &Path, backup: &Path) -> Result<(), String> {
+    let Ok(existing) = fs::read_to_string(conf) else { return Ok(()) };
+    if existing.starts_with(MANAGED_HEADER) {
+        return Ok(());
+    }
+    fs::write(backup, existing)
+        .map_err(|e| format!("Failed to back up {} to {}: {}", conf.display(), backup.display(), e))
+}
+
+/// Put the monitors.conf backup back in place and reload Hyprland with it.
+/// Returns the restored file's path.
+pub fn restore_backup() -> Result<PathBuf, String> {
+    let (conf_path, backup) = (monitors_conf_path(), backup_path());
+    if !backup.exists() {
+        return Err(format!(
+            "No backup at {} — monitui only makes one when it overwrites a monitors.conf it didn't write",
+            backup.display()
+        ));
+    }
+    fs::copy(&backup, &conf_path)
+        .map_err(|e| format!("Failed to restore {}: {}", conf_path.display(), e))?;
+    hyprctl_reload()?;
+    Ok(conf_path)
+}
+
+/// A monitor rule read back from monitors.conf. Anything the rule doesn't set is `None`;
+/// the mode is left to the live state, since "preferred" has no size of its own.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartialMonitorConfig {
+    pub name: String,
+    pub position: Option<(i32, i32)>,
+    pub scale: Option<f32>,
+    pub transform: Option<u8>,
+    pub mirror_of: Option<String>,
+    pub vrr: Option<u8>,
+    pub bitdepth: Option<u8>,
+    pub cm: Option<String>,
+    pub reserved: Option<(u16, u16, u16, u16)>,
+}
+
+impl PartialMonitorConfig {
+    /// Set one rule option by keyword; unknown keywords and unparsable values are ignored.
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "output" => self.name = value.to_string(),
+            "position" => {
+                self.position = value.split_once('x')
+                    .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)));
+            }
+            "scale" => self.scale = value.parse().ok().filter(|s: &f32| *s > 0.0),
+            "transform" => self.transform = value.parse().ok().filter(|t: &u8| *t < 8),
+            "mirror" => self.mirror_of = Some(value.to_string()),
+            "vrr" => self.vrr = value.parse().ok().filter(|v: &u8| *v <= 2),
+            "bitdepth" => self.bitdepth = value.parse().ok().filter(|b: &u8| *b == 8 || *b == 10),
+            "cm" => self.cm = Some(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// Read the monitor rules from monitors.conf, if monitui wrote it. A hand-written file
+/// (or none at all) yields nothing: its rules may be sourced from elsewhere or partial.
+pub fn read_monitors_conf() -> Vec<PartialMonitorConfig> {
+    fs::read_to_string(monitors_conf_path())
+        .map(|content| parse_monitors_conf(&content))
+        .unwrap_or_default()
+}
+
+/// Parse `monitor = ...` lines and `monitorv2 { ... }` blocks from a monitors.conf carrying
+/// monitui's header. Comments and blank lines are skipped, as are other keywords.
+fn parse_monitors_conf(content: &str) -> Vec<PartialMonitorConfig> {
+    if !content.starts_with(MANAGED_HEADER) {
+        return Vec::new();
+    }
+    let mut rules = Vec::new();
+    let mut block: Option<PartialMonitorConfig> = None;
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(rule) = block.as_mut() {
+            if line == "}" {
+                rules.extend(block.take().filter(|r| !r.name.is_empty()));
+            } else if let Some((key, value)) = line.split_once('=') {
+                rule.set(key.trim(), value.trim());
+            }
+            continue;
+        }
+        if line.strip_prefix("monitorv2").is_some_and(|rest| rest.trim() == "{") {
+            block = Some(PartialMonitorConfig::default());
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        if key.trim() != "monitor" {
+            continue;
+        }
+        let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+        if fields.len() < 4 || fields[0].is_empty() {
+            continue;
+        }
+        if fields[1] == "addreserved" {
+            if let Ok(reserved) = monitor::parse_reserved(&fields[2..].join(" ")) {
+                rules.push(PartialMonitorConfig { name: fields[0].to_string(), reserved, ..Default::default() });
+            }
+            continue;
+        }
+        let mut rule = PartialMonitorConfig { name: fields[0].to_string(), ..Default::default() };
+        rule.set("position", fields[2]);
+        rule.set("scale", fields[3]);
+        for pair in fields[4..].chunks(2) {
+            if let [key, value] = pair {
+                rule.set(key, value);
+            }
+        }
+        rules.push(rule);
+    }
+    rules
+}
+
+/// Lay the rules from monitors.conf over freshly fetched monitors, by name. A scale
+/// Hyprland already rounded to fit the mode is left as reported.
+pub fn merge_conf_rules(monitors: &mut [MonitorInfo], rules: &[PartialMonitorConfig]) {
+    for rule in rules {
+        let Some(m) = monitors.iter_mut().find(|m| m.name == rule.name) else { continue };
+        if let Some((x, y)) = rule.position {
+            m.x = x;
+            m.y = y;
+        }
+        if let Some(scale) = rule.scale.filter(|scale| !m.same_scale(*scale)) {
+            m.scale = scale;
+        }
+        if let Some(transform) = rule.transform {
+            m.transform = transform;
+        }
+        if rule.mirror_of.is_some() {
+            m.mirror_of = rule.mirror_of.clone();
+        }
+        if let Some(vrr) = rule.vrr {
+            m.vrr = vrr;
+            m.vrr_set = true;
+        }
+        if let Some(bitdepth) = rule.bitdepth {
+            m.bitdepth = bitdepth;
+        }
+        if rule.cm.is_some() {
+            m.cm = rule.cm.clone();
+        }
+        if rule.reserved.is_some() {
+            m.reserved = rule.reserved;
+        }
+    }
+}
+
+fn hyprctl_reload() -> Result<(), String> {
+    let output = Command::new("hyprctl")
         .args(["reload"])
         .output()
         .map_err(|e| format!("Failed to run hyprctl reload: {}", e))?;
-    if !reload_output.status.success() {
+    if !output.status.success() {
         return Err(format!(
             "hyprctl reload failed: {}",
-            String::from_utf8_lossy(&reload_output.stderr).trim()
+            String::from_utf8_lossy(&output.stderr).trim()
         ));
     }
+    Ok(())
+}
 
-    // Then apply runtime state (including temporary disables) on top of the persisted config.
+/// The hyprctl commands that bring Hyprland to `monitors`: one `keyword monitor` rule
+/// per output, each followed by a rule binding each of its workspaces to it (for
+/// workspaces created later, and for when the reload is skipped) and a move of the
+/// workspace onto it (for workspaces that already exist).
+fn batch_commands(monitors: &[MonitorInfo], strategy: ScaleStrategy) -> Vec<String> {
+    let mut commands = Vec::new();
     for monitor in monitors {
-        let cmd = if monitor.disabled {
+        let rule = if monitor.disabled {
             format!("{},disable", monitor.name)
         } else {
             let mode = monitor.mode_string();
             let pos = format!("{}x{}", monitor.x, monitor.y);
-            let scale = format_scale(monitor.scale);
-            format!("{},{},{},{},transform,{}", monitor.name, mode, pos, scale, monitor.transform)
+            let scale = format_scale(monitor.effective_scale(strategy));
+            let options: String = rule_options(monitor).iter().map(|(key, value)| format!(",{},{}", key, value)).collect();
+            match &monitor.mirror_of {
+                Some(target) => format!("{},{},{},{},mirror,{}{}", monitor.name, mode, pos, scale, target, options),
+                None => format!("{},{},{},{},transform,{}{}", monitor.name, mode, pos, scale, monitor.transform, options),
+            }
         };
-
-        let output = Command::new("hyprctl")
-            .args(["keyword", "monitor", &cmd])
-            .output()
-            .map_err(|e| format!("Failed to run hyprctl: {}", e))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("hyprctl failed for {}: {}", monitor.name, stderr));
+        commands.push(format!("keyword monitor {}", rule));
+        if let (false, Some((top, bottom, left, right))) = (monitor.disabled, monitor.reserved) {
+            commands.push(format!("keyword monitor {},addreserved,{},{},{},{}", monitor.name, top, bottom, left, right));
         }
 
         if !monitor.disabled {
             for ws in &monitor.workspaces {
-                Command::new("hyprctl")
-                    .args(["dispatch", "moveworkspacetomonitor", &ws.to_string(), &monitor.name])
-                    .output()
-                    .ok();
+                commands.push(format!("keyword workspace {},monitor:{}", ws, monitor.name));
+                commands.push(format!("dispatch moveworkspacetomonitor {} {}", ws, monitor.name));
             }
         }
     }
+    commands
+}
 
-    Command::new("notify-send")
-        .args(["monitui", "Monitor configuration applied"])
+/// Find the first monitor rule that hyprctl rejected, given the `--batch` output, which
+/// has one reply per command ("ok" on success). Workspace rules and moves stay
+/// best-effort, as a workspace that can't be placed shouldn't undo the layout.
+fn batch_failure(commands: &[String], output: &str) -> Option<String> {
+    let replies: Vec<&str> = output.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    if replies.len() != commands.len() {
+        // Can't line replies up with commands; only complain if something went wrong
+        return replies.iter()
+            .any(|r| *r != "ok")
+            .then(|| format!("hyprctl --batch reported: {}", replies.join("; ")));
+    }
+    commands.iter().zip(&replies)
+        .find(|(cmd, reply)| cmd.starts_with("keyword monitor ") && **reply != "ok")
+        .map(|(cmd, reply)| format!("hyprctl failed on '{}': {}", cmd, reply))
+}
+
+/// Describe what `apply_monitors` would write and run, without doing any of it.
+pub fn dry_run(monitors: &[MonitorInfo], config: &Config) -> String {
+    let mut out = format!(
+        "# Would write {}:\n{}\n# Would run:\n",
+        monitors_conf_path().display(),
+        generate_monitors_conf(monitors, config)
+    );
+    if !config.skip_reload {
+        out.push_str("hyprctl reload\n");
+    }
+    let batch = batch_commands(monitors, config.scale_strategy).join(" ; ");
+    out.push_str(&format!("hyprctl --batch '{}'\n", batch));
+    out
+}
+
+/// Move Hyprland's focus (and cursor) to the given output.
+pub fn focus_monitor(name: &str) {
+    Command::new("hyprctl")
+        .args(["dispatch", "focusmonitor", name])
         .output()
         .ok();
+}
 
+/// Turn an output's screen off or back on (DPMS) without touching its place in the layout.
+pub fn set_dpms(name: &str, on: bool) -> Result<(), String> {
+    let output = Command::new("hyprctl")
+        .args(["dispatch", "dpms", if on { "on" } else { "off" }, name])
+        .output()
+        .map_err(|e| format!("Failed to run hyprctl: {}", e))?;
+    let reply = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || reply.trim() != "ok" {
+        return Err(format!("hyprctl dispatch dpms failed: {}", reply.trim()));
+    }
     Ok(())
 }
 
+/// Show a large notification on the given output so the user can tell whether it lights up.
+/// Focus briefly moves to that output, then returns to where it was.
+pub fn flash_monitor(name: &str) {
+    let previous = Command::new("hyprctl")
+        .args(["-j", "activeworkspace"])
+        .output()
+        .ok()
+        .and_then(|o| serde_json::from_slice::<serde_json::Value>(&o.stdout).ok())
+        .and_then(|v| v.get("monitor").and_then(|m| m.as_str()).map(str::to_string));
+
+    focus_monitor(name);
+    Command::new("hyprctl")
+        .args(["notify", "1", "5000", "rgb(ff00ff)", &format!("fontsize:40 monitui: can you see {}?", name)])
+        .output()
+        .ok();
+
+    if let Some(prev) = previous {
+        focus_monitor(&prev);
+    }
+}
+
 fn format_scale(scale: f32) -> String {
     if (scale - scale.round()).abs() < 0.001 {
         format!("{}", scale as u32)
@@ -102,27 +589,72 @@ fn format_scale(scale: f32) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::generate_monitors_conf;
+    use super::{backup_hand_written, batch_commands, MANAGED_HEADER, batch_failure, describe_changes, dry_run, generate_monitors_conf, merge_conf_rules, parse_monitors_conf, pinned, summary, validate_monitors};
+    use crate::config::Config;
+    use crate::config::ScaleStrategy;
     use crate::monitor::MonitorInfo;
 
     fn test_monitor(name: &str, disabled: bool) -> MonitorInfo {
         MonitorInfo {
             name: name.to_string(),
             description: "Test monitor".to_string(),
-            width: 1920,
-            height: 1080,
-            refresh_rate: 60.0,
-            x: 0,
-            y: 0,
-            scale: 1.0,
             disabled,
-            transform: 0,
-            workspaces: vec![],
-            available_modes: vec![],
-            selected_mode: None,
+            dpms_status: Some(true),
+            ..Default::default()
         }
     }
 
+    #[test]
+    fn validate_rejects_all_disabled() {
+        let monitors = vec![
+            test_monitor("DP-1", true),
+            test_monitor("HDMI-A-1", true),
+        ];
+        assert!(validate_monitors(&monitors).is_err());
+    }
+
+    #[test]
+    fn validate_allows_headless_only() {
+        let monitors = vec![
+            test_monitor("DP-1", true),
+            test_monitor("HEADLESS-2", false),
+        ];
+        assert!(validate_monitors(&monitors).is_ok());
+    }
+
+    #[test]
+    fn summary_lists_desktop_size_and_disabled() {
+        let mut right = test_monitor("HDMI-A-1", false);
+        right.x = 1920;
+        right.scale = 2.0;
+        let monitors = vec![
+            test_monitor("DP-1", false),
+            right,
+            test_monitor("eDP-1", true),
+        ];
+        assert_eq!(summary(&monitors), "2 monitors on, 2880x1080 desktop, disabled: eDP-1");
+        assert_eq!(summary(&monitors[..1]), "1 monitor on, 1920x1080 desktop");
+    }
+
+    #[test]
+    fn validate_rejects_mirroring_a_disabled_monitor() {
+        let mut mirror = test_monitor("HDMI-A-1", false);
+        mirror.mirror_of = Some("eDP-1".to_string());
+        let mut monitors = vec![test_monitor("eDP-1", true), mirror];
+        assert!(validate_monitors(&monitors).is_err());
+        monitors[0].disabled = false;
+        assert!(validate_monitors(&monitors).is_ok());
+    }
+
+    #[test]
+    fn monitors_conf_writes_mirror_rule() {
+        let mut mirror = test_monitor("HDMI-A-1", false);
+        mirror.mirror_of = Some("eDP-1".to_string());
+        let content = generate_monitors_conf(&[test_monitor("eDP-1", false), mirror], &Config::default());
+        assert!(content.contains("monitor = HDMI-A-1, preferred, 0x0, 1, mirror, eDP-1"));
+        assert!(content.contains("monitor = eDP-1, preferred, 0x0, 1, transform, 0"));
+    }
+
     #[test]
     fn monitors_conf_excludes_disabled_monitors() {
         let monitors = vec![
@@ -130,10 +662,275 @@ mod tests {
             test_monitor("HDMI-A-1", true),
         ];
 
-        let content = generate_monitors_conf(&monitors);
+        let content = generate_monitors_conf(&monitors, &Config::default());
 
         assert!(content.contains("monitor = DP-1, preferred, 0x0, 1, transform, 0"));
         assert!(!content.contains("HDMI-A-1, disable"));
         assert!(!content.contains("monitor = HDMI-A-1"));
     }
+
+    #[test]
+    fn batch_commands_cover_rules_and_workspaces() {
+        let mut laptop = test_monitor("eDP-1", false);
+        laptop.workspaces = vec![1, 2];
+        let commands = batch_commands(&[laptop, test_monitor("DP-1", true)], ScaleStrategy::Exact);
+        assert_eq!(commands, vec![
+            "keyword monitor eDP-1,preferred,0x0,1,transform,0",
+            "keyword workspace 1,monitor:eDP-1",
+            "dispatch moveworkspacetomonitor 1 eDP-1",
+            "keyword workspace 2,monitor:eDP-1",
+            "dispatch moveworkspacetomonitor 2 eDP-1",
+            "keyword monitor DP-1,disable",
+        ]);
+    }
+
+    #[test]
+    fn monitors_conf_binds_workspaces_with_rules() {
+        let mut laptop = test_monitor("eDP-1", false);
+        laptop.workspaces = vec![1, 2];
+        let mut external = test_monitor("DP-1", true);
+        external.workspaces = vec![3];
+        for config in [Config::default(), Config { use_monitorv2: true, ..Config::default() }] {
+            let content = generate_monitors_conf(&[laptop.clone(), external.clone()], &config);
+            assert!(content.ends_with("\n\nworkspace = 1, monitor:eDP-1\nworkspace = 2, monitor:eDP-1\n"), "{}", content);
+            // A disabled monitor's workspaces go wherever Hyprland puts them
+            assert!(!content.contains("monitor:DP-1"));
+            // and the rules don't confuse the parser
+            assert_eq!(parse_monitors_conf(&content).len(), 1);
+        }
+        let bare = generate_monitors_conf(&[test_monitor("eDP-1", false)], &Config::default());
+        assert!(!bare.contains("workspace ="));
+    }
+
+    #[test]
+    fn rollback_rules_keep_the_live_mode() {
+        let mut live = test_monitor("DP-1", false);
+        live.width = 2560;
+        live.height = 1440;
+        live.refresh_rate = 143.97;
+        let commands = batch_commands(&pinned(vec![live, test_monitor("DP-2", true)]), ScaleStrategy::Exact);
+        assert_eq!(commands, vec![
+            "keyword monitor DP-1,2560x1440@143.97,0x0,1,transform,0",
+            "keyword monitor DP-2,disable",
+        ]);
+    }
+
+    #[test]
+    fn batch_failure_names_the_failing_rule() {
+        let commands: Vec<String> = vec![
+            "keyword monitor eDP-1,preferred,0x0,1,transform,0".into(),
+            "dispatch moveworkspacetomonitor 9 eDP-1".into(),
+            "keyword monitor DP-1,bogus".into(),
+        ];
+        assert_eq!(batch_failure(&commands, "ok\nok\nok\n"), None);
+        // A failed workspace move is tolerated
+        assert_eq!(batch_failure(&commands, "ok\n\ninvalid workspace\n\nok"), None);
+
+        let err = batch_failure(&commands, "ok\nok\ninvalid monitor rule").unwrap();
+        assert!(err.contains("keyword monitor DP-1,bogus"));
+        assert!(err.contains("invalid monitor rule"));
+
+        assert!(batch_failure(&commands, "error").is_some());
+    }
+
+    #[test]
+    fn describe_changes_lists_each_changed_monitor() {
+        let before = vec![test_monitor("eDP-1", false), test_monitor("DP-1", true), test_monitor("DP-2", false)];
+        let mut after = before.clone();
+        after[0].disabled = true;
+        after[1].disabled = false;
+        after[1].x = 1920;
+        after[2].scale = 1.5;
+        after[2].transform = 1;
+
+        assert_eq!(describe_changes(&before, &after, 0), vec![
+            "eDP-1: disabled",
+            "DP-1: enabled, 1920x1080@60Hz, position 1920x0",
+            "DP-2: scale 1.00x → 1.50x, rotation normal → 90°",
+        ]);
+        assert!(describe_changes(&before, &before, 0).is_empty());
+
+        let unplugged = vec![before[1].clone(), before[2].clone(), test_monitor("HDMI-A-1", false)];
+        assert_eq!(describe_changes(&before, &unplugged, 0), vec![
+            "eDP-1: removed",
+            "HDMI-A-1: added, 1920x1080@60Hz",
+        ]);
+        // A disabled monitor dropping out of the list isn't news
+        assert!(describe_changes(&before, &before[..1], 0).iter().all(|l| !l.starts_with("DP-1")));
+    }
+
+    #[test]
+    fn backup_only_copies_hand_written_conf() {
+        let dir = std::env::temp_dir().join(format!("monitui-backup-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (conf, backup) = (dir.join("monitors.conf"), dir.join("monitors.conf.monitui.bak"));
+
+        // Nothing to back up yet
+        backup_hand_written(&conf, &backup).unwrap();
+        assert!(!backup.exists());
+
+        // monitui's own output is regenerated, not backed up
+        std::fs::write(&conf, generate_monitors_conf(&[test_monitor("eDP-1", false)], &Config::default())).unwrap();
+        backup_hand_written(&conf, &backup).unwrap();
+        assert!(!backup.exists());
+
+        std::fs::write(&conf, "source = ~/.config/hypr/desk.conf\n").unwrap();
+        backup_hand_written(&conf, &backup).unwrap();
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "source = ~/.config/hypr/desk.conf\n");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dry_run_lists_conf_and_commands() {
+        let out = dry_run(&[test_monitor("eDP-1", false)], &Config::default());
+        assert!(out.contains("monitor = eDP-1, preferred, 0x0, 1, transform, 0"));
+        assert!(out.contains("\nhyprctl reload\n"));
+        assert!(out.contains("hyprctl --batch 'keyword monitor eDP-1,preferred,0x0,1,transform,0'"));
+
+        let config = Config { skip_reload: true, ..Config::default() };
+        assert!(!dry_run(&[test_monitor("eDP-1", false)], &config).contains("hyprctl reload"));
+    }
+
+    #[test]
+    fn monitors_conf_rounds_scale_when_asked() {
+        let mut monitor = test_monitor("DP-1", false);
+        monitor.width = 2560;
+        monitor.height = 1440;
+        monitor.scale = 1.5;
+
+        let exact = generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default());
+        assert!(exact.contains("monitor = DP-1, preferred, 0x0, 1.500000, transform, 0"));
+        let config = Config { scale_strategy: ScaleStrategy::HyprRounded, ..Config::default() };
+        let rounded = generate_monitors_conf(&[monitor], &config);
+        assert!(rounded.contains("monitor = DP-1, preferred, 0x0, 1.600000, transform, 0"));
+    }
+
+    #[test]
+    fn monitors_conf_one_line_and_monitorv2_forms() {
+        let mut monitor = test_monitor("DP-1", false);
+        monitor.x = 1920;
+        monitor.transform = 1;
+
+        let one_line = generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default());
+        assert!(one_line.contains("monitor = DP-1, preferred, 1920x0, 1, transform, 1\n"));
+        assert!(!one_line.contains("monitorv2"));
+
+        let config = Config { use_monitorv2: true, ..Config::default() };
+        let v2 = generate_monitors_conf(&[monitor], &config);
+        assert!(v2.contains(
+            "monitorv2 {\n    output = DP-1\n    mode = preferred\n    position = 1920x0\n    scale = 1\n    transform = 1\n}\n"
+        ));
+        assert!(!v2.contains("monitor = "));
+    }
+
+    #[test]
+    fn color_options_are_only_written_when_set() {
+        let mut monitor = test_monitor("DP-1", false);
+        monitor.bitdepth = 10;
+
+        let depth_only = generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default());
+        assert!(depth_only.contains("monitor = DP-1, preferred, 0x0, 1, transform, 0, bitdepth, 10\n"));
+        assert_eq!(
+            batch_commands(std::slice::from_ref(&monitor), ScaleStrategy::Exact)[0],
+            "keyword monitor DP-1,preferred,0x0,1,transform,0,bitdepth,10"
+        );
+
+        monitor.cm = Some("hdr".to_string());
+        let hdr = generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default());
+        assert!(hdr.contains("transform, 0, bitdepth, 10, cm, hdr\n"));
+        let config = Config { use_monitorv2: true, ..Config::default() };
+        let v2 = generate_monitors_conf(&[monitor], &config);
+        assert!(v2.contains("    bitdepth = 10\n    cm = hdr\n}\n"));
+    }
+
+    #[test]
+    fn vrr_is_only_written_when_on_or_chosen() {
+        let mut monitor = test_monitor("DP-1", false);
+        let conf = generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default());
+        assert!(!conf.contains("vrr"));
+
+        // Turning it off by hand overrides a global misc:vrr, so it is written
+        monitor.cycle_vrr();
+        monitor.cycle_vrr();
+        monitor.cycle_vrr();
+        assert_eq!(monitor.vrr, 0);
+        let conf = generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default());
+        assert!(conf.contains("monitor = DP-1, preferred, 0x0, 1, transform, 0, vrr, 0\n"));
+        assert_eq!(
+            batch_commands(std::slice::from_ref(&monitor), ScaleStrategy::Exact)[0],
+            "keyword monitor DP-1,preferred,0x0,1,transform,0,vrr,0"
+        );
+    }
+
+    #[test]
+    fn monitors_conf_round_trips_through_the_parser() {
+        let mut monitor = test_monitor("DP-1", false);
+        monitor.x = 1920;
+        monitor.y = -200;
+        monitor.scale = 1.5;
+        monitor.transform = 5;
+        monitor.vrr = 2;
+        monitor.bitdepth = 10;
+        monitor.cm = Some("hdr".to_string());
+        monitor.reserved = Some((30, 0, 0, 12));
+
+        for config in [Config::default(), Config { use_monitorv2: true, ..Config::default() }] {
+            let conf = generate_monitors_conf(std::slice::from_ref(&monitor), &config);
+            let rules = parse_monitors_conf(&conf);
+            assert_eq!(rules.len(), 2);  // The monitor rule and its addreserved rule
+            let mut fetched = test_monitor("DP-1", false);
+            merge_conf_rules(std::slice::from_mut(&mut fetched), &rules);
+            assert_eq!((fetched.x, fetched.y, fetched.scale, fetched.transform), (1920, -200, 1.5, 5));
+            assert_eq!((fetched.vrr, fetched.bitdepth, fetched.cm.as_deref()), (2, 10, Some("hdr")));
+            assert_eq!(fetched.reserved, Some((30, 0, 0, 12)));
+        }
+    }
+
+    #[test]
+    fn merge_keeps_the_scale_hyprland_rounded_to() {
+        let conf = format!("{}\nmonitor = DP-1, preferred, 0x0, 1.300000, transform, 0\n", MANAGED_HEADER);
+        let rules = parse_monitors_conf(&conf);
+        assert_eq!(rules.len(), 1);
+        let mut fetched = test_monitor("DP-1", false);
+        fetched.scale = 1.333_333_3;
+        merge_conf_rules(std::slice::from_mut(&mut fetched), &rules);
+        assert_eq!(fetched.scale, 1.333_333_3);
+
+        fetched.scale = 1.0;  // Not reloaded yet: the conf's scale is seeded
+        merge_conf_rules(std::slice::from_mut(&mut fetched), &rules);
+        assert_eq!(fetched.scale, 1.3);
+    }
+
+    #[test]
+    fn reserved_area_is_its_own_rule() {
+        let mut monitor = test_monitor("DP-1", false);
+        monitor.reserved = Some((30, 0, 0, 0));
+        let conf = generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default());
+        assert!(conf.contains("transform, 0\n\nmonitor = DP-1, addreserved, 30, 0, 0, 0\n"), "{}", conf);
+        let commands = batch_commands(std::slice::from_ref(&monitor), ScaleStrategy::Exact);
+        assert_eq!(commands[1], "keyword monitor DP-1,addreserved,30,0,0,0");
+
+        // Nothing is reserved on a disabled monitor
+        monitor.disabled = true;
+        assert!(!generate_monitors_conf(std::slice::from_ref(&monitor), &Config::default()).contains("addreserved"));
+        assert_eq!(batch_commands(&[monitor], ScaleStrategy::Exact).len(), 1);
+    }
+
+    #[test]
+    fn monitors_conf_parser_skips_comments_and_unknown_keywords() {
+        let conf = "# Managed by monitui\n\
+            # monitor = HDMI-A-1, preferred, 0x0, 2\n\
+            monitor = DP-1, 2560x1440@144, 0x0, 1.25, transform, 1, sdrbrightness, 1.2 # tweaked\n\
+            workspace = 1, monitor:DP-1\n\
+            monitor = eDP-1, preferred, auto, auto\n";
+        let rules = parse_monitors_conf(conf);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].name, "DP-1");
+        assert_eq!((rules[0].position, rules[0].scale, rules[0].transform), (Some((0, 0)), Some(1.25), Some(1)));
+        assert_eq!((rules[1].position, rules[1].scale), (None, None));
+
+        // Files monitui didn't write are left to the live state
+        assert!(parse_monitors_conf("monitor = DP-1, preferred, 0x0, 2\n").is_empty());
+    }
 }
@@ -0,0 +1,410 @@
+/// What a single monitui invocation should do, parsed from the command line.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Tui,
+    /// The TUI as a read-only dashboard that follows the live state.
+    Monitor,
+    Help,
+    List,
+    Presets,
+    Preset(String),
+    /// Apply whichever preset best fits the connected monitors.
+    Auto,
+    /// Stay running and apply the best-fitting preset whenever monitors are plugged in or out.
+    Watch,
+    SavePreset(String),
+    /// Write a saved preset's JSON to a file, e.g. to share it.
+    ExportPreset { name: String, path: String },
+    /// Add a preset from a JSON file, renamed if its name is taken.
+    ImportPreset(String),
+    Reload,
+    Undo,
+    Recover,
+    /// Put back the monitors.conf that monitui backed up before first overwriting it.
+    RestoreBackup,
+    SetWorkspace { workspace: u32, monitor: String },
+    ExportWallpaperLayout,
+    /// Write an SVG of the current layout, or of what a preset would produce.
+    Diagram { path: String, preset: Option<String> },
+    Batch(Vec<BatchOp>),
+    /// Print a completion script for the named shell.
+    Completions(String),
+}
+
+/// Modifiers that change how a command runs rather than what it does.
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    /// Apply even if the result would leave no monitor enabled.
+    pub force: bool,
+    /// Only report success or failure, without the resulting layout or a desktop notification.
+    pub quiet: bool,
+    /// Print what would be written and run instead of applying.
+    pub dry_run: bool,
+    /// Print `--list` as JSON.
+    pub json: bool,
+}
+
+/// A single monitor edit. Several of these can be chained and are applied together.
+#[derive(Debug, PartialEq)]
+pub enum BatchOp {
+    Enable(String),
+    Disable(String),
+    Scale(String, f32),
+    /// Rotation as a Hyprland transform, 0–3 (0°, 90°, 180°, 270°).
+    Rotate(String, u8),
+    /// Top-left corner in logical (scaled) pixels.
+    Position(String, i32, i32),
+}
+
+impl BatchOp {
+    pub fn monitor(&self) -> &str {
+        match self {
+            BatchOp::Enable(name) | BatchOp::Disable(name) | BatchOp::Scale(name, _) | BatchOp::Rotate(name, _) | BatchOp::Position(name, ..) => name,
+        }
+    }
+}
+
+/// Walks the arguments one flag at a time. `--flag=value` is split so its
+/// value is handed out before any following arguments.
+struct Parser<'a> {
+    args: std::slice::Iter<'a, String>,
+    inline: Option<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(args: &'a [String]) -> Self {
+        Parser { args: args.iter(), inline: None }
+    }
+
+    fn next_flag(&mut self) -> Result<Option<String>, String> {
+        if let Some(value) = self.inline.take() {
+            return Err(format!("Unexpected value '{}'", value));
+        }
+        let Some(arg) = self.args.next() else {
+            return Ok(None);
+        };
+        match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                self.inline = Some(value.to_string());
+                Ok(Some(flag.to_string()))
+            }
+            _ => Ok(Some(arg.clone())),
+        }
+    }
+
+    /// Take the next argument if it is a value rather than another flag.
+    fn optional_value(&mut self) -> Option<String> {
+        if let Some(value) = self.inline.take() {
+            return Some(value);
+        }
+        match self.args.clone().next() {
+            Some(v) if !v.starts_with("--") => self.args.next().cloned(),
+            _ => None,
+        }
+    }
+
+    /// Take the next value for `flag`, explaining `usage` if it is missing.
+    fn value(&mut self, flag: &str, what: &str, usage: &str) -> Result<String, String> {
+        if let Some(value) = self.inline.take() {
+            return Ok(value);
+        }
+        match self.args.next() {
+            Some(v) if !v.starts_with("--") => Ok(v.clone()),
+            _ => Err(format!("{} requires {}\nUsage: monitui {}", flag, what, usage)),
+        }
+    }
+}
+
+/// Parse the arguments after the program name into a `Command` and its `Options`.
+pub fn parse(args: &[String]) -> Result<(Command, Options), String> {
+    let mut parser = Parser::new(args);
+    let mut command: Option<Command> = None;
+    let mut ops = Vec::new();
+    let mut options = Options::default();
+
+    while let Some(flag) = parser.next_flag()? {
+        let standalone = match flag.as_str() {
+            "--help" | "-h" => return Ok((Command::Help, Options::default())),
+            "--list" => Command::List,
+            "--presets" => Command::Presets,
+            "--reload" => Command::Reload,
+            "--auto" => Command::Auto,
+            "--watch" => Command::Watch,
+            "--undo" => Command::Undo,
+            "--recover" => Command::Recover,
+            "--restore-backup" => Command::RestoreBackup,
+            "--monitor" => Command::Monitor,
+            "--completions" => {
+                let shell = parser.value(&flag, "a shell", "--completions <bash|zsh|fish>")?;
+                if !crate::completions::SHELLS.contains(&shell.as_str()) {
+                    return Err(format!("Unsupported shell '{}' (expected bash, zsh or fish)", shell));
+                }
+                Command::Completions(shell)
+            }
+            "--export-wallpaper-layout" => Command::ExportWallpaperLayout,
+            "--diagram" => {
+                let path = parser.value(&flag, "an output file", "--diagram <out.svg> [preset]")?;
+                Command::Diagram { path, preset: parser.optional_value() }
+            }
+            "--preset" => Command::Preset(parser.value(&flag, "a preset name", "--preset <name>")?),
+            "--save-preset" => Command::SavePreset(parser.value(&flag, "a preset name", "--save-preset <name>")?),
+            "--export-preset" => {
+                let usage = "--export-preset <name> <path>";
+                let name = parser.value(&flag, "a preset name and an output file", usage)?;
+                let path = parser.value(&flag, "a preset name and an output file", usage)?;
+                Command::ExportPreset { name, path }
+            }
+            "--import-preset" => Command::ImportPreset(parser.value(&flag, "a preset file", "--import-preset <path>")?),
+            "--set-workspace" => {
+                let usage = "--set-workspace <num> <monitor>";
+                let what = "workspace number and monitor name";
+                let raw = parser.value(&flag, what, usage)?;
+                let workspace = raw.parse()
+                    .map_err(|_| format!("Invalid workspace number '{}'", raw))?;
+                let monitor = parser.value(&flag, what, usage)?;
+                Command::SetWorkspace { workspace, monitor }
+            }
+            "--enable" => {
+                ops.push(BatchOp::Enable(parser.value(&flag, "a monitor name", "--enable <monitor>")?));
+                continue;
+            }
+            "--disable" => {
+                ops.push(BatchOp::Disable(parser.value(&flag, "a monitor name", "--disable <monitor>")?));
+                continue;
+            }
+            "--scale" => {
+                let usage = "--scale <monitor> <value>";
+                let name = parser.value(&flag, "a monitor name and a scale", usage)?;
+                let raw = parser.value(&flag, "a monitor name and a scale", usage)?;
+                let scale = crate::monitor::parse_scale(&raw)
+                    .map_err(|e| format!("Invalid scale '{}': {}", raw, e))?;
+                ops.push(BatchOp::Scale(name, scale));
+                continue;
+            }
+            "--rotate" => {
+                let usage = "--rotate <monitor> <0|90|180|270>";
+                let name = parser.value(&flag, "a monitor name and an angle", usage)?;
+                let raw = parser.value(&flag, "a monitor name and an angle", usage)?;
+                let transform = match raw.as_str() {
+                    "0" => 0,
+                    "90" => 1,
+                    "180" => 2,
+                    "270" => 3,
+                    _ => return Err(format!("Invalid angle '{}'\nUsage: monitui {}", raw, usage)),
+                };
+                ops.push(BatchOp::Rotate(name, transform));
+                continue;
+            }
+            "--set-position" => {
+                let usage = "--set-position <monitor> <x> <y>";
+                let what = "a monitor name and x y";
+                let name = parser.value(&flag, what, usage)?;
+                let mut coord = |axis: &str| -> Result<i32, String> {
+                    let raw = parser.value(&flag, what, usage)?;
+                    raw.parse().map_err(|_| format!("Invalid {} position '{}' (whole logical pixels, e.g. -1920)", axis, raw))
+                };
+                let (x, y) = (coord("x")?, coord("y")?);
+                ops.push(BatchOp::Position(name, x, y));
+                continue;
+            }
+            // Applying always happens once at the end; the flag just makes it explicit
+            "--apply" => continue,
+            "--force" => {
+                options.force = true;
+                continue;
+            }
+            "--quiet" => {
+                options.quiet = true;
+                continue;
+            }
+            "--dry-run" => {
+                options.dry_run = true;
+                continue;
+            }
+            "--json" => {
+                options.json = true;
+                continue;
+            }
+            other => return Err(format!("Unknown option '{}'", other)),
+        };
+
+        if command.is_some() {
+            return Err(format!("'{}' can't be combined with other options", flag));
+        }
+        command = Some(standalone);
+    }
+
+    if options.dry_run && !matches!(command, Some(Command::Preset(_) | Command::Reload | Command::Auto | Command::Watch)) {
+        return Err("--dry-run only works with --preset, --auto, --watch or --reload".to_string());
+    }
+
+    if options.json && command != Some(Command::List) {
+        return Err("--json only works with --list".to_string());
+    }
+
+    let command = match (command, ops.is_empty()) {
+        (Some(_), false) => return Err("--enable, --disable, --scale, --rotate and --set-position can't be combined with other options".to_string()),
+        (Some(command), true) => command,
+        (None, false) => Command::Batch(ops),
+        (None, true) if args.is_empty() => Command::Tui,
+        (None, true) if options.force => return Err("--force needs a command to apply".to_string()),
+        (None, true) if options.quiet => return Err("--quiet needs a command to apply".to_string()),
+        (None, true) => return Err("--apply needs --enable, --disable, --scale, --rotate or --set-position to apply".to_string()),
+    };
+    Ok((command, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(s: &str) -> Result<Command, String> {
+        parse_full(s).map(|(command, _)| command)
+    }
+
+    fn parse_full(s: &str) -> Result<(Command, Options), String> {
+        let args: Vec<String> = s.split_whitespace().map(String::from).collect();
+        parse(&args)
+    }
+
+    #[test]
+    fn test_no_args_launches_tui() {
+        assert_eq!(parse_str(""), Ok(Command::Tui));
+    }
+
+    #[test]
+    fn test_existing_flags() {
+        assert_eq!(parse_str("-h"), Ok(Command::Help));
+        assert_eq!(parse_str("--list"), Ok(Command::List));
+        assert_eq!(parse_str("--presets"), Ok(Command::Presets));
+        assert_eq!(parse_str("--reload"), Ok(Command::Reload));
+        assert_eq!(parse_str("--auto"), Ok(Command::Auto));
+        assert_eq!(parse_str("--watch"), Ok(Command::Watch));
+        assert_eq!(parse_str("--undo"), Ok(Command::Undo));
+        assert_eq!(parse_str("--recover"), Ok(Command::Recover));
+        assert_eq!(parse_str("--restore-backup"), Ok(Command::RestoreBackup));
+        assert_eq!(parse_str("--monitor"), Ok(Command::Monitor));
+        assert_eq!(parse_str("--export-wallpaper-layout"), Ok(Command::ExportWallpaperLayout));
+        assert_eq!(
+            parse_str("--diagram desk.svg"),
+            Ok(Command::Diagram { path: "desk.svg".to_string(), preset: None })
+        );
+        assert_eq!(
+            parse_str("--diagram=desk.svg laptop --force"),
+            Ok(Command::Diagram { path: "desk.svg".to_string(), preset: Some("laptop".to_string()) })
+        );
+        assert_eq!(parse_str("--preset laptop"), Ok(Command::Preset("laptop".to_string())));
+        assert_eq!(parse_str("--save-preset desk"), Ok(Command::SavePreset("desk".to_string())));
+        assert_eq!(
+            parse_str("--export-preset desk /tmp/desk.json"),
+            Ok(Command::ExportPreset { name: "desk".to_string(), path: "/tmp/desk.json".to_string() })
+        );
+        assert_eq!(parse_str("--import-preset desk.json"), Ok(Command::ImportPreset("desk.json".to_string())));
+        assert_eq!(
+            parse_str("--set-workspace 5 DP-1"),
+            Ok(Command::SetWorkspace { workspace: 5, monitor: "DP-1".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_equals_syntax() {
+        assert_eq!(parse_str("--preset=laptop"), Ok(Command::Preset("laptop".to_string())));
+        assert_eq!(
+            parse_str("--set-workspace=5 DP-1"),
+            Ok(Command::SetWorkspace { workspace: 5, monitor: "DP-1".to_string() })
+        );
+        assert_eq!(
+            parse_str("--scale=DP-1 1.5"),
+            Ok(Command::Batch(vec![BatchOp::Scale("DP-1".to_string(), 1.5)]))
+        );
+        assert!(parse_str("--list=yes").is_err());
+    }
+
+    #[test]
+    fn test_set_position_takes_negative_coordinates() {
+        assert_eq!(
+            parse_str("--set-position DP-2 -1920 -200"),
+            Ok(Command::Batch(vec![BatchOp::Position("DP-2".to_string(), -1920, -200)]))
+        );
+    }
+
+    #[test]
+    fn test_batch_keeps_order() {
+        assert_eq!(
+            parse_str("--disable eDP-1 --enable=DP-1 --scale DP-1 1.5 --rotate DP-1 270 --apply"),
+            Ok(Command::Batch(vec![
+                BatchOp::Disable("eDP-1".to_string()),
+                BatchOp::Enable("DP-1".to_string()),
+                BatchOp::Scale("DP-1".to_string(), 1.5),
+                BatchOp::Rotate("DP-1".to_string(), 3),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse_str("--enable").is_err());
+        assert!(parse_str("--save-preset").is_err());
+        assert!(parse_str("--export-preset desk").is_err());
+        assert!(parse_str("--enable --disable DP-1").is_err());
+        assert!(parse_str("--scale DP-1").is_err());
+        assert!(parse_str("--scale DP-1 big").is_err());
+        assert!(parse_str("--scale DP-1 0").is_err());
+        assert!(parse_str("--scale DP-1 9").is_err());
+        assert!(parse_str("--rotate DP-1").is_err());
+        assert!(parse_str("--rotate DP-1 45").is_err());
+        assert!(parse_str("--set-position DP-1 100").is_err());
+        assert!(parse_str("--completions").is_err());
+        assert!(parse_str("--completions powershell").is_err());
+        assert!(parse_str("--set-position DP-1 1.5 0").is_err());
+        assert!(parse_str("--set-workspace five DP-1").is_err());
+        assert!(parse_str("--enable DP-1 --bogus").is_err());
+        assert!(parse_str("--apply").is_err());
+        assert!(parse_str("--list --presets").is_err());
+        assert!(parse_str("--list --enable DP-1").is_err());
+    }
+
+    #[test]
+    fn test_force_modifier() {
+        assert_eq!(
+            parse_full("--preset headless --force"),
+            Ok((Command::Preset("headless".to_string()), Options { force: true, ..Options::default() }))
+        );
+        assert_eq!(
+            parse_full("--quiet --preset desk"),
+            Ok((Command::Preset("desk".to_string()), Options { quiet: true, ..Options::default() }))
+        );
+        assert_eq!(parse_full("--list").unwrap().1, Options::default());
+        assert!(parse_str("--force").is_err());
+        assert!(parse_str("--quiet").is_err());
+    }
+
+    #[test]
+    fn test_dry_run_modifier() {
+        assert_eq!(
+            parse_full("--preset desk --dry-run"),
+            Ok((Command::Preset("desk".to_string()), Options { dry_run: true, ..Options::default() }))
+        );
+        assert!(parse_full("--dry-run --reload").unwrap().1.dry_run);
+        assert!(parse_full("--auto --dry-run").unwrap().1.dry_run);
+        assert!(parse_full("--watch --dry-run").unwrap().1.dry_run);
+        assert!(parse_str("--dry-run").is_err());
+        assert!(parse_str("--undo --dry-run").is_err());
+        assert!(parse_str("--enable DP-1 --dry-run").is_err());
+    }
+
+    #[test]
+    fn test_json_modifier() {
+        assert_eq!(
+            parse_full("--list --json"),
+            Ok((Command::List, Options { json: true, ..Options::default() }))
+        );
+        assert!(parse_str("--json").is_err());
+        assert!(parse_str("--presets --json").is_err());
+    }
+
+    #[test]
+    fn test_help_wins_over_other_flags() {
+        assert_eq!(parse_str("--enable DP-1 --help"), Ok(Command::Help));
+    }
+}
@@ -0,0 +1,349 @@
+use std::cell::RefCell;
+use std::env;
+use std::process::{self, Command};
+use std::time::{Duration, Instant};
+
+use crate::apply;
+use crate::config::{BackendChoice, Config};
+use crate::monitor::{self, AvailableMode, MonitorInfo};
+
+/// The compositor-facing side of monitui: reading the outputs and applying a layout.
+pub trait Backend {
+    /// Read the outputs. `available_modes` are only filled in `with_modes`.
+    fn fetch_monitors(&self, with_modes: bool) -> Vec<MonitorInfo>;
+    fn apply(&self, monitors: &[MonitorInfo], config: &Config) -> Result<(), String>;
+    /// What `apply` would do, for `--dry-run`.
+    fn dry_run(&self, monitors: &[MonitorInfo], config: &Config) -> Result<String, String>;
+}
+
+/// Pick the backend named in the config, or the one whose tool is installed.
+/// Hyprland wins when both are.
+pub fn select(config: &Config) -> Box<dyn Backend> {
+    let hyprland = || Box::new(HyprlandBackend { min_refresh: config.min_refresh, last_fetch: RefCell::new(None) });
+    let wlr_randr = || Box::new(WlrRandrBackend { min_refresh: config.min_refresh });
+    match config.backend {
+        BackendChoice::Hyprland => hyprland(),
+        BackendChoice::WlrRandr => wlr_randr(),
+        BackendChoice::Auto if !on_path("hyprctl") && on_path("wlr-randr") => wlr_randr(),
+        BackendChoice::Auto => hyprland(),
+    }
+}
+
+/// Tidy freshly read mode lists; see `MonitorInfo::prune_modes`.
+fn prune_modes(monitors: &mut [MonitorInfo], min_refresh: f32) {
+    for m in monitors {
+        m.prune_modes(min_refresh);
+    }
+}
+
+fn on_path(binary: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(binary).is_file()))
+}
+
+/// Hyprland via `hyprctl`, also writing `~/.config/hypr/monitors.conf`.
+pub struct HyprlandBackend {
+    min_refresh: f32,
+    /// The TUI polls far more often than anything changes, so most polls can skip the parse.
+    last_fetch: RefCell<Option<Fetch>>,
+}
+
+/// How long a fetch is reused without running `hyprctl` again, for the several reads a
+/// single keypress or poll can make.
+const FETCH_TTL: Duration = Duration::from_millis(500);
+
+/// One `hyprctl` read and what it parsed to.
+struct Fetch {
+    json: Vec<u8>,
+    with_modes: bool,
+    monitors: Vec<MonitorInfo>,
+    at: Instant,
+}
+
+impl HyprlandBackend {
+    /// `fetch_monitors` with the `hyprctl` read passed in: within `FETCH_TTL` of the last
+    /// fetch nothing is read, and output identical to the last read isn't parsed again.
+    fn fetch_with(&self, with_modes: bool, read: impl FnOnce() -> Vec<u8>) -> Vec<MonitorInfo> {
+        let mut last = self.last_fetch.borrow_mut();
+        let cached = last.as_mut().filter(|f| f.with_modes == with_modes);
+        if let Some(fetch) = cached.as_ref().filter(|f| f.at.elapsed() < FETCH_TTL) {
+            return fetch.monitors.clone();
+        }
+        let json = read();
+        if let Some(fetch) = cached.filter(|f| f.json == json) {
+            fetch.at = Instant::now();
+            return fetch.monitors.clone();
+        }
+        let mut monitors = monitor::parse_monitors(&json, with_modes);
+        prune_modes(&mut monitors, self.min_refresh);
+        *last = Some(Fetch { json, with_modes, monitors: monitors.clone(), at: Instant::now() });
+        monitors
+    }
+}
+
+impl Backend for HyprlandBackend {
+    fn fetch_monitors(&self, with_modes: bool) -> Vec<MonitorInfo> {
+        self.fetch_with(with_modes, monitor::hyprctl_monitors_json)
+    }
+
+    fn apply(&self, monitors: &[MonitorInfo], config: &Config) -> Result<(), String> {
+        // Whatever was read before no longer holds
+        self.last_fetch.replace(None);
+        apply::apply_monitors(monitors, config)
+    }
+
+    fn dry_run(&self, monitors: &[MonitorInfo], config: &Config) -> Result<String, String> {
+        Ok(apply::dry_run(monitors, config))
+    }
+}
+
+/// Sway and other wlroots compositors via `wlr-randr`. Changes are runtime only, and
+/// mirroring and workspaces aren't supported.
+pub struct WlrRandrBackend {
+    min_refresh: f32,
+}
+
+impl Backend for WlrRandrBackend {
+    fn fetch_monitors(&self, with_modes: bool) -> Vec<MonitorInfo> {
+        let output = match Command::new("wlr-randr").arg("--json").output() {
+            Ok(o) if o.status.success() => o,
+            _ => {
+                eprintln!("Failed to run wlr-randr --json");
+                process::exit(1);
+            }
+        };
+        let mut monitors = parse_wlr_randr(&output.stdout, with_modes);
+        prune_modes(&mut monitors, self.min_refresh);
+        monitors
+    }
+
+    fn apply(&self, monitors: &[MonitorInfo], config: &Config) -> Result<(), String> {
+        let args = wlr_randr_args(monitors, config)?;
+        let output = Command::new("wlr-randr")
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to run wlr-randr: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "wlr-randr failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+
+    fn dry_run(&self, monitors: &[MonitorInfo], config: &Config) -> Result<String, String> {
+        let args = wlr_randr_args(monitors, config)?;
+        Ok(format!("# Would run:\nwlr-randr {}\n", args.join(" ")))
+    }
+}
+
+/// wlr-randr transform names, indexed by Hyprland's transform number.
+const TRANSFORMS: &[&str] = &[
+    "normal", "90", "180", "270", "flipped", "flipped-90", "flipped-180", "flipped-270",
+];
+
+/// Parse the JSON printed by `wlr-randr --json`.
+fn parse_wlr_randr(json: &[u8], with_modes: bool) -> Vec<MonitorInfo> {
+    let raw: Vec<serde_json::Value> = match serde_json::from_slice(json) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to parse wlr-randr output: {}", e);
+            vec![]
+        }
+    };
+
+    let mut monitors = Vec::new();
+    for m in raw {
+        let Some(name) = m.get("name").and_then(|v| v.as_str()) else { continue };
+
+        let modes: Vec<&serde_json::Value> = m.get("modes")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().collect())
+            .unwrap_or_default();
+        let flag = |mode: &serde_json::Value, key: &str| mode.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        // Disabled outputs have no current mode; show the one they'd come up in
+        let shown = modes.iter().find(|mode| flag(mode, "current"))
+            .or_else(|| modes.iter().find(|mode| flag(mode, "preferred")))
+            .or(modes.first());
+
+        let available_modes: Vec<AvailableMode> = modes.iter()
+            .filter(|_| with_modes)
+            .filter_map(|mode| Some(AvailableMode {
+                width: mode.get("width")?.as_u64()? as u32,
+                height: mode.get("height")?.as_u64()? as u32,
+                refresh: mode.get("refresh")?.as_f64()? as f32,
+            }))
+            .collect();
+        let field = |key: &str| shown.and_then(|mode| mode.get(key));
+        let position = |key: &str| m.get("position").and_then(|p| p.get(key)).and_then(|v| v.as_i64()).unwrap_or(0) as i32;
+        let transform = m.get("transform")
+            .and_then(|v| v.as_str())
+            .and_then(|t| TRANSFORMS.iter().position(|name| *name == t))
+            .unwrap_or(0) as u8;
+
+        monitors.push(MonitorInfo {
+            name: name.to_string(),
+            description: m.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            width: field("width").and_then(|v| v.as_u64()).unwrap_or(1920) as u32,
+            height: field("height").and_then(|v| v.as_u64()).unwrap_or(1080) as u32,
+            refresh_rate: field("refresh").and_then(|v| v.as_f64()).unwrap_or(60.0) as f32,
+            x: position("x"),
+            y: position("y"),
+            scale: m.get("scale").and_then(|v| v.as_f64()).unwrap_or(1.0).max(0.1) as f32,
+            disabled: !m.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true),
+            transform,
+            workspaces: vec![],
+            available_modes,
+            selected_mode: None,
+            dpms_status: None,
+            current_format: None,
+            custom_mode: false,
+            vrr: u8::from(m.get("adaptive_sync").and_then(|v| v.as_bool()).unwrap_or(false)),
+            vrr_set: false,
+            mirror_of: None,
+            primary: false,
+            bitdepth: 8,
+            cm: None,
+            physical_size: monitor::physical_size(
+                m.get("physical_size").and_then(|s| s.get("width")).and_then(|v| v.as_u64()),
+                m.get("physical_size").and_then(|s| s.get("height")).and_then(|v| v.as_u64()),
+            ),
+            reserved: None,
+        });
+    }
+
+    monitor::sort_monitors(&mut monitors);
+    monitors
+}
+
+/// Arguments for a single `wlr-randr` call that sets up every output.
+fn wlr_randr_args(monitors: &[MonitorInfo], config: &Config) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    for m in monitors {
+        args.extend(["--output".to_string(), m.name.clone()]);
+        if m.disabled {
+            args.push("--off".to_string());
+            continue;
+        }
+        if let Some(target) = &m.mirror_of {
+            return Err(format!("{} can't mirror {}: wlr-randr doesn't support mirroring", m.name, target));
+        }
+
+        args.push("--on".to_string());
+        let mode = format!("{}x{}@{}Hz", m.width, m.height, m.refresh_rate);
+        if m.custom_mode {
+            args.extend(["--custom-mode".to_string(), mode]);
+        } else if m.selected_mode.is_some() {
+            args.extend(["--mode".to_string(), mode]);
+        } else {
+            args.push("--preferred".to_string());
+        }
+        args.extend([
+            "--pos".to_string(),
+            format!("{},{}", m.x, m.y),
+            "--scale".to_string(),
+            m.effective_scale(config.scale_strategy).to_string(),
+            "--transform".to_string(),
+            TRANSFORMS.get(m.transform as usize).unwrap_or(&"normal").to_string(),
+        ]);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &[u8] = br#"[
+        {
+            "name": "eDP-1",
+            "description": "BOE 0x0BCA",
+            "enabled": true,
+            "modes": [
+                {"width": 2256, "height": 1504, "refresh": 59.999, "preferred": true, "current": true},
+                {"width": 1920, "height": 1200, "refresh": 59.885, "preferred": false, "current": false}
+            ],
+            "position": {"x": 1920, "y": 0},
+            "transform": "90",
+            "scale": 1.5,
+            "adaptive_sync": false,
+            "physical_size": {"width": 290, "height": 190}
+        },
+        {
+            "name": "DP-1",
+            "description": "Dell U2720Q",
+            "enabled": false,
+            "modes": [
+                {"width": 3840, "height": 2160, "refresh": 60.0, "preferred": true, "current": false}
+            ],
+            "position": {"x": 0, "y": 0},
+            "transform": "normal",
+            "scale": 1.0
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse_wlr_randr() {
+        let monitors = parse_wlr_randr(SAMPLE, true);
+        assert_eq!(monitors.len(), 2);
+
+        let laptop = &monitors[0];
+        assert_eq!(laptop.name, "eDP-1");
+        assert_eq!((laptop.width, laptop.height), (2256, 1504));
+        assert_eq!((laptop.x, laptop.y), (1920, 0));
+        assert_eq!(laptop.transform, 1);
+        assert_eq!(laptop.scale, 1.5);
+        assert_eq!(laptop.available_modes.len(), 2);
+        assert_eq!(laptop.physical_size, Some((290, 190)));
+        assert!(!laptop.disabled);
+
+        // Disabled outputs show their preferred mode and sort last
+        let external = &monitors[1];
+        assert!(external.disabled);
+        assert_eq!((external.width, external.height), (3840, 2160));
+    }
+
+    #[test]
+    fn test_wlr_randr_args() {
+        let mut monitors = parse_wlr_randr(SAMPLE, true);
+        monitors[0].selected_mode = Some(1);
+        monitors[0].width = 1920;
+        monitors[0].height = 1200;
+        monitors[0].refresh_rate = 59.885;
+
+        let args = wlr_randr_args(&monitors, &Config::default()).unwrap();
+        assert_eq!(args.join(" "), "--output eDP-1 --on --mode 1920x1200@59.885Hz --pos 1920,0 \
+            --scale 1.5 --transform 90 --output DP-1 --off");
+    }
+
+    #[test]
+    fn test_wlr_randr_rejects_mirroring() {
+        let mut monitors = parse_wlr_randr(SAMPLE, true);
+        monitors[1].disabled = false;
+        monitors[1].mirror_of = Some("eDP-1".to_string());
+        assert!(wlr_randr_args(&monitors, &Config::default()).is_err());
+    }
+
+    #[test]
+    fn fetch_cache_reuses_unchanged_output() {
+        const ONE: &[u8] = br#"[{"name": "DP-1", "width": 2560, "height": 1440}]"#;
+        const TWO: &[u8] = br#"[{"name": "DP-1", "width": 1920, "height": 1080}]"#;
+        let backend = HyprlandBackend { min_refresh: 0.0, last_fetch: RefCell::new(None) };
+        let expire = || backend.last_fetch.borrow_mut().as_mut().unwrap().at -= FETCH_TTL;
+
+        assert_eq!(backend.fetch_with(false, || ONE.to_vec())[0].width, 2560);
+        // Within the TTL hyprctl isn't run at all
+        assert_eq!(backend.fetch_with(false, || unreachable!())[0].width, 2560);
+
+        // Past it, the same output hands back the cached parse (marked here to tell it apart)
+        backend.last_fetch.borrow_mut().as_mut().unwrap().monitors[0].description = "cached".into();
+        expire();
+        assert_eq!(backend.fetch_with(false, || ONE.to_vec())[0].description, "cached");
+
+        // and different output is parsed again
+        expire();
+        let monitors = backend.fetch_with(false, || TWO.to_vec());
+        assert_eq!((monitors[0].width, monitors[0].description.as_str()), (1920, ""));
+    }
+}
@@ -1,5 +1,19 @@
-use crate::{apply, monitor, preset};
+use crate::args::{BatchOp, Options};
+use crate::config::Config;
+use crate::{apply, backend, completions, diagram, events, hooks, layout, monitor, preset};
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long `--watch` waits for hotplug events to stop before acting on them. A dock
+/// brings its monitors up one after another, and that should be one apply, not three.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(1500);
+/// How often `--watch` checks whether it was asked to stop.
+const WATCH_TICK: Duration = Duration::from_millis(200);
 
 pub fn print_help() {
     println!("monitui v{}", env!("CARGO_PKG_VERSION"));
@@ -7,15 +21,40 @@ pub fn print_help() {
     println!();
     println!("USAGE:");
     println!("    monitui                                    Launch interactive TUI");
+    println!("    monitui --monitor                          Launch a read-only TUI that follows the live layout");
     println!("    monitui --list                             List all monitors and their status");
     println!("    monitui --presets                          List all saved presets");
     println!("    monitui --preset <name>                    Apply saved preset");
+    println!("    monitui --auto                             Apply the preset that best fits the connected monitors");
+    println!("    monitui --watch                            Keep running, doing --auto whenever monitors are plugged in or out");
+    println!("    monitui --save-preset <name>               Save the current layout as a preset");
+    println!("    monitui --export-preset <name> <path>      Write a preset's JSON to a file");
+    println!("    monitui --import-preset <path>             Add a preset from a JSON file");
     println!("    monitui --reload                           Reload most recent configuration");
+    println!("    monitui --undo                             Undo the last CLI apply");
+    println!("    monitui --recover                          Apply the last confirmed layout for the connected monitors");
+    println!("    monitui --restore-backup                   Restore the monitors.conf you had before monitui overwrote it");
     println!("    monitui --enable <monitor>                 Enable a monitor (e.g., DP-1)");
     println!("    monitui --disable <monitor>                Disable a monitor (e.g., DP-2)");
+    println!("    monitui --scale <monitor> <value>          Set a monitor's scale (0.1–8.0)");
+    println!("    monitui --rotate <monitor> <0|90|180|270>  Rotate a monitor (keeps a flip)");
+    println!("    monitui --set-position <monitor> <x> <y>   Move a monitor, in logical (scaled) pixels");
     println!("    monitui --set-workspace <num> <monitor>    Assign workspace to monitor");
+    println!("    monitui --export-wallpaper-layout          Print monitor geometry as JSON for wallpaper scripts");
+    println!("    monitui --diagram <out.svg> [preset]       Draw the current (or a preset's) layout as an SVG");
+    println!("    monitui --completions <bash|zsh|fish>      Print a shell completion script");
     println!("    monitui --help                             Show this help message");
     println!();
+    println!("    --enable, --disable, --scale, --rotate and --set-position can be chained");
+    println!("    (optionally ending with --apply); they are applied together in one go.");
+    println!("    Options that take a value also accept --option=value.");
+    println!();
+    println!("OPTIONS:");
+    println!("    --force                                    Apply even if no monitor would stay enabled");
+    println!("    --quiet                                    Don't print the resulting layout after --preset, nor notify");
+    println!("    --dry-run                                  With --preset, --auto, --watch or --reload: print what would be written and run");
+    println!("    --json                                     With --list: print every monitor and all its fields as JSON");
+    println!();
     println!("EXAMPLES:");
     println!("    monitui --list                             Show all monitors");
     println!("    monitui --presets                          Show all presets");
@@ -23,42 +62,51 @@ pub fn print_help() {
     println!("    monitui --reload                           Reload last applied config");
     println!("    monitui --enable DP-1                      Enable DP-1 monitor");
     println!("    monitui --disable HDMI-A-1                 Disable HDMI-A-1 monitor");
+    println!("    monitui --disable eDP-1 --enable DP-1 --scale DP-1 1.5 --apply");
+    println!("                                               Chain edits and apply them once");
     println!("    monitui --set-workspace 5 DP-1             Move workspace 5 to DP-1");
+    println!("    monitui --export-wallpaper-layout > ~/.cache/monitors.json");
     println!();
     println!("For more information, visit: https://github.com/nathanielbd/monitui");
 }
 
-pub fn apply_preset(name: &str) {
-    let preset_obj = match preset::load_preset(name) {
-        Ok(p) => p,
-        Err(_) => {
-            eprintln!("Error: Preset '{}' not found", name);
-            eprintln!("Available presets:");
-            for preset_name in preset::list_presets() {
-                eprintln!("  - {}", preset_name);
-            }
-            process::exit(1);
-        }
-    };
+/// Apply preset `name` to the connected monitors. Errors are returned rather than
+/// exiting, so `--watch` can report them and carry on.
+pub fn apply_preset(name: &str, config: &Config, options: &Options) -> Result<(), String> {
+    let preset_obj = preset::load_preset(name).map_err(|_| {
+        let available: Vec<String> = preset::list_presets().iter().map(|p| format!("\n  - {}", p)).collect();
+        format!("Preset '{}' not found\nAvailable presets:{}", name, available.concat())
+    })?;
 
     // Get current monitors and apply preset configs
-    let mut monitors = monitor::fetch_monitors_all();
+    let backend = backend::select(config);
+    let mut monitors = backend.fetch_monitors(true);
+    let before = monitors.clone();
     preset::apply_preset_to_monitors(&mut monitors, &preset_obj.monitors);
+    if let Err(e) = apply::validate_monitors(&monitors) {
+        if !options.force {
+            return Err(format!("{}; not applying '{}' (run with --force to apply anyway)", e, name));
+        }
+        eprintln!("Warning: {} (continuing because of --force)", e);
+    }
+    if options.dry_run {
+        return print_dry_run(backend.as_ref(), &monitors, config);
+    }
+    preset::save_last_cli_state(&before);
 
     println!("Applying preset '{}'...", name);
-    match apply::apply_monitors(&monitors) {
-        Ok(_) => {
-            preset::save_recent(&monitors);
-            println!("✓ Successfully applied preset '{}'", name);
-        }
-        Err(e) => {
-            eprintln!("Error: Failed to apply preset: {}", e);
-            process::exit(1);
-        }
+    apply_with_hooks(backend.as_ref(), &monitors, config)
+        .map_err(|e| format!("Failed to apply preset '{}': {}", name, e))?;
+    preset::save_recent(&monitors);
+    println!("✓ Successfully applied preset '{}'", name);
+    if !options.quiet {
+        let applied: Vec<preset::MonitorConfig> = monitors.iter().map(preset::MonitorConfig::from).collect();
+        print_layout(&applied, config);
     }
+    Ok(())
 }
 
-pub fn reload_recent() {
+pub fn reload_recent(config: &Config, options: &Options) {
     let configs = match preset::load_recent() {
         Some(c) => c,
         None => {
@@ -69,11 +117,22 @@ pub fn reload_recent() {
     };
 
     // Get current monitors and apply recent configs
-    let mut monitors = monitor::fetch_monitors_all();
+    let backend = backend::select(config);
+    let mut monitors = backend.fetch_monitors(true);
+    let before = monitors.clone();
     preset::apply_preset_to_monitors(&mut monitors, &configs);
+    check_safe(&monitors, options);
+    if options.dry_run {
+        if let Err(e) = print_dry_run(backend.as_ref(), &monitors, config) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    preset::save_last_cli_state(&before);
 
     println!("Reloading most recent configuration...");
-    match apply::apply_monitors(&monitors) {
+    match apply_with_hooks(backend.as_ref(), &monitors, config) {
         Ok(_) => {
             println!("✓ Successfully reloaded recent configuration");
         }
@@ -84,89 +143,376 @@ pub fn reload_recent() {
     }
 }
 
-pub fn enable_monitor(monitor_name: &str) {
-    let mut monitors = monitor::fetch_monitors_all();
-
-    let monitor = match monitors.iter_mut().find(|m| m.name == monitor_name) {
-        Some(m) => m,
+/// Apply the last layout confirmed in the TUI for the monitors connected right now.
+pub fn recover(config: &Config, options: &Options) {
+    let backend = backend::select(config);
+    let mut monitors = backend.fetch_monitors(true);
+    let configs = match preset::load_last_known_good(&monitors) {
+        Some(c) => c,
         None => {
-            eprintln!("Error: Monitor '{}' not found", monitor_name);
-            eprintln!("Available monitors:");
-            for m in &monitors {
-                eprintln!("  - {} ({})", m.name, if m.disabled { "disabled" } else { "enabled" });
-            }
+            eprintln!("Error: No known-good configuration for this set of monitors");
+            eprintln!("Connected: {}", preset::hardware_fingerprint(&monitors));
+            eprintln!("Confirm a layout in the TUI with these monitors connected to record one");
             process::exit(1);
         }
     };
 
-    if !monitor.disabled {
-        println!("Monitor '{}' is already enabled", monitor_name);
-        return;
-    }
-
-    monitor.disabled = false;
+    let before = monitors.clone();
+    preset::apply_preset_to_monitors(&mut monitors, &configs);
+    check_safe(&monitors, options);
+    preset::save_last_cli_state(&before);
 
-    println!("Enabling monitor '{}'...", monitor_name);
-    match apply::apply_monitors(&monitors) {
+    println!("Recovering last known-good configuration for these monitors...");
+    match apply_with_hooks(backend.as_ref(), &monitors, config) {
         Ok(_) => {
             preset::save_recent(&monitors);
-            println!("✓ Successfully enabled '{}'", monitor_name);
+            println!("✓ Successfully recovered configuration");
         }
         Err(e) => {
-            eprintln!("Error: {}", e);
+            eprintln!("Error: Failed to recover config: {}", e);
             process::exit(1);
         }
     }
 }
 
-pub fn disable_monitor(monitor_name: &str) {
-    let mut monitors = monitor::fetch_monitors_all();
+/// Pick the saved preset that best fits the connected monitors and apply it.
+pub fn apply_best_preset(config: &Config, options: &Options) {
+    let presets: Vec<preset::Preset> = preset::list_presets()
+        .iter()
+        .filter_map(|name| preset::load_preset(name).ok())
+        .collect();
+    let connected = backend::select(config).fetch_monitors(false);
+
+    let Some((chosen, found)) = preset::best_match(&presets, &connected) else {
+        eprintln!("Error: No preset matches the connected monitors");
+        eprintln!("Connected: {}", preset::hardware_fingerprint(&connected));
+        process::exit(1);
+    };
+    println!("Chose preset '{}': connected {}", chosen.name, found.matched.join(", "));
+    if !found.missing.is_empty() {
+        println!("  (best match, but missing {})", found.missing.join(", "));
+    }
+    if let Err(e) = apply_preset(&chosen.name, config, options) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
 
-    let monitor = match monitors.iter_mut().find(|m| m.name == monitor_name) {
-        Some(m) => m,
-        None => {
-            eprintln!("Error: Monitor '{}' not found", monitor_name);
-            eprintln!("Available monitors:");
-            for m in &monitors {
-                eprintln!("  - {} ({})", m.name, if m.disabled { "disabled" } else { "enabled" });
+/// Stay running and, whenever monitors are plugged in or unplugged, apply the preset that
+/// best fits them (as `--auto` would). Each decision is logged to stderr. Stops on Ctrl+C
+/// (SIGINT) or SIGTERM, never in the middle of an apply.
+pub fn watch(config: &Config, options: &Options) {
+    let Some(events) = events::subscribe_hotplug() else {
+        eprintln!("Error: Can't reach Hyprland's event socket; --watch needs a running Hyprland session");
+        process::exit(1);
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+        if let Err(e) = signal_hook::flag::register(signal, Arc::clone(&stop)) {
+            eprintln!("Error: Failed to set up signal handling: {}", e);
+            process::exit(1);
+        }
+    }
+
+    let backend = backend::select(config);
+    let mut fingerprint = preset::hardware_fingerprint(&backend.fetch_monitors(false));
+    eprintln!("Watching for monitors being plugged in or out (connected: {})", fingerprint);
+
+    while !stop.load(Ordering::Relaxed) {
+        match events.recv_timeout(WATCH_TICK) {
+            Ok(()) => {}
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => {
+                eprintln!("Error: Lost the connection to Hyprland's event socket");
+                process::exit(1);
             }
+        }
+        if !wait_for_quiet(&events, &stop) {
+            break;
+        }
+
+        // Enabling or disabling a monitor also fires these events, so only act when
+        // the hardware actually changed (which also keeps our own applies from looping)
+        let connected = backend.fetch_monitors(false);
+        let current = preset::hardware_fingerprint(&connected);
+        if current == fingerprint {
+            eprintln!("Monitor event, but the same monitors are connected; nothing to do");
+            continue;
+        }
+        fingerprint = current;
+        eprintln!("Connected monitors changed: {}", fingerprint);
+        if let Err(e) = apply_best_match(&connected, config, options) {
+            eprintln!("Error: {}", e);
+        }
+    }
+    eprintln!("Stopped watching");
+}
+
+/// Wait until no hotplug event has arrived for `WATCH_DEBOUNCE`. False if asked to stop meanwhile.
+fn wait_for_quiet(events: &Receiver<()>, stop: &AtomicBool) -> bool {
+    let mut last_event = Instant::now();
+    while last_event.elapsed() < WATCH_DEBOUNCE {
+        if stop.load(Ordering::Relaxed) {
+            return false;
+        }
+        match events.recv_timeout(WATCH_TICK) {
+            Ok(()) => last_event = Instant::now(),
+            Err(RecvTimeoutError::Timeout) => {}
+            // Let the caller report it
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    true
+}
+
+/// `--auto` for `--watch`: pick and apply the best-fitting preset, reporting problems
+/// instead of exiting so the watch carries on.
+fn apply_best_match(connected: &[monitor::MonitorInfo], config: &Config, options: &Options) -> Result<(), String> {
+    let presets: Vec<preset::Preset> = preset::list_presets()
+        .iter()
+        .filter_map(|name| preset::load_preset(name).ok())
+        .collect();
+    let (chosen, found) = preset::best_match(&presets, connected)
+        .ok_or("No preset matches the connected monitors; leaving the layout alone")?;
+    eprintln!("Chose preset '{}': connected {}", chosen.name, found.matched.join(", "));
+    if !found.missing.is_empty() {
+        eprintln!("  (best match, but missing {})", found.missing.join(", "));
+    }
+    apply_preset(&chosen.name, config, options)
+}
+
+/// Save the live layout as preset `name`, replacing any preset already called that.
+pub fn save_preset(name: &str, config: &Config) {
+    let monitors = backend::select(config).fetch_monitors(true);
+    if preset::load_preset(name).is_ok() {
+        eprintln!("Warning: Overwriting existing preset '{}'", name);
+    }
+    match preset::save_preset(name, &monitors) {
+        Ok(()) => println!("✓ Saved preset '{}'", name),
+        Err(e) => {
+            eprintln!("Error: Failed to save preset: {}", e);
             process::exit(1);
         }
-    };
+    }
+}
 
-    if monitor.disabled {
-        println!("Monitor '{}' is already disabled", monitor_name);
+pub fn export_preset(name: &str, path: &str) {
+    match preset::export_preset(name, Path::new(path)) {
+        Ok(()) => println!("✓ Exported preset '{}' to {}", name, path),
+        Err(e) => {
+            eprintln!("Error: Failed to export preset '{}': {}", name, e);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn import_preset(path: &str) {
+    match preset::import_preset(Path::new(path)) {
+        Ok(name) => println!("✓ Imported preset '{}'", name),
+        Err(e) => {
+            eprintln!("Error: Failed to import {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn restore_backup() {
+    match apply::restore_backup() {
+        Ok(path) => println!("✓ Restored {} and reloaded Hyprland", path.display()),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Run a batch of edits against one fetched state and apply the result once.
+pub fn run_batch(ops: &[BatchOp], config: &Config, options: &Options) {
+    let backend = backend::select(config);
+    let mut monitors = backend.fetch_monitors(true);
+    let before = monitors.clone();
+    let mut done = Vec::new();
+
+    for op in ops {
+        let name = op.monitor();
+        let monitor = match monitors.iter_mut().find(|m| m.name == name) {
+            Some(m) => m,
+            None => monitor_not_found(name, &monitors),
+        };
+
+        match op {
+            BatchOp::Enable(_) if !monitor.disabled => {
+                println!("Monitor '{}' is already enabled", name);
+            }
+            BatchOp::Enable(_) => {
+                monitor.disabled = false;
+                done.push(format!("enabled '{}'", name));
+            }
+            BatchOp::Disable(_) if monitor.disabled => {
+                println!("Monitor '{}' is already disabled", name);
+            }
+            BatchOp::Disable(_) => {
+                monitor.disabled = true;
+                done.push(format!("disabled '{}'", name));
+            }
+            BatchOp::Scale(..) if monitor.disabled => {
+                eprintln!("Error: Cannot set the scale of disabled monitor '{}'", name);
+                eprintln!("Enable it first with: monitui --enable {}", name);
+                process::exit(1);
+            }
+            BatchOp::Scale(_, scale) => {
+                monitor.scale = *scale;
+                monitor.scale = monitor.effective_scale(config.scale_strategy);
+                done.push(format!("set scale of '{}' to {:.2}x", name, monitor.scale));
+            }
+            BatchOp::Rotate(..) if monitor.disabled => {
+                eprintln!("Error: Cannot rotate disabled monitor '{}'", name);
+                eprintln!("Enable it first with: monitui --enable {}", name);
+                process::exit(1);
+            }
+            BatchOp::Rotate(_, transform) => {
+                // Keep a flip, only the rotation changes
+                monitor.transform = (monitor.transform & 4) | transform;
+                let rotation = monitor.rotation_string().to_string();
+                // A quarter turn swaps width and height, so overlaps and gaps can appear
+                settle_position(&mut monitors, name, config);
+                done.push(format!("rotated '{}' to {}", name, rotation));
+            }
+            BatchOp::Position(..) if monitor.disabled => {
+                eprintln!("Error: Cannot position disabled monitor '{}'", name);
+                eprintln!("Enable it first with: monitui --enable {}", name);
+                process::exit(1);
+            }
+            BatchOp::Position(_, x, y) => {
+                monitor.x = *x;
+                monitor.y = *y;
+                settle_position(&mut monitors, name, config);
+                let m = monitors.iter().find(|m| m.name == name).expect("monitor was just found");
+                done.push(format!("moved '{}' to {}x{}", name, m.x, m.y));
+            }
+        }
+    }
+
+    if done.is_empty() {
         return;
     }
 
-    monitor.disabled = true;
+    check_safe(&monitors, options);
+    preset::save_last_cli_state(&before);
+    println!("Applying {} change{}...", done.len(), if done.len() == 1 { "" } else { "s" });
+    match apply_with_hooks(backend.as_ref(), &monitors, config) {
+        Ok(_) => {
+            preset::save_recent(&monitors);
+            for change in &done {
+                println!("✓ Successfully {}", change);
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Re-apply the state snapshotted before the last CLI apply.
+/// The state being replaced becomes the new snapshot, so a second `--undo` redoes.
+pub fn undo_last(config: &Config, options: &Options) {
+    let configs = match preset::load_last_cli_state() {
+        Some(c) => c,
+        None => {
+            eprintln!("Error: Nothing to undo");
+            eprintln!("--undo restores the state from before the last CLI apply");
+            process::exit(1);
+        }
+    };
+
+    let backend = backend::select(config);
+    let mut monitors = backend.fetch_monitors(true);
+    let before = monitors.clone();
+    preset::apply_preset_to_monitors(&mut monitors, &configs);
+    check_safe(&monitors, options);
 
-    println!("Disabling monitor '{}'...", monitor_name);
-    match apply::apply_monitors(&monitors) {
+    println!("Undoing last CLI operation...");
+    match apply_with_hooks(backend.as_ref(), &monitors, config) {
         Ok(_) => {
+            preset::save_last_cli_state(&before);
             preset::save_recent(&monitors);
-            println!("✓ Successfully disabled '{}'", monitor_name);
+            println!("✓ Successfully restored previous configuration");
         }
         Err(e) => {
+            eprintln!("Error: Failed to undo: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Apply through the backend and the configured hooks. A failed hook is only a warning.
+fn apply_with_hooks(backend: &dyn backend::Backend, monitors: &[monitor::MonitorInfo], config: &Config) -> Result<(), String> {
+    for e in hooks::apply(backend, monitors, config)? {
+        eprintln!("Warning: {}", e);
+    }
+    Ok(())
+}
+
+/// Show what applying `monitors` would do, for `--dry-run`. Nothing is written or run.
+fn print_dry_run(backend: &dyn backend::Backend, monitors: &[monitor::MonitorInfo], config: &Config) -> Result<(), String> {
+    print!("{}", backend.dry_run(monitors, config)?);
+    Ok(())
+}
+
+/// Push a monitor that was placed by hand out of any monitor it now overlaps (keeping
+/// it as close to where it was put as possible), then shift the layout back to start
+/// at 0x0 (unless `normalize_origin` is off), or at the primary monitor.
+fn settle_position(monitors: &mut [monitor::MonitorInfo], name: &str, config: &Config) {
+    let mut rects: Vec<_> = monitors.iter()
+        .filter(|m| !m.disabled && m.mirror_of.is_none())
+        .map(monitor::MonitorInfo::layout_rect)
+        .collect();
+    let Some(idx) = rects.iter().position(|r| r.id == name) else { return };
+    let (x, y) = (rects[idx].x, rects[idx].y);
+    layout::resolve_overlaps(&mut rects, idx, x, y, config.gap);
+    match monitors.iter().find(|m| m.primary).and_then(|p| rects.iter().position(|r| r.id == p.name)) {
+        Some(primary) => layout::anchor_at_origin(&mut rects, primary),
+        None if config.normalize_origin => layout::normalize(&mut rects),
+        None => (0, 0),
+    };
+    for r in &rects {
+        if let Some(m) = monitors.iter_mut().find(|m| m.name == r.id) {
+            m.x = r.x;
+            m.y = r.y;
+        }
+    }
+}
+
+/// Abort before applying a layout with no enabled monitors, unless `--force` was given.
+fn check_safe(monitors: &[monitor::MonitorInfo], options: &Options) {
+    if let Err(e) = apply::validate_monitors(monitors) {
+        if options.force {
+            eprintln!("Warning: {} (continuing because of --force)", e);
+        } else {
             eprintln!("Error: {}", e);
+            eprintln!("Nothing was applied. Re-run with --force if this is intended.");
             process::exit(1);
         }
     }
 }
 
+fn monitor_not_found(name: &str, monitors: &[monitor::MonitorInfo]) -> ! {
+    eprintln!("Error: Monitor '{}' not found", name);
+    eprintln!("Available monitors:");
+    for m in monitors {
+        eprintln!("  - {} ({})", m.name, if m.disabled { "disabled" } else { "enabled" });
+    }
+    process::exit(1);
+}
+
 pub fn set_workspace(workspace: u32, monitor_name: &str) {
-    let monitors = monitor::fetch_monitors_all();
+    // Workspaces are a Hyprland concept, so this always talks to hyprctl
+    let monitors = monitor::fetch_monitors_all(false);
 
     let monitor = match monitors.iter().find(|m| m.name == monitor_name) {
         Some(m) => m,
-        None => {
-            eprintln!("Error: Monitor '{}' not found", monitor_name);
-            eprintln!("Available monitors:");
-            for m in &monitors {
-                eprintln!("  - {} ({})", m.name, if m.disabled { "disabled" } else { "enabled" });
-            }
-            process::exit(1);
-        }
+        None => monitor_not_found(monitor_name, &monitors),
     };
 
     if monitor.disabled {
@@ -198,14 +544,31 @@ pub fn set_workspace(workspace: u32, monitor_name: &str) {
     }
 }
 
-pub fn list_monitors() {
-    let monitors = monitor::fetch_monitors_all();
+pub fn list_monitors(config: &Config, options: &Options) {
+    let monitors = backend::select(config).fetch_monitors(true);
+    if options.json {
+        // Every field of every output, disabled ones included, for scripts
+        match serde_json::to_string_pretty(&monitors) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
     println!("Monitors:");
     println!();
 
     for m in &monitors {
-        let status = if m.disabled { "DISABLED" } else { "enabled" };
+        let status = if m.disabled {
+            "DISABLED"
+        } else if m.no_signal() {
+            "enabled (NO SIGNAL)"
+        } else {
+            "enabled"
+        };
         let ws_text = if m.workspaces.is_empty() {
             "no workspaces".to_string()
         } else {
@@ -215,7 +578,7 @@ pub fn list_monitors() {
         println!("  {} - {} | {} | {} | Pos: {}x{} | Scale: {:.2}x | Rotation: {}",
             m.name,
             status,
-            m.resolution_string(),
+            m.resolution_string(config.refresh_decimals),
             ws_text,
             m.x,
             m.y,
@@ -225,7 +588,63 @@ pub fn list_monitors() {
     }
 }
 
-pub fn list_presets_cmd() {
+/// Print a JSON object mapping each enabled monitor's name to its geometry, for
+/// wallpaper scripts (swww, hyprpaper, ...) to read.
+pub fn export_wallpaper_layout(config: &Config) {
+    let layout: BTreeMap<String, monitor::WallpaperGeometry> = backend::select(config).fetch_monitors(true)
+        .iter()
+        .filter(|m| !m.disabled)
+        .map(|m| (m.name.clone(), m.wallpaper_geometry()))
+        .collect();
+
+    match serde_json::to_string_pretty(&layout) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Write an SVG diagram of the current layout, or of the layout `preset` would
+/// produce on the connected monitors.
+pub fn write_diagram(path: &str, preset_name: Option<&str>, config: &Config) {
+    let mut monitors = backend::select(config).fetch_monitors(true);
+    if let Some(name) = preset_name {
+        match preset::load_preset(name) {
+            Ok(p) => preset::apply_preset_to_monitors(&mut monitors, &p.monitors),
+            Err(_) => {
+                eprintln!("Error: Preset '{}' not found", name);
+                process::exit(1);
+            }
+        }
+    }
+
+    let svg = match diagram::render_svg(&monitors) {
+        Ok(svg) => svg,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    if let Err(e) = std::fs::write(path, svg) {
+        eprintln!("Error: Failed to write {}: {}", path, e);
+        process::exit(1);
+    }
+    println!("✓ Wrote layout diagram to {}", path);
+}
+
+pub fn print_completions(shell: &str) {
+    match completions::script(shell) {
+        Some(script) => print!("{}", script),
+        None => {
+            eprintln!("Error: Unsupported shell '{}'", shell);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn list_presets_cmd(config: &Config) {
     let preset_names = preset::list_presets();
 
     if preset_names.is_empty() {
@@ -241,38 +660,7 @@ pub fn list_presets_cmd() {
         match preset::load_preset(name) {
             Ok(p) => {
                 println!("  {}:", name);
-
-                // Sort monitors by position: left to right, top to bottom for ties
-                let mut enabled: Vec<_> = p.monitors.iter()
-                    .filter(|m| !m.disabled)
-                    .collect();
-                enabled.sort_by(|a, b| a.y.cmp(&b.y).then_with(|| a.x.cmp(&b.x)));
-
-                if enabled.is_empty() {
-                    println!("    (no monitors enabled)");
-                } else {
-                    for m in enabled {
-                        let ws_text = if m.workspaces.is_empty() {
-                            "no WS".to_string()
-                        } else {
-                            format!("WS: {}", m.workspaces.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(", "))
-                        };
-                        // Build resolution string accounting for rotation
-                        let (w, h) = match m.transform {
-                            1 | 3 => (m.height, m.width),  // 90° or 270° - swap dimensions
-                            _ => (m.width, m.height),      // 0° or 180° - keep dimensions
-                        };
-                        let resolution = format!("{}x{}@{:.0}Hz", w, h, m.refresh_rate);
-                        println!("    - {} ({}) | {} | Pos: {}x{} | Scale: {:.2}x",
-                            m.name,
-                            ws_text,
-                            resolution,
-                            m.x,
-                            m.y,
-                            m.scale
-                        );
-                    }
-                }
+                print_layout(&p.monitors, config);
                 println!();
             }
             Err(e) => {
@@ -281,3 +669,38 @@ pub fn list_presets_cmd() {
         }
     }
 }
+
+/// Print one line per enabled monitor: workspaces, resolution, position and scale.
+fn print_layout(monitors: &[preset::MonitorConfig], config: &Config) {
+    // Sort monitors by position: left to right, top to bottom for ties
+    let mut enabled: Vec<_> = monitors.iter()
+        .filter(|m| !m.disabled)
+        .collect();
+    enabled.sort_by(|a, b| a.y.cmp(&b.y).then_with(|| a.x.cmp(&b.x)));
+
+    if enabled.is_empty() {
+        println!("    (no monitors enabled)");
+        return;
+    }
+    for m in enabled {
+        let ws_text = if m.workspaces.is_empty() {
+            "no WS".to_string()
+        } else {
+            format!("WS: {}", m.workspaces.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(", "))
+        };
+        // Build resolution string accounting for rotation
+        let (w, h) = match m.transform {
+            1 | 3 | 5 | 7 => (m.height, m.width),  // 90° or 270°, flipped or not - swap dimensions
+            _ => (m.width, m.height),              // 0° or 180° - keep dimensions
+        };
+        let resolution = format!("{}x{}@{}", w, h, monitor::format_refresh(m.refresh_rate, config.refresh_decimals));
+        println!("    - {} ({}) | {} | Pos: {}x{} | Scale: {:.2}x",
+            m.name,
+            ws_text,
+            resolution,
+            m.x,
+            m.y,
+            m.scale
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the Unix epoch, now.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// A Unix timestamp as a time of day, e.g. "13:05:09 UTC". std has no timezone support,
+/// so times are shown in UTC (and labelled so) rather than guessing at the local zone.
+pub fn time_of_day(secs: u64) -> String {
+    let day = secs % 86_400;
+    format!("{:02}:{:02}:{:02} UTC", day / 3600, day % 3600 / 60, day % 60)
+}
+
+/// A Unix timestamp as its UTC calendar date, e.g. "2024-01-05".
+pub fn date(secs: u64) -> String {
+    let (year, month, day) = civil_date(secs);
+    format!("{}-{:02}-{:02}", year, month, day)
+}
+
+/// The UTC calendar date of a Unix timestamp, as (year, month, day).
+fn civil_date(secs: u64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm, counting from 0000-03-01
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_of_day() {
+        assert_eq!(time_of_day(0), "00:00:00 UTC");
+        assert_eq!(time_of_day(86_400 * 3 + 13 * 3600 + 5 * 60 + 9), "13:05:09 UTC");
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_735_689_599), "2024-12-31");
+    }
+}
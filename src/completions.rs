@@ -0,0 +1,172 @@
+/// What follows a flag on the command line, as far as completion is concerned.
+#[derive(Clone, Copy, PartialEq)]
+enum Value {
+    None,
+    Preset,
+    File,
+    Shell,
+    /// A monitor name, number or other free-form value: nothing to offer.
+    Other,
+}
+
+/// Every flag monitui understands, with what it takes and a short description for fish.
+const FLAGS: &[(&str, Value, &str)] = &[
+    ("--help", Value::None, "Show help"),
+    ("--list", Value::None, "List all monitors"),
+    ("--presets", Value::None, "List saved presets"),
+    ("--preset", Value::Preset, "Apply a saved preset"),
+    ("--auto", Value::None, "Apply the preset that best fits the connected monitors"),
+    ("--watch", Value::None, "Apply the best-fitting preset whenever monitors are plugged in or out"),
+    ("--save-preset", Value::Other, "Save the current layout as a preset"),
+    ("--export-preset", Value::Preset, "Write a preset to a file"),
+    ("--import-preset", Value::File, "Add a preset from a file"),
+    ("--reload", Value::None, "Reload the most recent configuration"),
+    ("--undo", Value::None, "Undo the last CLI apply"),
+    ("--recover", Value::None, "Apply the last confirmed layout for these monitors"),
+    ("--restore-backup", Value::None, "Restore the monitors.conf monitui backed up"),
+    ("--monitor", Value::None, "Read-only TUI that follows the live layout"),
+    ("--enable", Value::Other, "Enable a monitor"),
+    ("--disable", Value::Other, "Disable a monitor"),
+    ("--scale", Value::Other, "Set a monitor's scale"),
+    ("--rotate", Value::Other, "Rotate a monitor"),
+    ("--set-position", Value::Other, "Move a monitor"),
+    ("--set-workspace", Value::Other, "Assign a workspace to a monitor"),
+    ("--export-wallpaper-layout", Value::None, "Print monitor geometry as JSON"),
+    ("--diagram", Value::File, "Draw the layout as an SVG"),
+    ("--completions", Value::Shell, "Print a shell completion script"),
+    ("--apply", Value::None, "Apply chained edits"),
+    ("--force", Value::None, "Apply even if no monitor would stay enabled"),
+    ("--quiet", Value::None, "Don't print the resulting layout or notify"),
+    ("--dry-run", Value::None, "Print what would be written and run"),
+    ("--json", Value::None, "Print --list as JSON"),
+];
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// Preset names, pulled from the "  <name>:" headings of `monitui --presets`.
+const LIST_PRESETS: &str = r"monitui --presets 2>/dev/null | sed -n 's/^  \([^ ].*\):$/\1/p'";
+
+/// The completion script for `shell` (one of `SHELLS`), or `None` for any other shell.
+pub fn script(shell: &str) -> Option<String> {
+    match shell {
+        "bash" => Some(bash()),
+        "zsh" => Some(zsh()),
+        "fish" => Some(fish()),
+        _ => None,
+    }
+}
+
+fn flags_with(value: Value) -> Vec<&'static str> {
+    FLAGS.iter().filter(|(_, v, _)| *v == value).map(|(flag, _, _)| *flag).collect()
+}
+
+fn all_flags() -> String {
+    FLAGS.iter().map(|(flag, _, _)| *flag).collect::<Vec<_>>().join(" ")
+}
+
+fn bash() -> String {
+    format!(
+        r#"# monitui bash completion. Load with: source <(monitui --completions bash)
+_monitui() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    case "$prev" in
+        {presets})
+            # Preset names can contain spaces, so split on newlines only
+            local IFS=$'\n'
+            COMPREPLY=($(compgen -W "$({list})" -- "$cur"))
+            return ;;
+        {files})
+            COMPREPLY=($(compgen -f -- "$cur"))
+            return ;;
+        --completions)
+            COMPREPLY=($(compgen -W "{shells}" -- "$cur"))
+            return ;;
+    esac
+    COMPREPLY=($(compgen -W "{flags}" -- "$cur"))
+}}
+complete -F _monitui monitui
+"#,
+        presets = flags_with(Value::Preset).join("|"),
+        files = flags_with(Value::File).join("|"),
+        list = LIST_PRESETS,
+        shells = SHELLS.join(" "),
+        flags = all_flags(),
+    )
+}
+
+fn zsh() -> String {
+    format!(
+        r#"#compdef monitui
+# monitui zsh completion. Save as _monitui somewhere on your $fpath
+_monitui() {{
+    case "$words[CURRENT-1]" in
+        {presets})
+            local -a presets
+            presets=("${{(@f)$({list})}}")
+            compadd -a presets
+            return ;;
+        {files})
+            _files
+            return ;;
+        --completions)
+            compadd {shells}
+            return ;;
+    esac
+    compadd -- {flags}
+}}
+_monitui "$@"
+"#,
+        presets = flags_with(Value::Preset).join("|"),
+        files = flags_with(Value::File).join("|"),
+        list = LIST_PRESETS,
+        shells = SHELLS.join(" "),
+        flags = all_flags(),
+    )
+}
+
+fn fish() -> String {
+    let mut lines = vec![
+        "# monitui fish completion. Save as ~/.config/fish/completions/monitui.fish".to_string(),
+        format!("function __monitui_presets\n    {}\nend", LIST_PRESETS),
+        "complete -c monitui -f".to_string(),
+    ];
+    for (flag, value, description) in FLAGS {
+        let long = flag.trim_start_matches("--");
+        let args = match value {
+            Value::None => String::new(),
+            Value::Preset => " -x -a '(__monitui_presets)'".to_string(),
+            Value::File => " -r -F".to_string(),
+            Value::Shell => format!(" -x -a '{}'", SHELLS.join(" ")),
+            Value::Other => " -x".to_string(),
+        };
+        lines.push(format!("complete -c monitui -l {}{} -d '{}'", long, args, description.replace('\'', "\\'")));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_flag_is_one_monitui_knows() {
+        for (flag, _, _) in FLAGS {
+            if let Err(e) = crate::args::parse(&[flag.to_string()]) {
+                assert!(!e.starts_with("Unknown option"), "{}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn scripts_complete_presets_after_preset() {
+        for shell in SHELLS {
+            let script = script(shell).unwrap();
+            assert!(script.contains("monitui --presets"), "{}", shell);
+            assert!(script.contains("set-position"), "{}", shell);
+        }
+        assert!(bash().contains("        --preset|--export-preset)\n"));
+        assert!(fish().contains("complete -c monitui -l preset -x -a '(__monitui_presets)' -d 'Apply a saved preset'"));
+        assert!(script("powershell").is_none());
+    }
+}
@@ -0,0 +1,451 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// User settings, resolved as defaults < `~/.config/monitui/config.toml` < `MONITUI_*` env vars.
+/// Every field is optional in the file; missing keys fall back to defaults.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    /// Ask before an external-change pull throws away unsaved edits.
+    pub confirm_pull: bool,
+    /// Seconds to wait for confirmation before an apply is reverted.
+    pub confirm_duration: u64,
+    /// Keep applies straight away, without the confirm countdown (`c` turns it back on).
+    pub auto_confirm: bool,
+    /// Seconds between checks for external configuration changes.
+    pub poll_interval: u64,
+    /// Show a desktop notification (notify-send) after applying.
+    pub notify: bool,
+    /// After applying, flash each newly enabled monitor and ask whether it is visible.
+    pub verify_enabled: bool,
+    /// Move Hyprland focus to a monitor when it is selected in monitui.
+    pub focus_follows_selection: bool,
+    /// Draw each monitor's list position as a badge on the layout canvas.
+    pub show_order_badges: bool,
+    /// Height:width ratio of a terminal character cell, so the layout pane draws monitors
+    /// to scale. Most fonts are close to 2.
+    pub cell_aspect: f64,
+    /// List HEADLESS outputs in the TUI. Toggled (and saved) with `t`.
+    pub show_all_monitors: bool,
+    /// Monitors whose scale can't be changed from the TUI. Toggled with `S`.
+    pub scale_locked: Vec<String>,
+    /// Decimal places (0-2) shown for refresh rates, e.g. 60Hz vs 59.94Hz.
+    pub refresh_decimals: u8,
+    /// Modes below this refresh rate (Hz) are hidden from z, f and the mode picker.
+    pub min_refresh: f32,
+    /// Logical pixels left between neighboring monitors when snapping, e.g. for a bezel.
+    pub gap: i32,
+    /// Shift the layout back to start at 0x0 after every move. When off, monitors can sit
+    /// at negative coordinates (a primary monitor is kept at 0x0 either way).
+    pub normalize_origin: bool,
+    /// Whether scales are applied as typed or pre-rounded to what Hyprland will use.
+    pub scale_strategy: ScaleStrategy,
+    /// Don't run `hyprctl reload` when applying; only send the monitor rules directly.
+    pub skip_reload: bool,
+    /// Write monitors.conf as `monitorv2 { ... }` blocks instead of `monitor = ...` lines.
+    pub use_monitorv2: bool,
+    /// Which tool reads and sets up the outputs.
+    pub backend: BackendChoice,
+    /// Apply presets as soon as they're loaded instead of showing what would change first.
+    pub auto_apply_presets: bool,
+    /// Shell command run before each apply.
+    pub pre_apply: Option<String>,
+    /// Shell command run after each successful apply, e.g. to restart a bar.
+    pub post_apply: Option<String>,
+    /// `[keys]` table: action name to the key(s) that trigger it, replacing its defaults.
+    pub keys: BTreeMap<String, KeyNames>,
+    /// `[theme]` table: colors that replace the default palette.
+    pub theme: ThemeConfig,
+}
+
+/// Color overrides as written in config.toml; unset ones keep the default palette.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub border: Option<String>,
+    pub selected: Option<String>,
+    pub workspace: Option<String>,
+    pub disabled: Option<String>,
+    pub accent: Option<String>,
+}
+
+/// One key or a list of keys for an action, e.g. `apply = "y"` or `apply = ["y", "Enter"]`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum KeyNames {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The compositor backend to drive.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackendChoice {
+    /// Hyprland if `hyprctl` is installed, otherwise `wlr-randr` if that is.
+    #[default]
+    Auto,
+    Hyprland,
+    WlrRandr,
+}
+
+/// How a requested scale becomes the one sent to Hyprland.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScaleStrategy {
+    /// Send the scale as is and let Hyprland adjust it.
+    #[default]
+    Exact,
+    /// Round to Hyprland's nearest valid scale first, so monitui shows what you get.
+    HyprRounded,
+}
+
+/// Height:width ratio of a typical terminal character cell.
+const DEFAULT_CELL_ASPECT: f64 = 2.0;
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            confirm_pull: true,
+            confirm_duration: 10,
+            auto_confirm: false,
+            poll_interval: 3,
+            notify: true,
+            verify_enabled: false,
+            focus_follows_selection: false,
+            show_order_badges: true,
+            cell_aspect: DEFAULT_CELL_ASPECT,
+            show_all_monitors: false,
+            scale_locked: Vec::new(),
+            refresh_decimals: 0,
+            min_refresh: 0.0,
+            gap: 0,
+            normalize_origin: true,
+            scale_strategy: ScaleStrategy::Exact,
+            skip_reload: false,
+            use_monitorv2: false,
+            backend: BackendChoice::Auto,
+            auto_apply_presets: false,
+            pre_apply: None,
+            post_apply: None,
+            keys: BTreeMap::new(),
+            theme: ThemeConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Load the config file, falling back to defaults if it is missing, then apply any
+    /// `MONITUI_*` environment overrides on top. A file that doesn't parse is an error;
+    /// `Config::from_env` is what to carry on with then.
+    pub fn load() -> Result<Self, String> {
+        let config = match fs::read_to_string(config_path()) {
+            Ok(s) => parse(&s)?,
+            Err(_) => Config::default(),
+        };
+        Ok(config.finish())
+    }
+
+    /// The defaults with the `MONITUI_*` environment overrides applied.
+    pub fn from_env() -> Self {
+        Config::default().finish()
+    }
+
+    fn finish(mut self) -> Self {
+        self.apply_env(|key| std::env::var(key).ok());
+        self.gap = self.gap.max(0);
+        if !(self.cell_aspect.is_finite() && self.cell_aspect > 0.0) {
+            self.cell_aspect = DEFAULT_CELL_ASPECT;
+        }
+        self
+    }
+
+    /// A heads-up when `cell_aspect` is far enough from what fonts use that monitors will
+    /// look squashed or stretched in the layout pane.
+    pub fn cell_aspect_note(&self) -> Option<String> {
+        (!(1.0..=3.0).contains(&self.cell_aspect)).then(|| format!(
+            "cell_aspect = {} looks off (most terminals are about {}) — monitors may look stretched",
+            self.cell_aspect, DEFAULT_CELL_ASPECT
+        ))
+    }
+
+    /// Write the scale locks back to config.toml, leaving the file's other keys as they are.
+    pub fn save_scale_locks(&self) -> Result<(), String> {
+        let value: toml_edit::Array = self.scale_locked.iter().map(String::as_str).collect();
+        set_file_key("scale_locked", toml_edit::value(value))
+    }
+
+    /// Write `show_all_monitors` back to config.toml, leaving the file's other keys as they are.
+    pub fn save_show_all_monitors(&self) -> Result<(), String> {
+        set_file_key("show_all_monitors", toml_edit::value(self.show_all_monitors))
+    }
+
+    /// Override fields from environment variables. Unparseable values are ignored.
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) {
+        if let Some(v) = var("MONITUI_CONFIRM_PULL").and_then(|v| parse_bool(&v)) {
+            self.confirm_pull = v;
+        }
+        if let Some(v) = var("MONITUI_CONFIRM_DURATION").and_then(|v| v.trim().parse().ok()) {
+            self.confirm_duration = v;
+        }
+        if let Some(v) = var("MONITUI_AUTO_CONFIRM").and_then(|v| parse_bool(&v)) {
+            self.auto_confirm = v;
+        }
+        if let Some(v) = var("MONITUI_POLL_INTERVAL").and_then(|v| v.trim().parse().ok()) {
+            self.poll_interval = v;
+        }
+        if let Some(v) = var("MONITUI_NOTIFY").and_then(|v| parse_bool(&v)) {
+            self.notify = v;
+        }
+        if let Some(v) = var("MONITUI_VERIFY_ENABLED").and_then(|v| parse_bool(&v)) {
+            self.verify_enabled = v;
+        }
+        if let Some(v) = var("MONITUI_FOCUS_FOLLOWS_SELECTION").and_then(|v| parse_bool(&v)) {
+            self.focus_follows_selection = v;
+        }
+        if let Some(v) = var("MONITUI_SHOW_ORDER_BADGES").and_then(|v| parse_bool(&v)) {
+            self.show_order_badges = v;
+        }
+        if let Some(v) = var("MONITUI_CELL_ASPECT").and_then(|v| v.trim().parse().ok()) {
+            self.cell_aspect = v;
+        }
+        if let Some(v) = var("MONITUI_SHOW_ALL_MONITORS").and_then(|v| parse_bool(&v)) {
+            self.show_all_monitors = v;
+        }
+        if let Some(v) = var("MONITUI_REFRESH_DECIMALS").and_then(|v| v.trim().parse().ok()) {
+            self.refresh_decimals = v;
+        }
+        if let Some(v) = var("MONITUI_MIN_REFRESH").and_then(|v| v.trim().parse().ok()) {
+            self.min_refresh = v;
+        }
+        if let Some(v) = var("MONITUI_GAP").and_then(|v| v.trim().parse().ok()) {
+            self.gap = v;
+        }
+        if let Some(v) = var("MONITUI_NORMALIZE_ORIGIN").and_then(|v| parse_bool(&v)) {
+            self.normalize_origin = v;
+        }
+        if let Some(v) = var("MONITUI_SKIP_RELOAD").and_then(|v| parse_bool(&v)) {
+            self.skip_reload = v;
+        }
+        if let Some(v) = var("MONITUI_AUTO_APPLY_PRESETS").and_then(|v| parse_bool(&v)) {
+            self.auto_apply_presets = v;
+        }
+        if let Some(v) = var("MONITUI_PRE_APPLY") {
+            self.pre_apply = Some(v).filter(|v| !v.trim().is_empty());
+        }
+        if let Some(v) = var("MONITUI_POST_APPLY") {
+            self.post_apply = Some(v).filter(|v| !v.trim().is_empty());
+        }
+        if let Some(v) = var("MONITUI_USE_MONITORV2").and_then(|v| parse_bool(&v)) {
+            self.use_monitorv2 = v;
+        }
+        if let Some(v) = var("MONITUI_BACKEND").and_then(|v| parse_backend(&v)) {
+            self.backend = v;
+        }
+        if let Some(v) = var("MONITUI_SCALE_STRATEGY").and_then(|v| parse_scale_strategy(&v)) {
+            self.scale_strategy = v;
+        }
+    }
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_scale_strategy(s: &str) -> Option<ScaleStrategy> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "exact" => Some(ScaleStrategy::Exact),
+        "hypr-rounded" => Some(ScaleStrategy::HyprRounded),
+        _ => None,
+    }
+}
+
+fn parse_backend(s: &str) -> Option<BackendChoice> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "auto" => Some(BackendChoice::Auto),
+        "hyprland" => Some(BackendChoice::Hyprland),
+        "wlr-randr" => Some(BackendChoice::WlrRandr),
+        _ => None,
+    }
+}
+
+/// Parse config.toml, with a one-line error (the full one draws the offending line
+/// over several) that fits the status bar.
+fn parse(content: &str) -> Result<Config, String> {
+    toml::from_str(content).map_err(|e| {
+        let line = e.span().map_or(1, |span| content[..span.start].matches('\n').count() + 1);
+        format!("line {}: {}", line, e.message().trim().replace('\n', "; "))
+    })
+}
+
+/// Set one top-level key in config.toml. Only that key is written, so values that
+/// came from environment variables don't get baked into the file.
+fn set_file_key(key: &str, value: toml_edit::Item) -> Result<(), String> {
+    let path = config_path();
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let content = with_key(&existing, key, value)
+        .map_err(|e| format!("{} is not valid TOML: {}", path.display(), e))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// `content` with `key` set to `value`, keeping its comments, formatting and key order.
+fn with_key(content: &str, key: &str, mut value: toml_edit::Item) -> Result<String, toml_edit::TomlError> {
+    let mut doc: toml_edit::DocumentMut = content.parse()?;
+    // Keep the old value's surrounding whitespace and trailing comment
+    if let (Some(new), Some(old)) = (value.as_value_mut(), doc.get(key).and_then(|item| item.as_value())) {
+        *new.decor_mut() = old.decor().clone();
+    }
+    doc[key] = value;
+    Ok(doc.to_string())
+}
+
+fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("~/.config"))
+        .join("monitui")
+        .join("config.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config, Config::default());
+        assert!(config.confirm_pull);
+    }
+
+    #[test]
+    fn test_confirm_pull_can_be_disabled() {
+        let config: Config = toml::from_str("confirm_pull = false").unwrap();
+        assert!(!config.confirm_pull);
+    }
+
+    #[test]
+    fn test_normalize_origin_can_be_turned_off() {
+        assert!(Config::default().normalize_origin);
+        let config: Config = toml::from_str("normalize_origin = false").unwrap();
+        assert!(!config.normalize_origin);
+    }
+
+    #[test]
+    fn test_cell_aspect_note_only_when_far_off() {
+        assert_eq!(Config::default().cell_aspect_note(), None);
+        let config: Config = toml::from_str("cell_aspect = 2.3").unwrap();
+        assert_eq!(config.cell_aspect_note(), None);
+        let config: Config = toml::from_str("cell_aspect = 0.5").unwrap();
+        assert!(config.cell_aspect_note().unwrap().contains("cell_aspect = 0.5 looks off"));
+    }
+
+    #[test]
+    fn test_min_refresh_accepts_whole_numbers() {
+        let config: Config = toml::from_str("min_refresh = 100").unwrap();
+        assert_eq!(config.min_refresh, 100.0);
+    }
+
+    #[test]
+    fn test_scale_locks_parse() {
+        let config: Config = toml::from_str("scale_locked = [\"eDP-1\"]").unwrap();
+        assert_eq!(config.scale_locked, vec!["eDP-1".to_string()]);
+    }
+
+    #[test]
+    fn test_scale_strategy_parse() {
+        let config: Config = toml::from_str("scale_strategy = \"hypr-rounded\"").unwrap();
+        assert_eq!(config.scale_strategy, ScaleStrategy::HyprRounded);
+
+        let mut config = Config::default();
+        config.apply_env(|key| (key == "MONITUI_SCALE_STRATEGY").then(|| "Hypr-Rounded".to_string()));
+        assert_eq!(config.scale_strategy, ScaleStrategy::HyprRounded);
+    }
+
+    #[test]
+    fn test_backend_parse() {
+        let config: Config = toml::from_str("backend = \"wlr-randr\"").unwrap();
+        assert_eq!(config.backend, BackendChoice::WlrRandr);
+
+        let mut config = Config::default();
+        config.apply_env(|key| (key == "MONITUI_BACKEND").then(|| "hyprland".to_string()));
+        assert_eq!(config.backend, BackendChoice::Hyprland);
+    }
+
+    #[test]
+    fn test_keys_parse() {
+        let config: Config = toml::from_str("gap = 4\n[keys]\nmove_left = \"a\"\napply = [\"y\", \"Ctrl+s\"]").unwrap();
+        assert_eq!(config.gap, 4);
+        assert_eq!(config.keys["move_left"], KeyNames::One("a".to_string()));
+        assert_eq!(config.keys["apply"], KeyNames::Many(vec!["y".to_string(), "Ctrl+s".to_string()]));
+    }
+
+    #[test]
+    fn test_hooks_parse() {
+        let config: Config = toml::from_str("post_apply = \"pkill -SIGUSR2 waybar\"").unwrap();
+        assert_eq!(config.pre_apply, None);
+        assert_eq!(config.post_apply.as_deref(), Some("pkill -SIGUSR2 waybar"));
+
+        // An empty env var turns a hook from the file off
+        let mut config = config;
+        config.apply_env(|key| (key == "MONITUI_POST_APPLY").then(String::new));
+        assert_eq!(config.post_apply, None);
+    }
+
+    #[test]
+    fn test_env_overrides_file_values() {
+        let mut config: Config = toml::from_str("confirm_duration = 20\npoll_interval = 5").unwrap();
+        config.apply_env(|key| match key {
+            "MONITUI_CONFIRM_DURATION" => Some("30".to_string()),
+            "MONITUI_NOTIFY" => Some("no".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.confirm_duration, 30);
+        assert_eq!(config.poll_interval, 5);
+        assert!(!config.notify);
+    }
+
+    #[test]
+    fn test_invalid_env_values_are_ignored() {
+        let mut config = Config::default();
+        config.apply_env(|key| match key {
+            "MONITUI_POLL_INTERVAL" => Some("soon".to_string()),
+            "MONITUI_CONFIRM_PULL" => Some("maybe".to_string()),
+            _ => None,
+        });
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_parse_error_names_the_line() {
+        let err = parse("gap = 4\nauto_confirm = maybe\n").unwrap_err();
+        assert!(err.starts_with("line 2: "), "{}", err);
+        assert!(!err.contains('\n'), "{:?}", err);
+        assert!(parse("gap = 4\n").is_ok());
+    }
+
+    #[test]
+    fn test_writing_a_key_keeps_comments_and_order() {
+        let content = "# My monitors\nshow_all_monitors = false  # keep HEADLESS hidden\nauto_confirm = true\n\n[keys]\nquit = \"x\"\n";
+        let written = with_key(content, "show_all_monitors", toml_edit::value(true)).unwrap();
+        assert_eq!(
+            written,
+            "# My monitors\nshow_all_monitors = true  # keep HEADLESS hidden\nauto_confirm = true\n\n[keys]\nquit = \"x\"\n"
+        );
+
+        // A new key goes with the other top-level keys, ahead of the tables
+        let locks: toml_edit::Array = ["DP-1"].into_iter().collect();
+        let written = with_key(&written, "scale_locked", toml_edit::value(locks)).unwrap();
+        let config: Config = toml::from_str(&written).unwrap();
+        assert_eq!(config.scale_locked, vec!["DP-1".to_string()]);
+        assert!(config.keys.contains_key("quit"));
+        assert!(written.starts_with("# My monitors\n"));
+    }
+}
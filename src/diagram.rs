@@ -0,0 +1,99 @@
+use crate::layout;
+use crate::monitor::MonitorInfo;
+
+/// Width of the rendered image in pixels; the height follows the layout's proportions.
+const IMAGE_WIDTH: f64 = 960.0;
+
+/// Render the enabled monitors as an SVG diagram at true proportions, each one a
+/// labeled rectangle in layout (logical pixel) coordinates.
+pub fn render_svg(monitors: &[MonitorInfo]) -> Result<String, String> {
+    let enabled: Vec<&MonitorInfo> = monitors.iter().filter(|m| !m.disabled).collect();
+    let rects: Vec<_> = enabled.iter().map(|m| m.layout_rect()).collect();
+    let (x, y, w, h) = layout::bounding_box(&rects)
+        .ok_or_else(|| "No enabled monitors to draw".to_string())?;
+
+    let margin = w.max(h) as f64 * 0.03;
+    let view_w = w as f64 + margin * 2.0;
+    let view_h = h as f64 + margin * 2.0;
+    let stroke = w.max(h) as f64 / 400.0;
+
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" viewBox="{:.0} {:.0} {:.0} {:.0}">"#,
+            IMAGE_WIDTH,
+            IMAGE_WIDTH * view_h / view_w,
+            x as f64 - margin,
+            y as f64 - margin,
+            view_w,
+            view_h,
+        ),
+        format!(
+            r##"  <rect x="{:.0}" y="{:.0}" width="{:.0}" height="{:.0}" fill="#11111b"/>"##,
+            x as f64 - margin, y as f64 - margin, view_w, view_h,
+        ),
+    ];
+
+    for (m, r) in enabled.iter().zip(&rects) {
+        let font = r.w.min(r.h) as f64 / 9.0;
+        let cx = r.x as f64 + r.w as f64 / 2.0;
+        let cy = r.y as f64 + r.h as f64 / 2.0;
+        svg.push(format!(
+            r##"  <rect x="{}" y="{}" width="{}" height="{}" fill="#1e1e2e" stroke="#89dceb" stroke-width="{:.1}"/>"##,
+            r.x, r.y, r.w, r.h, stroke,
+        ));
+        svg.push(format!(
+            r##"  <text x="{:.0}" y="{:.0}" font-family="sans-serif" font-size="{:.0}" fill="#cdd6f4" text-anchor="middle">{}</text>"##,
+            cx, cy, font, escape(&m.name),
+        ));
+        svg.push(format!(
+            r##"  <text x="{:.0}" y="{:.0}" font-family="sans-serif" font-size="{:.0}" fill="#7f849c" text-anchor="middle">{}x{} @ {}x</text>"##,
+            cx, cy + font * 1.2, font * 0.6, m.width, m.height, m.scale,
+        ));
+    }
+
+    svg.push("</svg>".to_string());
+    svg.push(String::new());
+    Ok(svg.join("\n"))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_monitor(name: &str, x: i32, disabled: bool) -> MonitorInfo {
+        MonitorInfo {
+            name: name.to_string(),
+            x,
+            disabled,
+            dpms_status: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn draws_one_labeled_rect_per_enabled_monitor() {
+        let monitors = vec![
+            test_monitor("DP-1", 0, false),
+            test_monitor("HDMI<A>", 1920, false),
+            test_monitor("eDP-1", 0, true),
+        ];
+        let svg = render_svg(&monitors).unwrap();
+        assert!(svg.contains(r#"<rect x="0" y="0" width="1920" height="1080""#));
+        assert!(svg.contains(r#"<rect x="1920" y="0" width="1920" height="1080""#));
+        assert!(svg.contains(">HDMI&lt;A&gt;</text>"));
+        assert!(!svg.contains("eDP-1"));
+        assert!(svg.contains(r#"width="960" height="309""#));
+    }
+
+    #[test]
+    fn nothing_enabled_is_an_error() {
+        assert!(render_svg(&[test_monitor("DP-1", 0, true)]).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Hyprland events that mean the monitor setup may have changed under us.
+const MONITOR_EVENTS: &[&str] = &[
+    "monitoradded",
+    "monitoraddedv2",
+    "monitorremoved",
+    "monitorremovedv2",
+    "configreloaded",
+];
+
+/// Events for a monitor being plugged in or unplugged.
+const HOTPLUG_EVENTS: &[&str] = &[
+    "monitoradded",
+    "monitoraddedv2",
+    "monitorremoved",
+    "monitorremovedv2",
+];
+
+fn socket_path() -> Option<PathBuf> {
+    let runtime = env::var_os("XDG_RUNTIME_DIR")?;
+    let instance = env::var_os("HYPRLAND_INSTANCE_SIGNATURE")?;
+    Some(PathBuf::from(runtime).join("hypr").join(instance).join(".socket2.sock"))
+}
+
+/// Listen to Hyprland's event socket in a background thread. The receiver gets a
+/// message for every monitor-related event. Returns `None` if the socket can't be
+/// reached, in which case only timed polling will notice changes.
+pub fn subscribe() -> Option<Receiver<()>> {
+    listen(MONITOR_EVENTS)
+}
+
+/// Like `subscribe`, but only for monitors being plugged in or unplugged. Unlike
+/// `subscribe`, monitui's own applies (which reload the config) don't show up here.
+pub fn subscribe_hotplug() -> Option<Receiver<()>> {
+    listen(HOTPLUG_EVENTS)
+}
+
+fn listen(events: &'static [&'static str]) -> Option<Receiver<()>> {
+    let stream = UnixStream::connect(socket_path()?).ok()?;
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else { break };
+            // Stop once the app has gone away
+            if is_event(&line, events) && tx.send(()).is_err() {
+                break;
+            }
+        }
+    });
+    Some(rx)
+}
+
+/// Whether a raw socket line ("EVENT>>DATA") is one of `events`.
+fn is_event(line: &str, events: &[&str]) -> bool {
+    let event = line.split_once(">>").map_or(line, |(event, _)| event);
+    events.contains(&event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_monitor_event(line: &str) -> bool {
+        is_event(line, MONITOR_EVENTS)
+    }
+
+    #[test]
+    fn test_monitor_events_are_recognized() {
+        assert!(is_monitor_event("monitoradded>>DP-1"));
+        assert!(is_monitor_event("monitorremovedv2>>1,DP-1,Dell U2720Q"));
+        assert!(is_monitor_event("configreloaded>>"));
+        assert!(!is_monitor_event("workspace>>2"));
+        assert!(!is_monitor_event("activewindow>>kitty,~"));
+        assert!(!is_monitor_event("monitoraddedfoo>>DP-1"));
+    }
+
+    #[test]
+    fn test_hotplug_events_leave_out_reloads() {
+        assert!(is_event("monitoraddedv2>>1,DP-1,Dell U2720Q", HOTPLUG_EVENTS));
+        assert!(is_event("monitorremoved>>DP-1", HOTPLUG_EVENTS));
+        assert!(!is_event("configreloaded>>", HOTPLUG_EVENTS));
+    }
+}
@@ -0,0 +1,168 @@
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::backend::Backend;
+use crate::config::Config;
+use crate::monitor::MonitorInfo;
+
+/// How long a hook may run before it is killed.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Apply through `backend`, running the `pre_apply` hook before and the `post_apply`
+/// hook after a successful apply. A failing hook never stops the apply; its error is
+/// returned alongside the success so callers can show it.
+pub fn apply(backend: &dyn Backend, monitors: &[MonitorInfo], config: &Config) -> Result<Vec<String>, String> {
+    let mut failures = apply_after_pre(backend, monitors, config)?;
+    if let Some(command) = &config.post_apply {
+        if let Err(e) = run("post_apply", command, monitors, HOOK_TIMEOUT) {
+            failures.push(e);
+        }
+    }
+    Ok(failures)
+}
+
+/// `apply` for the TUI: the `post_apply` hook runs on its own thread, so a slow one
+/// doesn't freeze the screen. Its error, if it fails, arrives on the returned channel.
+pub fn apply_in_background(
+    backend: &dyn Backend,
+    monitors: &[MonitorInfo],
+    config: &Config,
+) -> Result<(Vec<String>, Option<Receiver<String>>), String> {
+    let failures = apply_after_pre(backend, monitors, config)?;
+    let post_apply = config.post_apply.clone().map(|command| {
+        let (tx, rx) = mpsc::channel();
+        let monitors = monitors.to_vec();
+        thread::spawn(move || {
+            if let Err(e) = run("post_apply", &command, &monitors, HOOK_TIMEOUT) {
+                tx.send(e).ok();
+            }
+        });
+        rx
+    });
+    Ok((failures, post_apply))
+}
+
+/// Run the `pre_apply` hook, then apply through `backend`.
+fn apply_after_pre(backend: &dyn Backend, monitors: &[MonitorInfo], config: &Config) -> Result<Vec<String>, String> {
+    let mut failures = Vec::new();
+    if let Some(command) = &config.pre_apply {
+        if let Err(e) = run("pre_apply", command, monitors, HOOK_TIMEOUT) {
+            failures.push(e);
+        }
+    }
+    backend.apply(monitors, config)?;
+    Ok(failures)
+}
+
+/// Environment handed to hooks describing the layout being applied.
+fn hook_env(hook: &str, monitors: &[MonitorInfo]) -> Vec<(&'static str, String)> {
+    let names = |disabled: bool| {
+        monitors.iter()
+            .filter(|m| m.disabled == disabled)
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>()
+    };
+    let enabled = names(false);
+    vec![
+        ("MONITUI_HOOK", hook.to_string()),
+        ("MONITUI_MONITORS", enabled.join(",")),
+        ("MONITUI_MONITOR_COUNT", enabled.len().to_string()),
+        ("MONITUI_DISABLED_MONITORS", names(true).join(",")),
+    ]
+}
+
+/// Run `command` through `sh -c`, killing it if it outlives `timeout`. Its output is
+/// discarded so it can't draw over the TUI.
+fn run(hook: &str, command: &str, monitors: &[MonitorInfo], timeout: Duration) -> Result<(), String> {
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .envs(hook_env(hook, monitors))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("{} hook failed to start: {}", hook, e))?;
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("{} hook failed ({})", hook, status)),
+            Ok(None) if start.elapsed() >= timeout => {
+                child.kill().ok();
+                child.wait().ok();
+                return Err(format!("{} hook timed out after {}s", hook, timeout.as_secs()));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("{} hook failed: {}", hook, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_monitor(name: &str, disabled: bool) -> MonitorInfo {
+        MonitorInfo {
+            name: name.to_string(),
+            description: "Test monitor".to_string(),
+            disabled,
+            ..Default::default()
+        }
+    }
+
+    fn sample() -> Vec<MonitorInfo> {
+        vec![test_monitor("eDP-1", false), test_monitor("DP-1", false), test_monitor("HDMI-A-1", true)]
+    }
+
+    #[test]
+    fn hook_env_lists_enabled_and_disabled() {
+        let env = hook_env("post_apply", &sample());
+        assert_eq!(env, vec![
+            ("MONITUI_HOOK", "post_apply".to_string()),
+            ("MONITUI_MONITORS", "eDP-1,DP-1".to_string()),
+            ("MONITUI_MONITOR_COUNT", "2".to_string()),
+            ("MONITUI_DISABLED_MONITORS", "HDMI-A-1".to_string()),
+        ]);
+    }
+
+    struct NoopBackend;
+
+    impl Backend for NoopBackend {
+        fn fetch_monitors(&self, _with_modes: bool) -> Vec<MonitorInfo> {
+            Vec::new()
+        }
+        fn apply(&self, _monitors: &[MonitorInfo], _config: &Config) -> Result<(), String> {
+            Ok(())
+        }
+        fn dry_run(&self, _monitors: &[MonitorInfo], _config: &Config) -> Result<String, String> {
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn post_apply_runs_in_the_background() {
+        let config = Config { post_apply: Some("sleep 0.3; exit 1".to_string()), ..Config::default() };
+        let start = Instant::now();
+        let (failures, post_apply) = apply_in_background(&NoopBackend, &sample(), &config).unwrap();
+        assert!(start.elapsed() < Duration::from_millis(300));
+        assert!(failures.is_empty());
+        let e = post_apply.unwrap().recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(e.contains("post_apply"));
+
+        let (_, post_apply) = apply_in_background(&NoopBackend, &sample(), &Config::default()).unwrap();
+        assert!(post_apply.is_none());
+    }
+
+    #[test]
+    fn run_reports_failures_and_timeouts() {
+        let monitors = sample();
+        assert!(run("pre_apply", "test \"$MONITUI_MONITOR_COUNT\" = 2", &monitors, HOOK_TIMEOUT).is_ok());
+        assert!(run("pre_apply", "exit 3", &monitors, HOOK_TIMEOUT).unwrap_err().contains("pre_apply"));
+        let timed_out = run("post_apply", "sleep 5", &monitors, Duration::from_millis(100)).unwrap_err();
+        assert!(timed_out.contains("timed out"));
+    }
+}
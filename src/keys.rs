@@ -0,0 +1,330 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::KeyNames;
+
+/// Something a key can do in the main view (no overlay open).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    NextMonitor,
+    PrevMonitor,
+    MoveLeft,
+    MoveDown,
+    MoveUp,
+    MoveRight,
+    SnapLeft,
+    SnapDown,
+    SnapUp,
+    SnapRight,
+    ResizeLeft,
+    ResizeDown,
+    ResizeUp,
+    ResizeRight,
+    Jump,
+    Filter,
+    Align,
+    Color,
+    Presets,
+    NextPreset,
+    PrevPreset,
+    Apply,
+    /// Apply and keep it at once, skipping the confirm countdown.
+    ApplyNow,
+    Disable,
+    Enable,
+    Solo,
+    CycleScale,
+    ScaleLock,
+    ExactScale,
+    ScaleUp,
+    ScaleDown,
+    Discard,
+    CycleResolution,
+    ModePicker,
+    CycleRefresh,
+    Mirror,
+    /// Turn the selected monitor's screen off or on (DPMS), leaving the layout alone.
+    Dpms,
+    Origin,
+    AutoSnap,
+    Rotate,
+    Flip,
+    ToggleAll,
+    ToggleView,
+    ToggleConfirm,
+    Baseline,
+    Primary,
+    Vrr,
+    WorkspaceInput,
+    /// Type the space to keep free at the selected monitor's edges.
+    ReservedInput,
+    ClearWorkspaces,
+    /// Assign workspace 1-9 to the selected monitor.
+    Workspace(u32),
+    /// Take workspace 1-9 off the selected monitor.
+    RemoveWorkspace(u32),
+    ZoomIn,
+    ZoomOut,
+    ResetView,
+    PanLeft,
+    PanDown,
+    PanUp,
+    PanRight,
+}
+
+impl Action {
+    /// Whether the action only looks around, so it still works in read-only mode.
+    pub fn is_read_only(self) -> bool {
+        matches!(self,
+            Action::Quit | Action::NextMonitor | Action::PrevMonitor | Action::Jump | Action::Filter
+            | Action::ToggleAll | Action::ToggleView | Action::ZoomIn | Action::ZoomOut
+            | Action::ResetView | Action::PanLeft | Action::PanDown | Action::PanUp | Action::PanRight)
+    }
+}
+
+/// Every action's name in the `[keys]` table and its default keys.
+/// `workspace_1`-`workspace_9` and `remove_workspace_1`-`remove_workspace_9` come on top.
+const ACTIONS: &[(&str, Action, &[&str])] = &[
+    ("quit", Action::Quit, &["q", "Esc"]),
+    ("next_monitor", Action::NextMonitor, &["Tab"]),
+    ("prev_monitor", Action::PrevMonitor, &["BackTab"]),
+    ("move_left", Action::MoveLeft, &["h", "Left"]),
+    ("move_down", Action::MoveDown, &["j", "Down"]),
+    ("move_up", Action::MoveUp, &["k", "Up"]),
+    ("move_right", Action::MoveRight, &["l", "Right"]),
+    ("snap_left", Action::SnapLeft, &["H", "Shift+Left"]),
+    ("snap_down", Action::SnapDown, &["J", "Shift+Down"]),
+    ("snap_up", Action::SnapUp, &["K", "Shift+Up"]),
+    ("snap_right", Action::SnapRight, &["L", "Shift+Right"]),
+    ("resize_left", Action::ResizeLeft, &["Alt+h"]),
+    ("resize_down", Action::ResizeDown, &["Alt+j"]),
+    ("resize_up", Action::ResizeUp, &["Alt+k"]),
+    ("resize_right", Action::ResizeRight, &["Alt+l"]),
+    ("jump", Action::Jump, &[":"]),
+    ("filter", Action::Filter, &["/"]),
+    ("align", Action::Align, &["A"]),
+    ("color", Action::Color, &["C"]),
+    ("presets", Action::Presets, &["p"]),
+    ("next_preset", Action::NextPreset, &["n"]),
+    ("prev_preset", Action::PrevPreset, &["N"]),
+    ("apply", Action::Apply, &["y", "Space", "Enter"]),
+    ("apply_now", Action::ApplyNow, &["Ctrl+Enter", "Y"]),
+    ("disable", Action::Disable, &["d"]),
+    ("enable", Action::Enable, &["e"]),
+    ("solo", Action::Solo, &["D"]),
+    ("cycle_scale", Action::CycleScale, &["s"]),
+    ("scale_lock", Action::ScaleLock, &["S"]),
+    ("exact_scale", Action::ExactScale, &["x"]),
+    ("scale_up", Action::ScaleUp, &["+", "="]),
+    ("scale_down", Action::ScaleDown, &["-"]),
+    ("discard", Action::Discard, &["Ctrl+z"]),
+    ("cycle_resolution", Action::CycleResolution, &["z"]),
+    ("mode_picker", Action::ModePicker, &["Z"]),
+    ("cycle_refresh", Action::CycleRefresh, &["f"]),
+    ("mirror", Action::Mirror, &["m"]),
+    ("dpms", Action::Dpms, &["O"]),
+    ("origin", Action::Origin, &["o"]),
+    ("auto_snap", Action::AutoSnap, &["a"]),
+    ("rotate", Action::Rotate, &["r", "R"]),
+    ("flip", Action::Flip, &["F"]),
+    ("toggle_all", Action::ToggleAll, &["t"]),
+    ("toggle_view", Action::ToggleView, &["v"]),
+    ("toggle_confirm", Action::ToggleConfirm, &["c"]),
+    ("baseline", Action::Baseline, &["b"]),
+    ("primary", Action::Primary, &["P"]),
+    ("vrr", Action::Vrr, &["V"]),
+    ("workspace_input", Action::WorkspaceInput, &["w"]),
+    ("reserved_input", Action::ReservedInput, &["B"]),
+    ("clear_workspaces", Action::ClearWorkspaces, &["W"]),
+    ("zoom_in", Action::ZoomIn, &["Alt+=", "Alt++"]),
+    ("zoom_out", Action::ZoomOut, &["Alt+-"]),
+    ("reset_view", Action::ResetView, &["Alt+0"]),
+    ("pan_left", Action::PanLeft, &["Ctrl+h", "Ctrl+Left"]),
+    ("pan_down", Action::PanDown, &["Ctrl+j", "Ctrl+Down"]),
+    ("pan_up", Action::PanUp, &["Ctrl+k", "Ctrl+Up"]),
+    ("pan_right", Action::PanRight, &["Ctrl+l", "Ctrl+Right"]),
+];
+
+/// A key with the modifiers that matter for matching it.
+type Key = (KeyCode, KeyModifiers);
+
+/// Which action each key triggers in the main view.
+#[derive(Clone, Debug)]
+pub struct Keymap(HashMap<Key, Action>);
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut map = HashMap::new();
+        for (_, action, keys) in ACTIONS {
+            for key in *keys {
+                map.insert(parse_key(key).expect("default keys parse"), *action);
+            }
+        }
+        for n in 1..=9 {
+            map.insert((KeyCode::Char(char::from(b'0' + n as u8)), KeyModifiers::NONE), Action::Workspace(n));
+            map.insert((KeyCode::Char(char::from(b'0' + n as u8)), KeyModifiers::ALT), Action::RemoveWorkspace(n));
+        }
+        Keymap(map)
+    }
+}
+
+impl Keymap {
+    /// The default keys with the `[keys]` table from config.toml on top. An action listed
+    /// there loses its default keys, and a key it takes stops doing anything else.
+    pub fn from_config(keys: &BTreeMap<String, KeyNames>) -> Result<Self, String> {
+        let mut keymap = Keymap::default();
+        for (name, names) in keys {
+            let action = action_by_name(name).ok_or_else(|| format!("unknown action \"{}\"", name))?;
+            keymap.0.retain(|_, a| *a != action);
+            let names = match names {
+                KeyNames::One(key) => std::slice::from_ref(key),
+                KeyNames::Many(keys) => keys.as_slice(),
+            };
+            for key in names {
+                let key = parse_key(key).ok_or_else(|| format!("can't read key \"{}\" for {}", key, name))?;
+                keymap.0.insert(key, action);
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.0.get(&normalize(key.code, key.modifiers)).copied()
+    }
+}
+
+fn action_by_name(name: &str) -> Option<Action> {
+    if let Some((_, action, _)) = ACTIONS.iter().find(|(n, _, _)| *n == name) {
+        return Some(*action);
+    }
+    let workspace = |n: &str| n.parse().ok().filter(|n| (1..=9).contains(n));
+    if let Some(n) = name.strip_prefix("remove_workspace_").and_then(workspace) {
+        return Some(Action::RemoveWorkspace(n));
+    }
+    name.strip_prefix("workspace_").and_then(workspace).map(Action::Workspace)
+}
+
+/// Parse a key like "h", "H", "Enter", "Shift+Left", "Ctrl+z" or "Alt++".
+/// Modifier and key names are case-insensitive; single characters are taken as typed.
+fn parse_key(s: &str) -> Option<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = s.trim();
+    while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+        modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        rest = key;
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::Char(c.to_ascii_uppercase()),
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_ascii_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            f => KeyCode::F(f.strip_prefix('f')?.parse().ok().filter(|n| (1..=24).contains(n))?),
+        },
+    };
+    Some(normalize(code, modifiers))
+}
+
+/// Terminals report Shift on typed characters (and Shift+Tab as BackTab) inconsistently;
+/// the character itself already says whether it was shifted, so Shift only counts on other keys.
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> Key {
+    let mut modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+    let code = match code {
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        code => code,
+    };
+    if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+        modifiers.remove(KeyModifiers::SHIFT);
+    }
+    (code, modifiers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_default_keys() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action(&key(KeyCode::Char('h'), KeyModifiers::NONE)), Some(Action::MoveLeft));
+        assert_eq!(keymap.action(&key(KeyCode::Char('H'), KeyModifiers::SHIFT)), Some(Action::SnapLeft));
+        assert_eq!(keymap.action(&key(KeyCode::Left, KeyModifiers::SHIFT)), Some(Action::SnapLeft));
+        assert_eq!(keymap.action(&key(KeyCode::Char('h'), KeyModifiers::ALT)), Some(Action::ResizeLeft));
+        assert_eq!(keymap.action(&key(KeyCode::Char('h'), KeyModifiers::CONTROL)), Some(Action::PanLeft));
+        assert_eq!(keymap.action(&key(KeyCode::Char('+'), KeyModifiers::SHIFT)), Some(Action::ScaleUp));
+        assert_eq!(keymap.action(&key(KeyCode::Char('+'), KeyModifiers::ALT | KeyModifiers::SHIFT)), Some(Action::ZoomIn));
+        assert_eq!(keymap.action(&key(KeyCode::BackTab, KeyModifiers::SHIFT)), Some(Action::PrevMonitor));
+        assert_eq!(keymap.action(&key(KeyCode::Char('3'), KeyModifiers::NONE)), Some(Action::Workspace(3)));
+        assert_eq!(keymap.action(&key(KeyCode::Char('3'), KeyModifiers::ALT)), Some(Action::RemoveWorkspace(3)));
+        assert_eq!(keymap.action(&key(KeyCode::Char('0'), KeyModifiers::NONE)), None);
+        assert_eq!(keymap.action(&key(KeyCode::Char('z'), KeyModifiers::CONTROL)), Some(Action::Discard));
+        assert_eq!(keymap.action(&key(KeyCode::Enter, KeyModifiers::CONTROL)), Some(Action::ApplyNow));
+        assert_eq!(keymap.action(&key(KeyCode::Enter, KeyModifiers::NONE)), Some(Action::Apply));
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("Ctrl+Enter"), Some((KeyCode::Enter, KeyModifiers::CONTROL)));
+        assert_eq!(parse_key("shift+h"), Some((KeyCode::Char('H'), KeyModifiers::NONE)));
+        assert_eq!(parse_key("Alt++"), Some((KeyCode::Char('+'), KeyModifiers::ALT)));
+        assert_eq!(parse_key("+"), Some((KeyCode::Char('+'), KeyModifiers::NONE)));
+        assert_eq!(parse_key("Shift+Tab"), Some((KeyCode::BackTab, KeyModifiers::NONE)));
+        assert_eq!(parse_key("F5"), Some((KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(parse_key("Hyper+x"), None);
+        assert_eq!(parse_key("nope"), None);
+    }
+
+    #[test]
+    fn test_config_overrides_defaults() {
+        let keys: BTreeMap<String, KeyNames> = toml::from_str(
+            "move_left = \"a\"\napply = [\"Ctrl+s\", \"Enter\"]\nworkspace_1 = \"F1\"",
+        ).unwrap();
+        let keymap = Keymap::from_config(&keys).unwrap();
+        // The action's old keys are gone, and the key it took no longer auto-snaps
+        assert_eq!(keymap.action(&key(KeyCode::Char('h'), KeyModifiers::NONE)), None);
+        assert_eq!(keymap.action(&key(KeyCode::Char('a'), KeyModifiers::NONE)), Some(Action::MoveLeft));
+        assert_eq!(keymap.action(&key(KeyCode::Left, KeyModifiers::NONE)), None);
+        assert_eq!(keymap.action(&key(KeyCode::Char('y'), KeyModifiers::NONE)), None);
+        assert_eq!(keymap.action(&key(KeyCode::Char('s'), KeyModifiers::CONTROL)), Some(Action::Apply));
+        assert_eq!(keymap.action(&key(KeyCode::Enter, KeyModifiers::NONE)), Some(Action::Apply));
+        assert_eq!(keymap.action(&key(KeyCode::F(1), KeyModifiers::NONE)), Some(Action::Workspace(1)));
+        assert_eq!(keymap.action(&key(KeyCode::Char('1'), KeyModifiers::NONE)), None);
+        // Everything else keeps its default
+        assert_eq!(keymap.action(&key(KeyCode::Char('j'), KeyModifiers::NONE)), Some(Action::MoveDown));
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let keys: BTreeMap<String, KeyNames> = toml::from_str("fly = \"x\"").unwrap();
+        assert!(Keymap::from_config(&keys).unwrap_err().contains("unknown action \"fly\""));
+        let keys: BTreeMap<String, KeyNames> = toml::from_str("workspace_10 = \"x\"").unwrap();
+        assert!(Keymap::from_config(&keys).is_err());
+        let keys: BTreeMap<String, KeyNames> = toml::from_str("quit = \"Super+q\"").unwrap();
+        assert!(Keymap::from_config(&keys).unwrap_err().contains("\"Super+q\" for quit"));
+    }
+}
@@ -50,20 +50,22 @@ pub enum SharedEdge {
 }
 
 /// Find which edge two monitors share, if any.
-/// They must be touching (edges exactly meeting) AND have overlap on the perpendicular axis.
-pub fn shared_edge(a: &LayoutMonitor, b: &LayoutMonitor) -> Option<SharedEdge> {
+/// They must be touching (edges meeting, give or take `gap` pixels) AND have overlap on
+/// the perpendicular axis. The edge coordinate is always `a`'s side of the shared edge.
+pub fn shared_edge(a: &LayoutMonitor, b: &LayoutMonitor, gap: i32) -> Option<SharedEdge> {
+    let meets = |edge: i32, other: i32| (other - edge).abs() <= gap;
     // Check vertical edge (side by side)
-    if a.right() == b.x && a.vertical_overlap(b).is_some() {
+    if meets(a.right(), b.x) && a.vertical_overlap(b).is_some() {
         return Some(SharedEdge::Vertical(a.right()));
     }
-    if b.right() == a.x && a.vertical_overlap(b).is_some() {
+    if meets(b.right(), a.x) && a.vertical_overlap(b).is_some() {
         return Some(SharedEdge::Vertical(a.x));
     }
     // Check horizontal edge (stacked)
-    if a.bottom() == b.y && a.horizontal_overlap(b).is_some() {
+    if meets(a.bottom(), b.y) && a.horizontal_overlap(b).is_some() {
         return Some(SharedEdge::Horizontal(a.bottom()));
     }
-    if b.bottom() == a.y && a.horizontal_overlap(b).is_some() {
+    if meets(b.bottom(), a.y) && a.horizontal_overlap(b).is_some() {
         return Some(SharedEdge::Horizontal(a.y));
     }
     None
@@ -73,7 +75,9 @@ pub fn shared_edge(a: &LayoutMonitor, b: &LayoutMonitor) -> Option<SharedEdge> {
 /// - Perpendicular to shared edge: swap positions
 /// - Parallel to shared edge: slide along it
 /// - If no neighbor with shared edge: try snap
-pub fn move_monitor(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir: Direction, step: i32) {
+///
+/// `gap` is the spacing (in logical pixels) left between neighbors; see `shared_edge`.
+pub fn move_monitor(monitors: &mut [LayoutMonitor], selected: usize, dir: Direction, step: i32, gap: i32) {
     if monitors.len() <= 1 { return; }
 
     // Find neighbors that share an edge with the selected monitor.
@@ -84,12 +88,12 @@ pub fn move_monitor(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir: Dir
 
     for (i, m) in monitors.iter().enumerate() {
         if i == selected { continue; }
-        if let Some(edge) = shared_edge(sel, m) {
-            let is_perp = match (&edge, dir) {
-                (SharedEdge::Vertical(_), Direction::Left | Direction::Right) => true,
-                (SharedEdge::Horizontal(_), Direction::Up | Direction::Down) => true,
-                _ => false,
-            };
+        if let Some(edge) = shared_edge(sel, m, gap) {
+            let is_perp = matches!(
+                (&edge, dir),
+                (SharedEdge::Vertical(_), Direction::Left | Direction::Right)
+                    | (SharedEdge::Horizontal(_), Direction::Up | Direction::Down)
+            );
 
             if is_perp {
                 // Only accept if the neighbor is actually in the direction we're pressing
@@ -111,18 +115,18 @@ pub fn move_monitor(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir: Dir
 
     if let Some((ni, _)) = perp_neighbor {
         // Perpendicular neighbor in the right direction — swap
-        swap_monitors(monitors, selected, ni);
+        swap_monitors(monitors, selected, ni, gap);
     } else if let Some((ni, _)) = parallel_neighbor {
         // No perpendicular neighbor in that direction — slide along a parallel edge
-        slide_monitor(monitors, selected, ni, dir, step);
+        slide_monitor(monitors, selected, ni, dir, step, gap);
     }
     // If neither: monitor is already at the edge in that direction — do nothing
 }
 
 /// Swap two monitors' positions. Each takes the other's position,
-/// adjusted so they remain touching. Also shifts other monitors
-/// to fill gaps caused by different sizes.
-pub fn swap_monitors(monitors: &mut Vec<LayoutMonitor>, a: usize, b: usize) {
+/// adjusted so they remain touching (keeping the spacing between them). Also shifts
+/// other monitors to fill gaps caused by different sizes.
+pub fn swap_monitors(monitors: &mut [LayoutMonitor], a: usize, b: usize, gap: i32) {
     let a_x = monitors[a].x;
     let a_y = monitors[a].y;
     let b_x = monitors[b].x;
@@ -132,35 +136,37 @@ pub fn swap_monitors(monitors: &mut Vec<LayoutMonitor>, a: usize, b: usize) {
     let a_h = monitors[a].h;
     let b_h = monitors[b].h;
 
-    if let Some(edge) = shared_edge(&monitors[a], &monitors[b]) {
+    if let Some(edge) = shared_edge(&monitors[a], &monitors[b], gap) {
         match edge {
             SharedEdge::Vertical(_) => {
                 let left = a_x.min(b_x);
                 let size_diff = b_w - a_w;
                 if a_x < b_x {
                     // a was left, b was right → swap
+                    let space = b_x - (a_x + a_w);
                     monitors[b].x = left;
-                    monitors[a].x = left + b_w;
+                    monitors[a].x = left + b_w + space;
                     // Shift all monitors to the right of the old b position
                     let old_b_right = b_x + b_w;
-                    for i in 0..monitors.len() {
+                    for (i, m) in monitors.iter_mut().enumerate() {
                         if i == a || i == b { continue; }
-                        if monitors[i].x >= old_b_right {
-                            monitors[i].x -= size_diff;
-                        } else if monitors[i].x >= a_x + a_w && monitors[i].x < b_x {
-                            monitors[i].x += size_diff;
+                        if m.x >= old_b_right {
+                            m.x -= size_diff;
+                        } else if m.x >= a_x + a_w && m.x < b_x {
+                            m.x += size_diff;
                         }
                     }
                 } else {
+                    let space = a_x - (b_x + b_w);
                     monitors[a].x = left;
-                    monitors[b].x = left + a_w;
+                    monitors[b].x = left + a_w + space;
                     let old_a_right = a_x + a_w;
-                    for i in 0..monitors.len() {
+                    for (i, m) in monitors.iter_mut().enumerate() {
                         if i == a || i == b { continue; }
-                        if monitors[i].x >= old_a_right {
-                            monitors[i].x += size_diff;
-                        } else if monitors[i].x >= b_x + b_w && monitors[i].x < a_x {
-                            monitors[i].x -= size_diff;
+                        if m.x >= old_a_right {
+                            m.x += size_diff;
+                        } else if m.x >= b_x + b_w && m.x < a_x {
+                            m.x -= size_diff;
                         }
                     }
                 }
@@ -169,23 +175,25 @@ pub fn swap_monitors(monitors: &mut Vec<LayoutMonitor>, a: usize, b: usize) {
                 let top = a_y.min(b_y);
                 let size_diff = b_h - a_h;
                 if a_y < b_y {
+                    let space = b_y - (a_y + a_h);
                     monitors[b].y = top;
-                    monitors[a].y = top + b_h;
+                    monitors[a].y = top + b_h + space;
                     let old_b_bottom = b_y + b_h;
-                    for i in 0..monitors.len() {
+                    for (i, m) in monitors.iter_mut().enumerate() {
                         if i == a || i == b { continue; }
-                        if monitors[i].y >= old_b_bottom {
-                            monitors[i].y -= size_diff;
+                        if m.y >= old_b_bottom {
+                            m.y -= size_diff;
                         }
                     }
                 } else {
+                    let space = a_y - (b_y + b_h);
                     monitors[a].y = top;
-                    monitors[b].y = top + a_h;
+                    monitors[b].y = top + a_h + space;
                     let old_a_bottom = a_y + a_h;
-                    for i in 0..monitors.len() {
+                    for (i, m) in monitors.iter_mut().enumerate() {
                         if i == a || i == b { continue; }
-                        if monitors[i].y >= old_a_bottom {
-                            monitors[i].y += size_diff;
+                        if m.y >= old_a_bottom {
+                            m.y += size_diff;
                         }
                     }
                 }
@@ -195,8 +203,9 @@ pub fn swap_monitors(monitors: &mut Vec<LayoutMonitor>, a: usize, b: usize) {
 }
 
 /// Slide a monitor along a shared edge.
-/// If the slide causes them to lose their shared edge, snap to stacked/side-by-side.
-pub fn slide_monitor(monitors: &mut Vec<LayoutMonitor>, selected: usize, neighbor: usize, dir: Direction, step: i32) {
+/// If the slide causes them to lose their shared edge, snap to stacked/side-by-side,
+/// `gap` pixels apart.
+pub fn slide_monitor(monitors: &mut [LayoutMonitor], selected: usize, neighbor: usize, dir: Direction, step: i32, gap: i32) {
     let delta = match dir {
         Direction::Up => -step,
         Direction::Down => step,
@@ -215,7 +224,7 @@ pub fn slide_monitor(monitors: &mut Vec<LayoutMonitor>, selected: usize, neighbo
     }
 
     // Check if they still share an edge
-    let still_touching = shared_edge(&monitors[selected], &monitors[neighbor]).is_some();
+    let still_touching = shared_edge(&monitors[selected], &monitors[neighbor], gap).is_some();
 
     if !still_touching {
         // Copy neighbor values to avoid borrow issues
@@ -234,19 +243,19 @@ pub fn slide_monitor(monitors: &mut Vec<LayoutMonitor>, selected: usize, neighbo
         match dir {
             Direction::Up | Direction::Down => {
                 if sel_cy < nbr_cy {
-                    monitors[selected].y = nbr_y - monitors[selected].h;
+                    monitors[selected].y = nbr_y - monitors[selected].h - gap;
                     monitors[selected].x = nbr_x;
                 } else {
-                    monitors[selected].y = nbr_bottom;
+                    monitors[selected].y = nbr_bottom + gap;
                     monitors[selected].x = nbr_x;
                 }
             }
             Direction::Left | Direction::Right => {
                 if sel_cx < nbr_cx {
-                    monitors[selected].x = nbr_x - monitors[selected].w;
+                    monitors[selected].x = nbr_x - monitors[selected].w - gap;
                     monitors[selected].y = nbr_y;
                 } else {
-                    monitors[selected].x = nbr_right;
+                    monitors[selected].x = nbr_right + gap;
                     monitors[selected].y = nbr_y;
                 }
             }
@@ -256,7 +265,7 @@ pub fn slide_monitor(monitors: &mut Vec<LayoutMonitor>, selected: usize, neighbo
 
 /// Snap `selected` to the far side of the entire layout in the given direction.
 /// E.g. Shift+L moves the monitor to the rightmost position, Shift+H to the leftmost.
-pub fn snap_to_far_side(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir: Direction) {
+pub fn snap_to_far_side(monitors: &mut [LayoutMonitor], selected: usize, dir: Direction, gap: i32) {
     if monitors.len() <= 1 { return; }
 
     // Remove selected from consideration to find the remaining layout bounds
@@ -287,7 +296,7 @@ pub fn snap_to_far_side(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir:
                 if i == selected { continue; }
                 if m.x == min_x { ref_y = m.y; break; }
             }
-            monitors[selected].x = min_x - sel_w;
+            monitors[selected].x = min_x - sel_w - gap;
             monitors[selected].y = ref_y;
         }
         Direction::Right => {
@@ -295,7 +304,7 @@ pub fn snap_to_far_side(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir:
                 if i == selected { continue; }
                 if m.right() == max_x { ref_y = m.y; break; }
             }
-            monitors[selected].x = max_x;
+            monitors[selected].x = max_x + gap;
             monitors[selected].y = ref_y;
         }
         Direction::Up => {
@@ -303,7 +312,7 @@ pub fn snap_to_far_side(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir:
                 if i == selected { continue; }
                 if m.y == min_y { ref_x = m.x; break; }
             }
-            monitors[selected].y = min_y - sel_h;
+            monitors[selected].y = min_y - sel_h - gap;
             monitors[selected].x = ref_x;
         }
         Direction::Down => {
@@ -311,15 +320,55 @@ pub fn snap_to_far_side(monitors: &mut Vec<LayoutMonitor>, selected: usize, dir:
                 if i == selected { continue; }
                 if m.bottom() == max_y { ref_x = m.x; break; }
             }
-            monitors[selected].y = max_y;
+            monitors[selected].y = max_y + gap;
             monitors[selected].x = ref_x;
         }
     }
 }
 
+/// The monitor closest to `selected` (by center distance) whose center lies in `dir`
+/// from it, or level with it on that axis.
+pub fn find_nearest(monitors: &[LayoutMonitor], selected: usize, dir: Direction) -> Option<usize> {
+    let center = |m: &LayoutMonitor| (m.x + m.w / 2, m.y + m.h / 2);
+    let (cx, cy) = center(&monitors[selected]);
+    (0..monitors.len())
+        .filter(|&i| i != selected)
+        .filter(|&i| {
+            let (ox, oy) = center(&monitors[i]);
+            match dir {
+                Direction::Left => ox <= cx,
+                Direction::Right => ox >= cx,
+                Direction::Up => oy <= cy,
+                Direction::Down => oy >= cy,
+            }
+        })
+        .min_by_key(|&i| {
+            let (ox, oy) = center(&monitors[i]);
+            (cx - ox).abs() + (cy - oy).abs()
+        })
+}
+
+/// Line up the `dir` edge of `selected` (left, right, top or bottom) with the same edge
+/// of its nearest neighbor in that direction, then push it out of anything it now overlaps.
+/// Returns the neighbor it was aligned to.
+pub fn align_edge(monitors: &mut [LayoutMonitor], selected: usize, dir: Direction, gap: i32) -> Option<usize> {
+    let neighbor = find_nearest(monitors, selected, dir)?;
+    let target = monitors[neighbor].clone();
+    let m = &mut monitors[selected];
+    match dir {
+        Direction::Left => m.x = target.x,
+        Direction::Right => m.x = target.right() - m.w,
+        Direction::Up => m.y = target.y,
+        Direction::Down => m.y = target.bottom() - m.h,
+    }
+    let (x, y) = (m.x, m.y);
+    resolve_overlaps(monitors, selected, x, y, gap);
+    Some(neighbor)
+}
+
 /// Ensure all monitors are connected to the layout by snapping any floating ones
-/// to the nearest monitor. Call after every move operation.
-pub fn auto_snap_all(monitors: &mut Vec<LayoutMonitor>) {
+/// to the nearest monitor, `gap` pixels from it. Call after every move operation.
+pub fn auto_snap_all(monitors: &mut [LayoutMonitor], gap: i32) {
     if monitors.len() <= 1 { return; }
 
     // Iterate until stable (max iterations = len to prevent infinite loops)
@@ -328,7 +377,7 @@ pub fn auto_snap_all(monitors: &mut Vec<LayoutMonitor>) {
         for i in 0..monitors.len() {
             // Check if this monitor touches at least one other
             let touches_any = (0..monitors.len())
-                .any(|j| j != i && shared_edge(&monitors[i], &monitors[j]).is_some());
+                .any(|j| j != i && shared_edge(&monitors[i], &monitors[j], gap).is_some());
 
             if !touches_any {
                 // Find nearest monitor by center distance and snap to closest edge
@@ -359,18 +408,18 @@ pub fn auto_snap_all(monitors: &mut Vec<LayoutMonitor>) {
                     if dx.abs() > dy.abs() {
                         // Snap horizontally
                         if dx > 0 {
-                            monitors[i].x = tx + tw;
+                            monitors[i].x = tx + tw + gap;
                         } else {
-                            monitors[i].x = tx - monitors[i].w;
+                            monitors[i].x = tx - monitors[i].w - gap;
                         }
                         // Align y to maximize overlap
                         monitors[i].y = ty;
                     } else {
                         // Snap vertically
                         if dy > 0 {
-                            monitors[i].y = ty + th;
+                            monitors[i].y = ty + th + gap;
                         } else {
-                            monitors[i].y = ty - monitors[i].h;
+                            monitors[i].y = ty - monitors[i].h - gap;
                         }
                         monitors[i].x = tx;
                     }
@@ -382,10 +431,29 @@ pub fn auto_snap_all(monitors: &mut Vec<LayoutMonitor>) {
     }
 }
 
+/// Whether every monitor can be reached from every other through shared edges.
+/// An empty layout counts as connected.
+pub fn is_layout_connected(monitors: &[LayoutMonitor], gap: i32) -> bool {
+    if monitors.is_empty() { return true; }
+    let mut reached = vec![false; monitors.len()];
+    let mut queue = vec![0];
+    reached[0] = true;
+    while let Some(i) = queue.pop() {
+        for j in 0..monitors.len() {
+            if !reached[j] && shared_edge(&monitors[i], &monitors[j], gap).is_some() {
+                reached[j] = true;
+                queue.push(j);
+            }
+        }
+    }
+    reached.iter().all(|&r| r)
+}
+
 /// Push `moved` monitor out of any overlapping monitors.
 /// Picks the push direction that places the monitor closest to `orig_x, orig_y`
 /// (its position before the operation), so it doesn't overshoot to the wrong side.
-pub fn resolve_overlaps(monitors: &mut Vec<LayoutMonitor>, moved: usize, orig_x: i32, orig_y: i32) {
+/// Pushed monitors end up `gap` pixels from the one they overlapped.
+pub fn resolve_overlaps(monitors: &mut [LayoutMonitor], moved: usize, orig_x: i32, orig_y: i32, gap: i32) {
     for _ in 0..monitors.len() {
         let mut best_push: Option<(i32, i32, i64)> = None; // (dx, dy, dist_to_origin)
 
@@ -399,10 +467,10 @@ pub fn resolve_overlaps(monitors: &mut Vec<LayoutMonitor>, moved: usize, orig_x:
                 continue;
             }
 
-            let push_left = monitors[j].x - monitors[moved].right();
-            let push_right = monitors[j].right() - monitors[moved].x;
-            let push_up = monitors[j].y - monitors[moved].bottom();
-            let push_down = monitors[j].bottom() - monitors[moved].y;
+            let push_left = monitors[j].x - monitors[moved].right() - gap;
+            let push_right = monitors[j].right() - monitors[moved].x + gap;
+            let push_up = monitors[j].y - monitors[moved].bottom() - gap;
+            let push_down = monitors[j].bottom() - monitors[moved].y + gap;
 
             let candidates = [
                 (push_left, 0),
@@ -437,14 +505,83 @@ pub fn resolve_overlaps(monitors: &mut Vec<LayoutMonitor>, moved: usize, orig_x:
 }
 
 /// Normalize layout so the top-left monitor is at (0, 0).
-pub fn normalize(monitors: &mut Vec<LayoutMonitor>) {
-    if monitors.is_empty() { return; }
+/// Returns the (x, y) shift that was applied to every monitor.
+pub fn normalize(monitors: &mut [LayoutMonitor]) -> (i32, i32) {
+    if monitors.is_empty() { return (0, 0); }
     let min_x = monitors.iter().map(|m| m.x).min().unwrap();
     let min_y = monitors.iter().map(|m| m.y).min().unwrap();
     for m in monitors.iter_mut() {
         m.x -= min_x;
         m.y -= min_y;
     }
+    (-min_x, -min_y)
+}
+
+/// Shift the layout so `anchor` sits at (0, 0), keeping relative positions.
+/// Returns the (x, y) shift that was applied to every monitor.
+pub fn anchor_at_origin(monitors: &mut [LayoutMonitor], anchor: usize) -> (i32, i32) {
+    let (dx, dy) = (-monitors[anchor].x, -monitors[anchor].y);
+    for m in monitors.iter_mut() {
+        m.x += dx;
+        m.y += dy;
+    }
+    (dx, dy)
+}
+
+/// Regions where two monitors overlap, as (x, y, w, h).
+pub fn overlaps(monitors: &[LayoutMonitor]) -> Vec<(i32, i32, i32, i32)> {
+    let mut regions = Vec::new();
+    for (i, a) in monitors.iter().enumerate() {
+        for b in &monitors[i + 1..] {
+            if let (Some((x1, x2)), Some((y1, y2))) = (a.horizontal_overlap(b), a.vertical_overlap(b)) {
+                regions.push((x1, y1, x2 - x1, y2 - y1));
+            }
+        }
+    }
+    regions
+}
+
+/// Lines across the empty space between monitors that face each other more than `gap`
+/// pixels apart with nothing in between, as ((x1, y1), (x2, y2)) midway along where they face.
+pub fn gaps(monitors: &[LayoutMonitor], gap: i32) -> Vec<((i32, i32), (i32, i32))> {
+    let mut lines = Vec::new();
+    for a in monitors {
+        for b in monitors {
+            // Each pair is looked at once per axis: b to the right of / below a
+            let space = if b.x - a.right() > gap {
+                a.vertical_overlap(b).map(|(y1, y2)| LayoutMonitor {
+                    id: String::new(), x: a.right(), y: y1, w: b.x - a.right(), h: y2 - y1,
+                })
+            } else if b.y - a.bottom() > gap {
+                a.horizontal_overlap(b).map(|(x1, x2)| LayoutMonitor {
+                    id: String::new(), x: x1, y: a.bottom(), w: x2 - x1, h: b.y - a.bottom(),
+                })
+            } else {
+                None
+            };
+            let Some(space) = space else { continue };
+            let blocked = monitors.iter()
+                .any(|c| c.horizontal_overlap(&space).is_some() && c.vertical_overlap(&space).is_some());
+            if blocked { continue; }
+            lines.push(if space.x == a.right() {
+                let mid = space.y + space.h / 2;
+                ((space.x, mid), (space.right(), mid))
+            } else {
+                let mid = space.x + space.w / 2;
+                ((mid, space.y), (mid, space.bottom()))
+            });
+        }
+    }
+    lines
+}
+
+/// The rectangle enclosing every monitor, as (x, y, w, h). `None` if there are no monitors.
+pub fn bounding_box(monitors: &[LayoutMonitor]) -> Option<(i32, i32, i32, i32)> {
+    let min_x = monitors.iter().map(|m| m.x).min()?;
+    let min_y = monitors.iter().map(|m| m.y).min()?;
+    let max_x = monitors.iter().map(|m| m.right()).max()?;
+    let max_y = monitors.iter().map(|m| m.bottom()).max()?;
+    Some((min_x, min_y, max_x - min_x, max_y - min_y))
 }
 
 #[cfg(test)]
@@ -479,15 +616,15 @@ mod tests {
     fn test_shared_edge_side_by_side() {
         let a = LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 };
         let b = LayoutMonitor { id: "B".into(), x: 1920, y: 0, w: 1920, h: 1080 };
-        assert_eq!(shared_edge(&a, &b), Some(SharedEdge::Vertical(1920)));
-        assert_eq!(shared_edge(&b, &a), Some(SharedEdge::Vertical(1920)));
+        assert_eq!(shared_edge(&a, &b, 0), Some(SharedEdge::Vertical(1920)));
+        assert_eq!(shared_edge(&b, &a, 0), Some(SharedEdge::Vertical(1920)));
     }
 
     #[test]
     fn test_shared_edge_stacked() {
         let a = LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 };
         let b = LayoutMonitor { id: "B".into(), x: 0, y: 1080, w: 1920, h: 1080 };
-        assert_eq!(shared_edge(&a, &b), Some(SharedEdge::Horizontal(1080)));
+        assert_eq!(shared_edge(&a, &b, 0), Some(SharedEdge::Horizontal(1080)));
     }
 
     #[test]
@@ -495,7 +632,7 @@ mod tests {
         let a = LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 };
         let b = LayoutMonitor { id: "B".into(), x: 1920, y: 500, w: 1920, h: 1080 };
         // They share a vertical edge at x=1920, and have vertical overlap (500..1080)
-        assert_eq!(shared_edge(&a, &b), Some(SharedEdge::Vertical(1920)));
+        assert_eq!(shared_edge(&a, &b, 0), Some(SharedEdge::Vertical(1920)));
     }
 
     #[test]
@@ -503,14 +640,60 @@ mod tests {
         let a = LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 };
         let b = LayoutMonitor { id: "B".into(), x: 1920, y: 1080, w: 1920, h: 1080 };
         // They touch at a single corner point (1920, 1080) — no edge overlap
-        assert_eq!(shared_edge(&a, &b), None);
+        assert_eq!(shared_edge(&a, &b, 0), None);
     }
 
     #[test]
     fn test_shared_edge_gap() {
         let a = LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 };
         let b = LayoutMonitor { id: "B".into(), x: 1921, y: 0, w: 1920, h: 1080 };
-        assert_eq!(shared_edge(&a, &b), None);
+        assert_eq!(shared_edge(&a, &b, 0), None);
+    }
+
+    // --- align tests ---
+
+    #[test]
+    fn test_align_tops_of_different_heights() {
+        let mut m = two_side_by_side_different_heights();
+        m[1].y = 200;
+        assert_eq!(align_edge(&mut m, 1, Direction::Up, 0), Some(0));
+        assert_eq!(m[1].y, 0);
+        assert_eq!(m[1].x, 1920);
+        assert!(shared_edge(&m[0], &m[1], 0).is_some());
+    }
+
+    #[test]
+    fn test_align_bottoms_of_different_heights() {
+        let mut m = two_side_by_side_different_heights();
+        assert_eq!(align_edge(&mut m, 0, Direction::Down, 0), Some(1));
+        assert_eq!(m[0].bottom(), m[1].bottom());
+        assert_eq!(m[0].y, 360);
+    }
+
+    #[test]
+    fn test_align_into_overlap_is_pushed_out() {
+        let mut m = two_side_by_side_different_heights();
+        // Lining up B's left edge with A's would stack them on top of each other
+        align_edge(&mut m, 1, Direction::Left, 0);
+        assert_eq!(m[1].x, 0);
+        assert!(m[0].horizontal_overlap(&m[1]).is_none() || m[0].vertical_overlap(&m[1]).is_none());
+    }
+
+    #[test]
+    fn test_align_without_neighbor_in_direction() {
+        let mut m = two_side_by_side_different_heights();
+        let before = m.clone();
+        // Nothing's center sits to the right of B
+        assert_eq!(align_edge(&mut m, 1, Direction::Right, 0), None);
+        assert_eq!(m, before);
+    }
+
+    #[test]
+    fn test_anchor_at_origin() {
+        let mut m = three_side_by_side();
+        assert_eq!(anchor_at_origin(&mut m, 1), (-1920, 0));
+        assert_eq!((m[0].x, m[1].x, m[2].x), (-1920, 0, 1920));
+        assert!(shared_edge(&m[0], &m[1], 0).is_some());
     }
 
     // --- swap tests ---
@@ -518,7 +701,7 @@ mod tests {
     #[test]
     fn test_swap_side_by_side() {
         let mut m = three_side_by_side();
-        swap_monitors(&mut m, 0, 1);
+        swap_monitors(&mut m, 0, 1, 0);
         // A and B should swap. B is now left, A is now right
         assert_eq!(m[1].x, 0); // B moved to 0
         assert_eq!(m[0].x, 1920); // A moved to 1920
@@ -529,7 +712,7 @@ mod tests {
     #[test]
     fn test_swap_stacked() {
         let mut m = two_stacked();
-        swap_monitors(&mut m, 0, 1);
+        swap_monitors(&mut m, 0, 1, 0);
         assert_eq!(m[1].y, 0); // B is now on top
         assert_eq!(m[0].y, 1080); // A is below
     }
@@ -540,11 +723,11 @@ mod tests {
     fn test_slide_vertical_along_shared_vertical_edge() {
         let mut m = two_side_by_side_different_heights();
         // A and B share a vertical edge. Sliding A down (parallel to edge).
-        slide_monitor(&mut m, 0, 1, Direction::Down, 100);
+        slide_monitor(&mut m, 0, 1, Direction::Down, 100, 0);
         assert_eq!(m[0].y, 100);
         assert_eq!(m[0].x, 0); // x unchanged
         // Still sharing edge
-        assert!(shared_edge(&m[0], &m[1]).is_some());
+        assert!(shared_edge(&m[0], &m[1], 0).is_some());
     }
 
     #[test]
@@ -554,7 +737,7 @@ mod tests {
             LayoutMonitor { id: "B".into(), x: 1920, y: 0, w: 1920, h: 1080 },
         ];
         // Slide A down by more than B's height — should snap below B
-        slide_monitor(&mut m, 0, 1, Direction::Down, 1200);
+        slide_monitor(&mut m, 0, 1, Direction::Down, 1200, 0);
         // A should now be below B with left edges aligned
         assert_eq!(m[0].y, 1080);
         assert_eq!(m[0].x, m[1].x);
@@ -566,7 +749,7 @@ mod tests {
     fn test_move_perpendicular_swaps() {
         let mut m = three_side_by_side();
         // Move B (index 1) left — perpendicular to the vertical edge it shares with A
-        move_monitor(&mut m, 1, Direction::Left, 10);
+        move_monitor(&mut m, 1, Direction::Left, 10, 0);
         // B and A should swap
         assert_eq!(m[1].x, 0); // B
         assert_eq!(m[0].x, 1920); // A
@@ -576,11 +759,11 @@ mod tests {
     fn test_move_parallel_slides() {
         let mut m = three_side_by_side();
         // Move B (index 1) down — parallel to the vertical edges it shares
-        move_monitor(&mut m, 1, Direction::Down, 50);
+        move_monitor(&mut m, 1, Direction::Down, 50, 0);
         assert_eq!(m[1].y, 50);
         // B still shares edge with A and C
-        assert!(shared_edge(&m[0], &m[1]).is_some());
-        assert!(shared_edge(&m[1], &m[2]).is_some());
+        assert!(shared_edge(&m[0], &m[1], 0).is_some());
+        assert!(shared_edge(&m[1], &m[2], 0).is_some());
     }
 
     #[test]
@@ -588,7 +771,7 @@ mod tests {
         let mut m = three_side_by_side();
         let before = m.clone();
         // A is already the leftmost — pressing left should do nothing
-        move_monitor(&mut m, 0, Direction::Left, 10);
+        move_monitor(&mut m, 0, Direction::Left, 10, 0);
         assert_eq!(m, before, "Leftmost monitor should not move when pressing left");
     }
 
@@ -596,7 +779,7 @@ mod tests {
     fn test_move_rightmost_right_is_noop() {
         let mut m = three_side_by_side();
         let before = m.clone();
-        move_monitor(&mut m, 2, Direction::Right, 10);
+        move_monitor(&mut m, 2, Direction::Right, 10, 0);
         assert_eq!(m, before, "Rightmost monitor should not move when pressing right");
     }
 
@@ -605,7 +788,7 @@ mod tests {
         // Two stacked — pressing up on the top one should do nothing
         let mut m = two_stacked();
         let before = m.clone();
-        move_monitor(&mut m, 0, Direction::Up, 10);
+        move_monitor(&mut m, 0, Direction::Up, 10, 0);
         assert_eq!(m, before, "Topmost stacked monitor should not move when pressing up");
     }
 
@@ -614,26 +797,54 @@ mod tests {
         let mut m = vec![
             LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 },
         ];
-        move_monitor(&mut m, 0, Direction::Left, 10);
+        move_monitor(&mut m, 0, Direction::Left, 10, 0);
         assert_eq!(m[0].x, 0);
         assert_eq!(m[0].y, 0);
     }
 
     // --- normalize tests ---
 
+    #[test]
+    fn test_bounding_box() {
+        assert_eq!(bounding_box(&two_side_by_side_different_heights()), Some((0, 0, 4480, 1440)));
+        assert_eq!(bounding_box(&two_stacked()), Some((0, 0, 1920, 2160)));
+        assert_eq!(bounding_box(&[]), None);
+    }
+
     #[test]
     fn test_normalize() {
         let mut m = vec![
             LayoutMonitor { id: "A".into(), x: 100, y: 50, w: 1920, h: 1080 },
             LayoutMonitor { id: "B".into(), x: 2020, y: 50, w: 1920, h: 1080 },
         ];
-        normalize(&mut m);
+        assert_eq!(normalize(&mut m), (-100, -50));
         assert_eq!(m[0].x, 0);
         assert_eq!(m[0].y, 0);
         assert_eq!(m[1].x, 1920);
         assert_eq!(m[1].y, 0);
     }
 
+    #[test]
+    fn test_normalize_negative_keeps_relative_positions() {
+        // B was dragged up and to the left of A
+        let mut m = vec![
+            LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 },
+            LayoutMonitor { id: "B".into(), x: -2560, y: -300, w: 2560, h: 1440 },
+        ];
+        let (bx, by) = (m[1].x - m[0].x, m[1].y - m[0].y);
+        assert_eq!(normalize(&mut m), (2560, 300));
+        assert_eq!((m[1].x, m[1].y), (0, 0));
+        assert_eq!((m[0].x, m[0].y), (2560, 300));
+        assert_eq!((m[1].x - m[0].x, m[1].y - m[0].y), (bx, by));
+    }
+
+    #[test]
+    fn test_normalize_is_idempotent() {
+        let mut m = three_side_by_side();
+        assert_eq!(normalize(&mut m), (0, 0));
+        assert_eq!(m, three_side_by_side());
+    }
+
     // --- auto_snap_all tests ---
 
     #[test]
@@ -643,10 +854,10 @@ mod tests {
             LayoutMonitor { id: "B".into(), x: 1920, y: 0, w: 1920, h: 1080 },
             LayoutMonitor { id: "C".into(), x: 5000, y: 5000, w: 1920, h: 1080 },
         ];
-        auto_snap_all(&mut m);
+        auto_snap_all(&mut m, 0);
         // C should now be touching something
         let c_touches = (0..m.len())
-            .any(|j| j != 2 && shared_edge(&m[2], &m[j]).is_some());
+            .any(|j| j != 2 && shared_edge(&m[2], &m[j], 0).is_some());
         assert!(c_touches, "C should be snapped to touch another monitor: {:?}", m);
     }
 
@@ -654,7 +865,7 @@ mod tests {
     fn test_auto_snap_already_connected() {
         let mut m = three_side_by_side();
         let before = m.clone();
-        auto_snap_all(&mut m);
+        auto_snap_all(&mut m, 0);
         // Should not change anything
         assert_eq!(m, before);
     }
@@ -667,11 +878,11 @@ mod tests {
             LayoutMonitor { id: "B".into(), x: 1920, y: 0, w: 2560, h: 1440 },
             LayoutMonitor { id: "C".into(), x: 4480, y: 0, w: 1920, h: 1080 },
         ];
-        swap_monitors(&mut m, 0, 1);
-        auto_snap_all(&mut m);
+        swap_monitors(&mut m, 0, 1, 0);
+        auto_snap_all(&mut m, 0);
         // Every monitor should touch at least one other
         for i in 0..m.len() {
-            let touches = (0..m.len()).any(|j| j != i && shared_edge(&m[i], &m[j]).is_some());
+            let touches = (0..m.len()).any(|j| j != i && shared_edge(&m[i], &m[j], 0).is_some());
             assert!(touches, "Monitor {} should touch another: {:?}", m[i].id, m);
         }
     }
@@ -682,8 +893,8 @@ mod tests {
     fn test_snap_to_far_right() {
         let mut m = three_side_by_side();
         // Snap A (index 0, leftmost) to far right
-        snap_to_far_side(&mut m, 0, Direction::Right);
-        auto_snap_all(&mut m);
+        snap_to_far_side(&mut m, 0, Direction::Right, 0);
+        auto_snap_all(&mut m, 0);
         // A should now be at the rightmost position
         let max_right = m.iter().map(|m| m.right()).max().unwrap();
         assert_eq!(m[0].right(), max_right, "A should be at far right: {:?}", m);
@@ -693,8 +904,8 @@ mod tests {
     fn test_snap_to_far_left() {
         let mut m = three_side_by_side();
         // Snap C (index 2, rightmost) to far left
-        snap_to_far_side(&mut m, 2, Direction::Left);
-        auto_snap_all(&mut m);
+        snap_to_far_side(&mut m, 2, Direction::Left, 0);
+        auto_snap_all(&mut m, 0);
         normalize(&mut m);
         assert_eq!(m[2].x, 0, "C should be at far left: {:?}", m);
     }
@@ -702,10 +913,104 @@ mod tests {
     #[test]
     fn test_snap_to_far_down() {
         let mut m = three_side_by_side();
-        snap_to_far_side(&mut m, 1, Direction::Down);
-        auto_snap_all(&mut m);
+        snap_to_far_side(&mut m, 1, Direction::Down, 0);
+        auto_snap_all(&mut m, 0);
         // B should be below some other monitor
         assert!(m[1].y > 0, "B should be below: {:?}", m);
     }
 
+    #[test]
+    fn test_is_layout_connected() {
+        let mut m = three_side_by_side();
+        assert!(is_layout_connected(&m, 0));
+        assert!(is_layout_connected(&[], 0));
+
+        // C floats 10px away: only connected when that's within the gap
+        m[2].x += 10;
+        assert!(!is_layout_connected(&m, 0));
+        assert!(is_layout_connected(&m, 10));
+
+        // Touching only at a corner doesn't count
+        let corner = vec![
+            LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 },
+            LayoutMonitor { id: "B".into(), x: 1920, y: 1080, w: 1920, h: 1080 },
+        ];
+        assert!(!is_layout_connected(&corner, 0));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let mut m = three_side_by_side();
+        assert!(overlaps(&m).is_empty());
+        m[1].x = 1820;
+        assert_eq!(overlaps(&m), vec![(1820, 0, 100, 1080)]);
+    }
+
+    #[test]
+    fn test_gaps_between_facing_monitors() {
+        let mut m = three_side_by_side();
+        assert!(gaps(&m, 0).is_empty());
+
+        // C pulled 100px away from B: one line across the space, none from A over B
+        m[2].x += 100;
+        assert_eq!(gaps(&m, 0), vec![((3840, 540), (3940, 540))]);
+        assert!(gaps(&m, 100).is_empty());
+
+        // Stacked monitors get a vertical line
+        let mut m = two_stacked();
+        m[1].y += 50;
+        assert_eq!(gaps(&m, 0), vec![((960, 1080), (960, 1130))]);
+    }
+
+    // --- gap tests ---
+
+    #[test]
+    fn test_shared_edge_within_gap() {
+        let a = LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 };
+        let b = LayoutMonitor { id: "B".into(), x: 1930, y: 0, w: 1920, h: 1080 };
+        assert_eq!(shared_edge(&a, &b, 0), None);
+        assert_eq!(shared_edge(&a, &b, 10), Some(SharedEdge::Vertical(1920)));
+        assert_eq!(shared_edge(&b, &a, 10), Some(SharedEdge::Vertical(1930)));
+        let far = LayoutMonitor { x: 1931, ..b };
+        assert_eq!(shared_edge(&a, &far, 10), None);
+    }
+
+    #[test]
+    fn test_auto_snap_all_leaves_gap() {
+        let mut m = vec![
+            LayoutMonitor { id: "A".into(), x: 0, y: 0, w: 1920, h: 1080 },
+            LayoutMonitor { id: "B".into(), x: 5000, y: 200, w: 1920, h: 1080 },
+        ];
+        auto_snap_all(&mut m, 10);
+        // A is floating too, so it's the one snapped next to B
+        assert_eq!(m[1].x - m[0].right(), 10);
+        assert_eq!(m[0].y, m[1].y);
+    }
+
+    #[test]
+    fn test_snaps_and_pushes_leave_gap() {
+        let mut m = three_side_by_side();
+        snap_to_far_side(&mut m, 0, Direction::Right, 10);
+        assert_eq!(m[0].x, 5770);
+
+        // Sliding B off the end of A's bottom edge re-snaps it beside A, 10px away
+        let mut m = two_stacked();
+        m[1].y = 1090;
+        slide_monitor(&mut m, 1, 0, Direction::Right, 2000, 10);
+        assert_eq!((m[1].x, m[1].y), (1930, 0));
+
+        // An overlapping monitor is pushed out to 10px from the one it overlapped
+        let mut m = three_side_by_side();
+        m[1].x = 1000;
+        resolve_overlaps(&mut m, 1, 1920, 0, 10);
+        assert_eq!(m[1].x, 1930);
+    }
+
+    #[test]
+    fn test_swap_keeps_gap() {
+        let mut m = two_side_by_side_different_heights();
+        m[1].x = 1930;
+        swap_monitors(&mut m, 0, 1, 10);
+        assert_eq!((m[1].x, m[0].x), (0, 2570));
+    }
 }
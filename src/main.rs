@@ -1,11 +1,22 @@
 mod app;
 mod apply;
+mod args;
+mod backend;
 mod cli;
+mod clock;
+mod completions;
+mod config;
+mod diagram;
+mod events;
+mod hooks;
+mod keys;
 mod layout;
 mod monitor;
 mod preset;
+mod theme;
 mod ui;
 
+use args::Command;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
@@ -15,79 +26,64 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 
 fn main() -> io::Result<()> {
-    let args: Vec<String> = std::env::args().collect();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (mut config, config_error) = match config::Config::load() {
+        Ok(config) => (config, None),
+        Err(e) => (config::Config::from_env(), Some(format!("{} — using the defaults", e))),
+    };
+
+    let (command, options) = match args::parse(&args) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Run 'monitui --help' for usage information");
+            std::process::exit(1);
+        }
+    };
+
+    // Command-line flags come last: defaults < config.toml < MONITUI_* < flags
+    if options.quiet {
+        config.notify = false;
+    }
+
+    if let Some(e) = &config_error {
+        if !matches!(command, Command::Tui | Command::Monitor) {
+            eprintln!("Warning: invalid config.toml: {}", e);
+        }
+    }
 
     // Handle CLI commands
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "--help" | "-h" => {
-                cli::print_help();
-                return Ok(());
-            }
-            "--list" => {
-                cli::list_monitors();
-                return Ok(());
-            }
-            "--presets" => {
-                cli::list_presets_cmd();
-                return Ok(());
-            }
-            "--preset" => {
-                if args.len() < 3 {
-                    eprintln!("Error: --preset requires a preset name");
-                    eprintln!("Usage: monitui --preset <name>");
-                    std::process::exit(1);
-                }
-                cli::apply_preset(&args[2]);
-                return Ok(());
-            }
-            "--reload" => {
-                cli::reload_recent();
-                return Ok(());
-            }
-            "--enable" => {
-                if args.len() < 3 {
-                    eprintln!("Error: --enable requires a monitor name");
-                    eprintln!("Usage: monitui --enable <monitor>");
-                    std::process::exit(1);
-                }
-                cli::enable_monitor(&args[2]);
-                return Ok(());
-            }
-            "--disable" => {
-                if args.len() < 3 {
-                    eprintln!("Error: --disable requires a monitor name");
-                    eprintln!("Usage: monitui --disable <monitor>");
-                    std::process::exit(1);
-                }
-                cli::disable_monitor(&args[2]);
-                return Ok(());
-            }
-            "--set-workspace" => {
-                if args.len() < 4 {
-                    eprintln!("Error: --set-workspace requires workspace number and monitor name");
-                    eprintln!("Usage: monitui --set-workspace <num> <monitor>");
-                    std::process::exit(1);
-                }
-                let workspace: u32 = match args[2].parse() {
-                    Ok(n) => n,
-                    Err(_) => {
-                        eprintln!("Error: Invalid workspace number '{}'", args[2]);
-                        std::process::exit(1);
-                    }
-                };
-                cli::set_workspace(workspace, &args[3]);
-                return Ok(());
-            }
-            _ => {
-                eprintln!("Error: Unknown option '{}'", args[1]);
-                eprintln!("Run 'monitui --help' for usage information");
+    match command {
+        Command::Help => cli::print_help(),
+        Command::List => cli::list_monitors(&config, &options),
+        Command::Presets => cli::list_presets_cmd(&config),
+        Command::ExportWallpaperLayout => cli::export_wallpaper_layout(&config),
+        Command::Diagram { path, preset } => cli::write_diagram(&path, preset.as_deref(), &config),
+        Command::Preset(name) => {
+            if let Err(e) = cli::apply_preset(&name, &config, &options) {
+                eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
         }
+        Command::Auto => cli::apply_best_preset(&config, &options),
+        Command::Watch => cli::watch(&config, &options),
+        Command::SavePreset(name) => cli::save_preset(&name, &config),
+        Command::ExportPreset { name, path } => cli::export_preset(&name, &path),
+        Command::ImportPreset(path) => cli::import_preset(&path),
+        Command::Reload => cli::reload_recent(&config, &options),
+        Command::Undo => cli::undo_last(&config, &options),
+        Command::Recover => cli::recover(&config, &options),
+        Command::RestoreBackup => cli::restore_backup(),
+        Command::SetWorkspace { workspace, monitor } => cli::set_workspace(workspace, &monitor),
+        Command::Batch(ops) => cli::run_batch(&ops, &config, &options),
+        Command::Completions(shell) => cli::print_completions(&shell),
+        Command::Tui => return run_tui(config, config_error, false),
+        Command::Monitor => return run_tui(config, config_error, true),
     }
+    Ok(())
+}
 
-    // No CLI args, launch TUI
+fn run_tui(config: config::Config, config_error: Option<String>, read_only: bool) -> io::Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -95,7 +91,7 @@ fn main() -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = app::App::new();
+    let mut app = app::App::new(config, config_error, read_only);
     let result = app.run(&mut terminal);
 
     disable_raw_mode()?;
@@ -1,3 +1,5 @@
+use crate::config::ScaleStrategy;
+use crate::layout::LayoutMonitor;
 use serde::{Deserialize, Serialize};
 use std::process::Command;
 
@@ -8,12 +10,20 @@ pub struct AvailableMode {
     pub refresh: f32,
 }
 
+/// Formats as "1920x1080@60Hz"; a precision (`{:.2}`) sets the refresh rate's decimals.
 impl std::fmt::Display for AvailableMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}x{}@{:.0}Hz", self.width, self.height, self.refresh)
+        let decimals = f.precision().unwrap_or(0).min(u8::MAX as usize) as u8;
+        write!(f, "{}x{}@{}", self.width, self.height, format_refresh(self.refresh, decimals))
     }
 }
 
+/// Format a refresh rate for display, e.g. "60Hz" or "59.94Hz". Every place that shows
+/// a refresh rate goes through here so they agree. `decimals` is capped at 2.
+pub fn format_refresh(rate: f32, decimals: u8) -> String {
+    format!("{:.*}Hz", decimals.min(2) as usize, rate)
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MonitorInfo {
     pub name: String,
@@ -29,9 +39,276 @@ pub struct MonitorInfo {
     pub workspaces: Vec<u32>,
     pub available_modes: Vec<AvailableMode>,
     pub selected_mode: Option<usize>,
+    /// Whether the output is powered on (DPMS). `None` if hyprctl doesn't report it.
+    pub dpms_status: Option<bool>,
+    /// Pixel format currently driven to the output, e.g. "XRGB8888".
+    pub current_format: Option<String>,
+    /// Set when the size was edited by hand and matches none of `available_modes`.
+    pub custom_mode: bool,
+    /// Variable refresh rate: 0 = off, 1 = on, 2 = fullscreen only. hyprctl only reports
+    /// whether it's active, so 2 is restored from the last save.
+    pub vrr: u8,
+    /// Whether VRR was chosen for this monitor (by hand, a preset or monitors.conf)
+    /// rather than left to Hyprland's `misc:vrr`, so an explicit "off" is still written.
+    pub vrr_set: bool,
+    /// Output this monitor mirrors. A mirrored monitor shows the target's picture,
+    /// so it takes no space of its own in the layout.
+    pub mirror_of: Option<String>,
+    /// monitui-side "primary" output, kept at 0x0 for bars and launchers that assume
+    /// the monitor there is the main one. Hyprland itself has no such notion.
+    pub primary: bool,
+    /// Output bit depth, 8 or 10.
+    pub bitdepth: u8,
+    /// Hyprland color management preset (e.g. "hdr", "wide"); `None` leaves Hyprland's default.
+    /// hyprctl doesn't report it, so it is restored from the last save.
+    pub cm: Option<String>,
+    /// Panel size in millimetres, as (width, height) before rotation, if the monitor reports it.
+    pub physical_size: Option<(u32, u32)>,
+    /// Extra space kept free at the (top, bottom, left, right) edges, in logical pixels, e.g.
+    /// for a bar that doesn't reserve its own. hyprctl's reserved area also counts what
+    /// layer-shell bars take, so this is restored from the last save rather than read back.
+    pub reserved: Option<(u16, u16, u16, u16)>,
+}
+
+/// An enabled 1920x1080@60Hz monitor at 0x0 with nothing else set, for building
+/// monitors field by field.
+impl Default for MonitorInfo {
+    fn default() -> Self {
+        MonitorInfo {
+            name: String::new(),
+            description: String::new(),
+            width: 1920,
+            height: 1080,
+            refresh_rate: 60.0,
+            x: 0,
+            y: 0,
+            scale: 1.0,
+            disabled: false,
+            transform: 0,
+            workspaces: vec![],
+            available_modes: vec![],
+            selected_mode: None,
+            dpms_status: None,
+            current_format: None,
+            custom_mode: false,
+            vrr: 0,
+            vrr_set: false,
+            mirror_of: None,
+            primary: false,
+            bitdepth: 8,
+            cm: None,
+            physical_size: None,
+            reserved: None,
+        }
+    }
+}
+
+/// Color management presets the color submode cycles through, after "unset".
+pub const CM_PRESETS: &[&str] = &["srgb", "wide", "edid", "hdr", "hdredid"];
+
+/// Where a monitor sits and how many pixels it shows, for wallpaper tools.
+/// `width`/`height` are physical pixels after rotation (the wallpaper image size);
+/// `x`/`y` are in Hyprland's logical layout space.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct WallpaperGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub logical_width: i32,
+    pub logical_height: i32,
+    pub scale: f32,
+    pub transform: u8,
+}
+
+/// The names in `names` that don't appear among the `connected` outputs from a fresh fetch.
+pub fn disconnected(names: &[String], connected: &[MonitorInfo]) -> Vec<String> {
+    names.iter()
+        .filter(|name| !connected.iter().any(|m| &m.name == *name))
+        .cloned()
+        .collect()
+}
+
+/// Highest workspace number that can be typed in a workspace list.
+const MAX_WORKSPACE: u32 = 99;
+
+/// Parse a workspace list such as "1-5,10,12" into sorted, de-duplicated numbers (1-99).
+/// An empty list is allowed and means no workspaces.
+pub fn parse_workspaces(input: &str) -> Result<Vec<u32>, String> {
+    let number = |s: &str| -> Result<u32, String> {
+        let n: u32 = s.trim().parse().map_err(|_| format!("'{}' is not a workspace number", s.trim()))?;
+        if !(1..=MAX_WORKSPACE).contains(&n) {
+            return Err(format!("workspace {} is outside 1-{}", n, MAX_WORKSPACE));
+        }
+        Ok(n)
+    };
+
+    let mut workspaces = Vec::new();
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (number(start)?, number(end)?);
+                if start > end {
+                    return Err(format!("range {} runs backwards", part));
+                }
+                workspaces.extend(start..=end);
+            }
+            None => workspaces.push(number(part)?),
+        }
+    }
+    workspaces.sort();
+    workspaces.dedup();
+    Ok(workspaces)
+}
+
+/// Write sorted workspaces back as a list, collapsing runs into ranges ("1-5,10,12").
+pub fn format_workspaces(workspaces: &[u32]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < workspaces.len() {
+        let start = workspaces[i];
+        while i + 1 < workspaces.len() && workspaces[i + 1] == workspaces[i] + 1 {
+            i += 1;
+        }
+        parts.push(match workspaces[i] - start {
+            0 => start.to_string(),
+            1 => format!("{},{}", start, workspaces[i]),
+            _ => format!("{}-{}", start, workspaces[i]),
+        });
+        i += 1;
+    }
+    parts.join(",")
+}
+
+/// Parse a reserved area typed as "top bottom left right", separated by spaces or commas.
+/// Empty input, or all zeros, means no reserved area.
+pub fn parse_reserved(input: &str) -> Result<Option<(u16, u16, u16, u16)>, String> {
+    let values = input.split([',', ' ']).filter(|s| !s.is_empty())
+        .map(|s| s.parse::<u16>().map_err(|_| format!("'{}' is not a size in pixels", s)))
+        .collect::<Result<Vec<_>, _>>()?;
+    match values[..] {
+        [] | [0, 0, 0, 0] => Ok(None),
+        [top, bottom, left, right] => Ok(Some((top, bottom, left, right))),
+        _ => Err(format!("expected 4 sizes (top bottom left right), got {}", values.len())),
+    }
+}
+
+/// The reserved area as `parse_reserved` reads it back, e.g. "30 0 0 0"; empty for none.
+pub fn format_reserved(reserved: Option<(u16, u16, u16, u16)>) -> String {
+    match reserved {
+        Some((top, bottom, left, right)) => format!("{} {} {} {}", top, bottom, left, right),
+        None => String::new(),
+    }
+}
+
+/// Give `monitors[idx]` exactly `workspaces`, taking each of them off any other monitor.
+pub fn set_workspaces(monitors: &mut [MonitorInfo], idx: usize, workspaces: Vec<u32>) -> Result<(), String> {
+    if monitors[idx].disabled {
+        return Err(format!("{} is disabled — enable it before assigning workspaces", monitors[idx].name));
+    }
+    for (i, m) in monitors.iter_mut().enumerate() {
+        if i != idx {
+            m.workspaces.retain(|w| !workspaces.contains(w));
+        }
+    }
+    monitors[idx].workspaces = workspaces;
+    Ok(())
+}
+
+/// Move workspace `ws` to `monitors[idx]`, taking it off any other monitor.
+/// Returns whether anything changed. Disabled monitors can't host workspaces.
+pub fn assign_workspace(monitors: &mut [MonitorInfo], idx: usize, ws: u32) -> Result<bool, String> {
+    if monitors[idx].disabled {
+        return Err(format!("{} is disabled — enable it before assigning workspaces", monitors[idx].name));
+    }
+    for (i, m) in monitors.iter_mut().enumerate() {
+        if i != idx {
+            m.workspaces.retain(|&w| w != ws);
+        }
+    }
+    let m = &mut monitors[idx];
+    if m.workspaces.contains(&ws) {
+        return Ok(false);
+    }
+    m.workspaces.push(ws);
+    m.workspaces.sort();
+    Ok(true)
+}
+
+/// Refresh rates further apart than this (in Hz) count as mismatched.
+const REFRESH_MISMATCH_HZ: f32 = 5.0;
+
+/// The lowest and highest refresh rate among enabled monitors, if they differ enough to
+/// risk stutter and VRR is off on any of them. Purely advisory.
+pub fn refresh_mismatch(monitors: &[MonitorInfo]) -> Option<(f32, f32)> {
+    let enabled: Vec<_> = monitors.iter().filter(|m| !m.disabled).collect();
+    if enabled.iter().all(|m| m.vrr != 0) {
+        return None;
+    }
+    let min = enabled.iter().map(|m| m.refresh_rate).reduce(f32::min)?;
+    let max = enabled.iter().map(|m| m.refresh_rate).reduce(f32::max)?;
+    (max - min > REFRESH_MISMATCH_HZ).then_some((min, max))
+}
+
+/// Range accepted for a typed scale value.
+const MIN_SCALE: f32 = 0.1;
+const MAX_SCALE: f32 = 8.0;
+
+/// Parse a scale typed by the user, e.g. "1.333333" (a decimal comma is fine too).
+pub fn parse_scale(input: &str) -> Result<f32, String> {
+    let scale: f32 = input.trim().replace(',', ".").parse()
+        .map_err(|_| format!("'{}' is not a number", input.trim()))?;
+    if !(MIN_SCALE..=MAX_SCALE).contains(&scale) {
+        return Err(format!("scale must be between {} and {}", MIN_SCALE, MAX_SCALE));
+    }
+    Ok(scale)
+}
+
+/// Hyprland only accepts scales in 1/120 steps that divide the mode into whole logical
+/// pixels, and quietly picks the nearest such scale otherwise. This mirrors its search
+/// so we can show the scale that will actually be used. Falls back to `scale` if no
+/// valid scale is close.
+pub fn hypr_rounded_scale(width: u32, height: u32, scale: f32) -> f32 {
+    let valid = |s: f64| {
+        let (w, h) = (width as f64 / s, height as f64 / s);
+        (w - w.round()).abs() < 0.01 && (h - h.round()).abs() < 0.01
+    };
+    let steps = (scale as f64 * 120.0).round();
+    if steps > 0.0 && valid(steps / 120.0) {
+        return (steps / 120.0) as f32;
+    }
+    for i in 1..90 {
+        for candidate in [steps + i as f64, steps - i as f64] {
+            if candidate > 0.0 && valid(candidate / 120.0) {
+                return (candidate / 120.0) as f32;
+            }
+        }
+    }
+    scale
 }
 
+/// Smallest and largest sizes a monitor can be resized to by hand.
+const MIN_SIZE: (u32, u32) = (640, 480);
+const MAX_SIZE: (u32, u32) = (7680, 4320);
+
 impl MonitorInfo {
+    /// Whether `scale` is this monitor's scale, allowing for Hyprland having rounded
+    /// either one to a scale that fits the mode.
+    pub fn same_scale(&self, scale: f32) -> bool {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.001;
+        close(self.scale, scale)
+            || close(hypr_rounded_scale(self.width, self.height, self.scale), scale)
+            || close(hypr_rounded_scale(self.width, self.height, scale), self.scale)
+    }
+
+    /// The scale to show and apply under `strategy`.
+    pub fn effective_scale(&self, strategy: ScaleStrategy) -> f32 {
+        match strategy {
+            ScaleStrategy::Exact => self.scale,
+            ScaleStrategy::HyprRounded => hypr_rounded_scale(self.width, self.height, self.scale),
+        }
+    }
+
     pub fn logical_width(&self) -> i32 {
         let (w, _) = self.physical_dimensions();
         ((w as f32) / self.scale).ceil() as i32
@@ -45,17 +322,17 @@ impl MonitorInfo {
     /// Returns (width, height) accounting for rotation
     fn physical_dimensions(&self) -> (u32, u32) {
         match self.transform {
-            1 | 3 => (self.height, self.width),  // 90° or 270° - swap dimensions
-            _ => (self.width, self.height),      // 0° or 180° - keep dimensions
+            1 | 3 | 5 | 7 => (self.height, self.width),  // 90° or 270°, flipped or not - swap dimensions
+            _ => (self.width, self.height),              // 0° or 180° - keep dimensions
         }
     }
 
-    pub fn resolution_string(&self) -> String {
+    pub fn resolution_string(&self, refresh_decimals: u8) -> String {
         if self.disabled {
             "Disabled".to_string()
         } else {
             let (w, h) = self.physical_dimensions();
-            format!("{}x{}@{:.0}Hz", w, h, self.refresh_rate)
+            format!("{}x{}@{}", w, h, format_refresh(self.refresh_rate, refresh_decimals))
         }
     }
 
@@ -67,33 +344,199 @@ impl MonitorInfo {
             Some(i) => (i + 1) % self.available_modes.len(),
             None => 0,
         };
-        self.selected_mode = Some(next);
-        let mode = &self.available_modes[next];
+        self.select_mode(next);
+    }
+
+    /// Step to the next refresh rate offered at the current resolution, keeping the size.
+    /// Returns the new rate, or an error if there is nothing else to switch to.
+    pub fn cycle_refresh(&mut self) -> Result<f32, String> {
+        let mut same_size: Vec<usize> = (0..self.available_modes.len())
+            .filter(|&i| self.available_modes[i].width == self.width && self.available_modes[i].height == self.height)
+            .collect();
+        if same_size.len() < 2 {
+            return Err(format!("{}: no other refresh rate at {}x{}", self.name, self.width, self.height));
+        }
+        same_size.sort_by(|&a, &b| self.available_modes[a].refresh.total_cmp(&self.available_modes[b].refresh));
+
+        // Step up from the current rate, wrapping to the slowest
+        let next = same_size.iter()
+            .copied()
+            .find(|&i| self.available_modes[i].refresh > self.refresh_rate + 0.01)
+            .unwrap_or(same_size[0]);
+        self.select_mode(next);
+        Ok(self.refresh_rate)
+    }
+
+    /// Switch to `available_modes[idx]`.
+    pub fn select_mode(&mut self, idx: usize) {
+        let Some(mode) = self.available_modes.get(idx) else { return; };
         self.width = mode.width;
         self.height = mode.height;
         self.refresh_rate = mode.refresh;
+        self.selected_mode = Some(idx);
+        self.custom_mode = false;
+    }
+
+    /// Index of the mode the monitor is set to: the picked one, or else the available
+    /// mode matching its current size and refresh rate.
+    pub fn current_mode_index(&self) -> Option<usize> {
+        self.selected_mode.or_else(|| {
+            self.available_modes.iter().position(|m| {
+                m.width == self.width
+                    && m.height == self.height
+                    && (m.refresh - self.refresh_rate).abs() < 0.01
+            })
+        })
     }
 
+    /// The mode as written to Hyprland, e.g. "2560x1440@143.97". Fractional rates are
+    /// kept, as rounding 59.94 to 60 can select a different mode or none at all.
     pub fn mode_string(&self) -> String {
-        if self.selected_mode.is_some() {
-            format!("{}x{}@{:.0}", self.width, self.height, self.refresh_rate)
+        if self.selected_mode.is_some() || self.custom_mode {
+            let refresh = format!("{:.3}", self.refresh_rate);
+            let refresh = refresh.trim_end_matches('0').trim_end_matches('.');
+            format!("{}x{}@{}", self.width, self.height, refresh)
         } else {
             "preferred".to_string()
         }
     }
 
+    /// Grow or shrink the mode by `dw`/`dh` pixels, clamped to sane bounds. Snaps to an
+    /// available mode when the new size matches one; otherwise the size is marked custom
+    /// and is only applied successfully if the output actually supports it.
+    pub fn resize(&mut self, dw: i32, dh: i32) {
+        self.width = (self.width as i32 + dw).clamp(MIN_SIZE.0 as i32, MAX_SIZE.0 as i32) as u32;
+        self.height = (self.height as i32 + dh).clamp(MIN_SIZE.1 as i32, MAX_SIZE.1 as i32) as u32;
+
+        let refresh = self.refresh_rate;
+        let matching = self.available_modes.iter()
+            .enumerate()
+            .filter(|(_, m)| m.width == self.width && m.height == self.height)
+            .min_by(|(_, a), (_, b)| {
+                (a.refresh - refresh).abs().total_cmp(&(b.refresh - refresh).abs())
+            })
+            .map(|(i, m)| (i, m.refresh));
+
+        match matching {
+            Some((i, refresh)) => {
+                self.selected_mode = Some(i);
+                self.refresh_rate = refresh;
+                self.custom_mode = false;
+            }
+            None => self.custom_mode = true,
+        }
+    }
+
     pub fn cycle_rotation(&mut self) {
-        // Cycle through 0 (normal), 1 (90°), 2 (180°), 3 (270°)
-        self.transform = (self.transform + 1) % 4;
+        // Cycle through 0 (normal), 1 (90°), 2 (180°), 3 (270°), then 4-7 (the same, flipped)
+        self.transform = (self.transform + 1) % 8;
+    }
+
+    /// Mirror the output horizontally (transforms 4-7), keeping its rotation.
+    pub fn toggle_flip(&mut self) {
+        self.transform ^= 4;
+    }
+
+    /// Position and logical size in layout space.
+    pub fn layout_rect(&self) -> LayoutMonitor {
+        LayoutMonitor {
+            id: self.name.clone(),
+            x: self.x,
+            y: self.y,
+            w: self.logical_width(),
+            h: self.logical_height(),
+        }
+    }
+
+    pub fn wallpaper_geometry(&self) -> WallpaperGeometry {
+        let (width, height) = self.physical_dimensions();
+        WallpaperGeometry {
+            x: self.x,
+            y: self.y,
+            width,
+            height,
+            logical_width: self.logical_width(),
+            logical_height: self.logical_height(),
+            scale: self.scale,
+            transform: self.transform,
+        }
+    }
+
+    /// True when the monitor is enabled but reports its signal as off.
+    pub fn no_signal(&self) -> bool {
+        !self.disabled && self.dpms_status == Some(false)
+    }
+
+    /// Pixel density of the panel, from its horizontal resolution and physical width.
+    pub fn dpi(&self) -> Option<f32> {
+        let (width_mm, _) = self.physical_size?;
+        Some(self.width as f32 / (width_mm as f32 / 25.4))
+    }
+
+    /// Drop duplicate modes and, unless none would be left, modes below `min_refresh` Hz.
+    /// The selected mode is always kept, and `selected_mode` still points at it.
+    pub fn prune_modes(&mut self, min_refresh: f32) {
+        let selected = self.selected_mode.and_then(|i| self.available_modes.get(i).cloned());
+        let mut modes: Vec<AvailableMode> = Vec::new();
+        for mode in self.available_modes.drain(..) {
+            if !modes.contains(&mode) {
+                modes.push(mode);
+            }
+        }
+        if modes.iter().any(|m| m.refresh >= min_refresh) {
+            modes.retain(|m| m.refresh >= min_refresh || Some(m) == selected.as_ref());
+        }
+        self.selected_mode = selected.and_then(|s| modes.iter().position(|m| *m == s));
+        self.available_modes = modes;
+    }
+
+    /// Switch between 8- and 10-bit output.
+    pub fn toggle_bitdepth(&mut self) {
+        self.bitdepth = if self.bitdepth == 10 { 8 } else { 10 };
+    }
+
+    /// Step the color management preset through unset → `CM_PRESETS` → unset.
+    pub fn cycle_cm(&mut self) {
+        let next = match self.cm.as_deref().and_then(|cm| CM_PRESETS.iter().position(|p| *p == cm)) {
+            Some(i) => CM_PRESETS.get(i + 1),
+            None if self.cm.is_some() => None,
+            None => CM_PRESETS.first(),
+        };
+        self.cm = next.map(|cm| cm.to_string());
+    }
+
+    /// e.g. "10-bit, cm hdr", or "8-bit" when no color preset is set.
+    pub fn color_string(&self) -> String {
+        match &self.cm {
+            Some(cm) => format!("{}-bit, cm {}", self.bitdepth, cm),
+            None => format!("{}-bit", self.bitdepth),
+        }
+    }
+
+    /// Step VRR through off → on → fullscreen only.
+    pub fn cycle_vrr(&mut self) {
+        self.vrr = (self.vrr + 1) % 3;
+        self.vrr_set = true;
+    }
+
+    pub fn vrr_string(&self) -> &str {
+        match self.vrr {
+            1 => "VRR",
+            2 => "VRR fullscreen",
+            _ => "VRR off",
+        }
     }
 
     pub fn rotation_string(&self) -> &str {
         match self.transform {
-            0 => "0°",
             1 => "90°",
             2 => "180°",
             3 => "270°",
-            _ => "0°",
+            4 => "flipped",
+            5 => "flipped-90°",
+            6 => "flipped-180°",
+            7 => "flipped-270°",
+            _ => "normal",
         }
     }
 }
@@ -110,23 +553,42 @@ fn parse_mode(mode_str: &str) -> Option<AvailableMode> {
     }
     let width = res[0].parse().ok()?;
     let height = res[1].parse().ok()?;
-    let refresh = parts[1].trim_end_matches("Hz").parse().ok()?;
+    // Some locales print the refresh rate with a decimal comma ("59,95Hz")
+    let refresh = parts[1].trim_end_matches("Hz").replace(',', ".").parse().ok()?;
     Some(AvailableMode { width, height, refresh })
 }
 
-pub fn fetch_monitors_all() -> Vec<MonitorInfo> {
-    let output = match Command::new("hyprctl")
+/// Fetch every output from hyprctl. Parsing `available_modes` is skipped unless
+/// `with_modes`, as most callers (like the periodic poll) never look at them.
+pub fn fetch_monitors_all(with_modes: bool) -> Vec<MonitorInfo> {
+    parse_monitors(&hyprctl_monitors_json(), with_modes)
+}
+
+/// The raw JSON printed by `hyprctl -j monitors all`.
+pub fn hyprctl_monitors_json() -> Vec<u8> {
+    match Command::new("hyprctl")
         .args(["-j", "monitors", "all"])
         .output()
     {
-        Ok(o) if o.status.success() => o,
+        Ok(o) if o.status.success() => o.stdout,
         _ => {
             eprintln!("Failed to run hyprctl -j monitors all");
             std::process::exit(1);
         }
-    };
+    }
+}
+
+/// A reported panel size in mm. Projectors and virtual outputs report 0x0, meaning unknown.
+pub fn physical_size(width: Option<u64>, height: Option<u64>) -> Option<(u32, u32)> {
+    match (width?, height?) {
+        (0, _) | (_, 0) => None,
+        (w, h) => Some((w as u32, h as u32)),
+    }
+}
 
-    let raw: Vec<serde_json::Value> = match serde_json::from_slice(&output.stdout) {
+/// Parse the JSON printed by `hyprctl -j monitors all`.
+pub fn parse_monitors(json: &[u8], with_modes: bool) -> Vec<MonitorInfo> {
+    let raw: Vec<serde_json::Value> = match serde_json::from_slice(json) {
         Ok(v) => v,
         Err(e) => {
             eprintln!("Failed to parse hyprctl output: {}", e);
@@ -148,9 +610,26 @@ pub fn fetch_monitors_all() -> Vec<MonitorInfo> {
         let refresh_rate = m.get("refreshRate").and_then(|v| v.as_f64()).unwrap_or(60.0) as f32;
         let x = m.get("x").and_then(|v| v.as_i64()).unwrap_or(0) as i32;
         let y = m.get("y").and_then(|v| v.as_i64()).unwrap_or(0) as i32;
-        let scale = m.get("scale").and_then(|v| v.as_f64()).unwrap_or(1.0) as f32;
+        // Some broken EDIDs briefly report a scale of 0 while initializing; logical sizes divide by it
+        let scale = (m.get("scale").and_then(|v| v.as_f64()).unwrap_or(1.0) as f32).max(MIN_SCALE);
         let disabled = m.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false);
         let transform = m.get("transform").and_then(|v| v.as_u64()).unwrap_or(0) as u8;
+        let dpms_status = m.get("dpmsStatus").and_then(|v| v.as_bool());
+        let vrr = u8::from(m.get("vrr").and_then(|v| v.as_bool()).unwrap_or(false));
+        let mirror_of = m.get("mirrorOf")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty() && *s != "none")
+            .map(str::to_string);
+        let current_format = m.get("currentFormat")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty() && *s != "Invalid")
+            .map(str::to_string);
+        let physical_size = physical_size(
+            m.get("physicalWidth").and_then(|v| v.as_u64()),
+            m.get("physicalHeight").and_then(|v| v.as_u64()),
+        );
+        // 10-bit formats are the 2101010 ones, e.g. XRGB2101010
+        let bitdepth = if current_format.as_deref().is_some_and(|f| f.contains("2101010")) { 10 } else { 8 };
 
         let workspaces = m.get("activeWorkspace")
             .and_then(|v| v.as_object())
@@ -160,6 +639,7 @@ pub fn fetch_monitors_all() -> Vec<MonitorInfo> {
             .unwrap_or_default();
 
         let available_modes = m.get("availableModes")
+            .filter(|_| with_modes)
             .and_then(|v| v.as_array())
             .map(|arr| {
                 arr.iter()
@@ -182,13 +662,343 @@ pub fn fetch_monitors_all() -> Vec<MonitorInfo> {
             workspaces,
             available_modes,
             selected_mode: None,
+            dpms_status,
+            current_format,
+            custom_mode: false,
+            vrr,
+            vrr_set: false,
+            mirror_of,
+            primary: false,
+            bitdepth,
+            cm: None,
+            physical_size,
+            reserved: None,
         });
     }
 
-    // Sort: enabled first by x position, disabled at bottom
+    sort_monitors(&mut monitors);
+    monitors
+}
+
+/// Sort for display: enabled first by x position, disabled at bottom.
+pub fn sort_monitors(monitors: &mut [MonitorInfo]) {
     monitors.sort_by(|a, b| {
         a.disabled.cmp(&b.disabled).then_with(|| a.x.cmp(&b.x))
     });
+}
 
-    monitors
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_monitor(available_modes: Vec<AvailableMode>) -> MonitorInfo {
+        MonitorInfo {
+            name: "DP-1".to_string(),
+            available_modes,
+            dpms_status: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cycle_refresh_keeps_resolution() {
+        let mut m = make_monitor(vec![
+            AvailableMode { width: 2560, height: 1440, refresh: 143.97 },
+            AvailableMode { width: 1920, height: 1080, refresh: 60.0 },
+            AvailableMode { width: 2560, height: 1440, refresh: 59.95 },
+            AvailableMode { width: 2560, height: 1440, refresh: 120.0 },
+        ]);
+        m.select_mode(2);
+        assert_eq!(m.cycle_refresh(), Ok(120.0));
+        assert_eq!(m.cycle_refresh(), Ok(143.97));
+        assert_eq!(m.mode_string(), "2560x1440@143.97");
+        assert_eq!(m.cycle_refresh(), Ok(59.95));
+        assert_eq!((m.width, m.height, m.selected_mode), (2560, 1440, Some(2)));
+
+        m.select_mode(1);
+        assert!(m.cycle_refresh().is_err());
+        assert_eq!(m.selected_mode, Some(1));
+    }
+
+    #[test]
+    fn test_resize_to_unlisted_size_is_custom() {
+        let mut m = make_monitor(vec![]);
+        m.resize(8, 0);
+        assert_eq!((m.width, m.height), (1928, 1080));
+        assert!(m.custom_mode);
+        assert_eq!(m.mode_string(), "1928x1080@60");
+    }
+
+    #[test]
+    fn test_resize_snaps_to_available_mode() {
+        let mut m = make_monitor(vec![
+            AvailableMode { width: 1920, height: 1088, refresh: 30.0 },
+            AvailableMode { width: 1920, height: 1088, refresh: 59.94 },
+        ]);
+        m.resize(0, 8);
+        assert!(!m.custom_mode);
+        assert_eq!(m.selected_mode, Some(1));
+        assert_eq!(m.refresh_rate, 59.94);
+    }
+
+    #[test]
+    fn test_refresh_decimals() {
+        let mut m = make_monitor(vec![]);
+        m.refresh_rate = 59.94;
+        assert_eq!(m.resolution_string(0), "1920x1080@60Hz");
+        assert_eq!(m.resolution_string(2), "1920x1080@59.94Hz");
+        assert_eq!(format_refresh(143.856, 5), "143.86Hz");
+
+        let mode = AvailableMode { width: 2560, height: 1440, refresh: 164.96 };
+        assert_eq!(mode.to_string(), "2560x1440@165Hz");
+        assert_eq!(format!("{:.1}", mode), "2560x1440@165.0Hz");
+    }
+
+    #[test]
+    fn test_wallpaper_geometry_uses_rotated_pixels() {
+        let mut m = make_monitor(vec![]);
+        m.transform = 1;
+        m.scale = 2.0;
+        let g = m.wallpaper_geometry();
+        assert_eq!((g.width, g.height), (1080, 1920));
+        assert_eq!((g.logical_width, g.logical_height), (540, 960));
+    }
+
+    #[test]
+    fn test_flipped_transforms() {
+        let mut m = make_monitor(vec![]);
+        m.transform = 3;
+        m.toggle_flip();
+        assert_eq!((m.transform, m.rotation_string()), (7, "flipped-270°"));
+        assert_eq!((m.logical_width(), m.logical_height()), (1080, 1920));
+        m.cycle_rotation();
+        assert_eq!((m.transform, m.rotation_string()), (0, "normal"));
+        m.transform = 5;
+        assert_eq!((m.logical_width(), m.logical_height()), (1080, 1920));
+        m.toggle_flip();
+        assert_eq!(m.transform, 1);
+    }
+
+    #[test]
+    fn test_refresh_mismatch() {
+        let mut fast = make_monitor(vec![]);
+        fast.refresh_rate = 144.0;
+        let mut near = make_monitor(vec![]);
+        near.refresh_rate = 59.94;
+        assert_eq!(refresh_mismatch(&[make_monitor(vec![]), fast.clone()]), Some((60.0, 144.0)));
+        assert_eq!(refresh_mismatch(&[make_monitor(vec![]), near]), None);
+
+        // Disabled monitors and VRR on every monitor don't count
+        let mut off = fast.clone();
+        off.disabled = true;
+        assert_eq!(refresh_mismatch(&[make_monitor(vec![]), off]), None);
+        let mut vrr = [make_monitor(vec![]), fast];
+        vrr.iter_mut().for_each(|m| m.vrr = 1);
+        assert_eq!(refresh_mismatch(&vrr), None);
+    }
+
+    #[test]
+    fn test_select_mode() {
+        let mut m = make_monitor(vec![
+            AvailableMode { width: 1920, height: 1080, refresh: 60.0 },
+            AvailableMode { width: 2560, height: 1440, refresh: 144.0 },
+        ]);
+        assert_eq!(m.current_mode_index(), Some(0));
+        m.select_mode(1);
+        assert_eq!((m.width, m.height, m.refresh_rate), (2560, 1440, 144.0));
+        assert_eq!(m.current_mode_index(), Some(1));
+        m.select_mode(5);
+        assert_eq!(m.selected_mode, Some(1));
+    }
+
+    #[test]
+    fn test_assign_workspace_moves_it() {
+        let mut monitors = [make_monitor(vec![]), make_monitor(vec![])];
+        monitors[0].workspaces = vec![3];
+        assert_eq!(assign_workspace(&mut monitors, 1, 3), Ok(true));
+        assert!(monitors[0].workspaces.is_empty());
+        assert_eq!(monitors[1].workspaces, vec![3]);
+        assert_eq!(assign_workspace(&mut monitors, 1, 3), Ok(false));
+    }
+
+    #[test]
+    fn test_assign_workspace_rejects_disabled_monitor() {
+        let mut monitors = [make_monitor(vec![]), make_monitor(vec![])];
+        monitors[0].workspaces = vec![3];
+        monitors[1].disabled = true;
+        assert!(assign_workspace(&mut monitors, 1, 3).is_err());
+        assert_eq!(monitors[0].workspaces, vec![3]);
+        assert!(monitors[1].workspaces.is_empty());
+    }
+
+    #[test]
+    fn test_parse_workspaces() {
+        assert_eq!(parse_workspaces("1-5,10,12"), Ok(vec![1, 2, 3, 4, 5, 10, 12]));
+        assert_eq!(parse_workspaces(" 12, 3-4 ,3,"), Ok(vec![3, 4, 12]));
+        assert_eq!(parse_workspaces(""), Ok(vec![]));
+        assert!(parse_workspaces("0").is_err());
+        assert!(parse_workspaces("5-100").is_err());
+        assert!(parse_workspaces("5-3").is_err());
+        assert!(parse_workspaces("one").is_err());
+    }
+
+    #[test]
+    fn test_format_workspaces() {
+        assert_eq!(format_workspaces(&[1, 2, 3, 4, 5, 10, 12, 13]), "1-5,10,12,13");
+        assert_eq!(format_workspaces(&[]), "");
+        assert_eq!(parse_workspaces(&format_workspaces(&[2, 3, 4, 7])), Ok(vec![2, 3, 4, 7]));
+    }
+
+    #[test]
+    fn test_parse_reserved() {
+        assert_eq!(parse_reserved("30 0 0 0"), Ok(Some((30, 0, 0, 0))));
+        assert_eq!(parse_reserved(" 0, 24,10 ,0"), Ok(Some((0, 24, 10, 0))));
+        assert_eq!(parse_reserved(""), Ok(None));
+        assert_eq!(parse_reserved("0 0 0 0"), Ok(None));
+        assert!(parse_reserved("30").is_err());
+        assert!(parse_reserved("30 0 0 -5").is_err());
+        assert_eq!(parse_reserved(&format_reserved(Some((1, 2, 3, 4)))), Ok(Some((1, 2, 3, 4))));
+        assert_eq!(format_reserved(None), "");
+    }
+
+    #[test]
+    fn test_set_workspaces_moves_them() {
+        let mut monitors = [make_monitor(vec![]), make_monitor(vec![])];
+        monitors[0].workspaces = vec![1, 2, 11];
+        assert!(set_workspaces(&mut monitors, 1, vec![2, 11, 12]).is_ok());
+        assert_eq!(monitors[0].workspaces, vec![1]);
+        assert_eq!(monitors[1].workspaces, vec![2, 11, 12]);
+
+        monitors[1].disabled = true;
+        assert!(set_workspaces(&mut monitors, 1, vec![1]).is_err());
+        assert_eq!(monitors[0].workspaces, vec![1]);
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(
+            parse_mode("1920x1080@60.00Hz"),
+            Some(AvailableMode { width: 1920, height: 1080, refresh: 60.0 })
+        );
+        assert_eq!(
+            parse_mode("1920x1080@59,95Hz"),
+            Some(AvailableMode { width: 1920, height: 1080, refresh: 59.95 })
+        );
+        assert_eq!(parse_mode("1920x1080"), None);
+    }
+
+    #[test]
+    fn test_hypr_rounded_scale() {
+        // Already valid scales are kept
+        assert_eq!(hypr_rounded_scale(3840, 2160, 1.333333), 160.0 / 120.0);
+        assert_eq!(hypr_rounded_scale(2560, 1440, 2.0), 2.0);
+        // 2560 / 1.2 and 2560 / 1.5 aren't whole pixels
+        assert_eq!(hypr_rounded_scale(2560, 1440, 1.2), 1.25);
+        assert_eq!(hypr_rounded_scale(2560, 1440, 1.5), 1.6);
+    }
+
+    #[test]
+    fn test_parse_monitors_clamps_zero_scale() {
+        let json = br#"[{"name": "DP-1", "width": 2560, "height": 1440, "scale": 0.0}]"#;
+        let monitors = parse_monitors(json, true);
+        assert_eq!(monitors.len(), 1);
+        assert_eq!(monitors[0].scale, MIN_SCALE);
+        assert_eq!(monitors[0].logical_width(), 25600);
+    }
+
+    #[test]
+    fn test_parse_monitors_physical_size_and_dpi() {
+        let json = br#"[
+            {"name": "DP-1", "width": 3840, "height": 2160, "physicalWidth": 600, "physicalHeight": 340},
+            {"name": "HEADLESS-1", "width": 1920, "height": 1080, "physicalWidth": 0, "physicalHeight": 0}
+        ]"#;
+        let monitors = parse_monitors(json, false);
+        assert_eq!(monitors[0].physical_size, Some((600, 340)));
+        assert_eq!(monitors[0].dpi().map(f32::round), Some(163.0));
+        assert_eq!(monitors[1].physical_size, None);
+        assert_eq!(monitors[1].dpi(), None);
+    }
+
+    #[test]
+    fn test_parse_monitors_modes_only_when_asked() {
+        let json = br#"[{"name": "DP-1", "availableModes": ["1920x1080@60.00Hz", "1280x720@60.00Hz"]}]"#;
+        assert_eq!(parse_monitors(json, true)[0].available_modes.len(), 2);
+        assert!(parse_monitors(json, false)[0].available_modes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_scale() {
+        assert_eq!(parse_scale("1.333333"), Ok(1.333333));
+        assert_eq!(parse_scale(" 1,5 "), Ok(1.5));
+        assert_eq!(parse_scale("8"), Ok(8.0));
+        assert!(parse_scale("0.05").is_err());
+        assert!(parse_scale("9").is_err());
+        assert!(parse_scale("abc").is_err());
+        assert!(parse_scale("").is_err());
+        assert!(parse_scale("NaN").is_err());
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let mut connected = make_monitor(vec![]);
+        connected.disabled = true;
+        let names = vec!["DP-1".to_string(), "DP-2".to_string()];
+        assert_eq!(disconnected(&names, &[connected]), vec!["DP-2".to_string()]);
+    }
+
+    #[test]
+    fn test_resize_is_clamped() {
+        let mut m = make_monitor(vec![]);
+        m.resize(-10_000, 10_000);
+        assert_eq!((m.width, m.height), (MIN_SIZE.0, MAX_SIZE.1));
+    }
+
+    #[test]
+    fn test_cycle_cm_wraps_through_unset() {
+        let mut m = make_monitor(vec![]);
+        let seen: Vec<Option<String>> = (0..=CM_PRESETS.len()).map(|_| { m.cycle_cm(); m.cm.clone() }).collect();
+        assert_eq!(seen[0].as_deref(), Some("srgb"));
+        assert_eq!(seen[CM_PRESETS.len()], None);
+        // A preset set by hand elsewhere goes back to unset
+        m.cm = Some("dcip3".to_string());
+        m.cycle_cm();
+        assert_eq!(m.cm, None);
+    }
+
+    #[test]
+    fn test_prune_modes() {
+        let mode = |w, h, refresh| AvailableMode { width: w, height: h, refresh };
+        let mut m = make_monitor(vec![
+            mode(1920, 1080, 144.0),
+            mode(1920, 1080, 60.0),
+            mode(1920, 1080, 144.0),
+            mode(1280, 720, 30.0),
+            mode(1280, 720, 60.0),
+        ]);
+        m.selected_mode = Some(3);
+        m.prune_modes(60.0);
+        // The duplicate and the 30Hz mode go, except that 30Hz is the selected one
+        assert_eq!(m.available_modes, vec![
+            mode(1920, 1080, 144.0),
+            mode(1920, 1080, 60.0),
+            mode(1280, 720, 30.0),
+            mode(1280, 720, 60.0),
+        ]);
+        assert_eq!(m.selected_mode, Some(2));
+
+        m.selected_mode = None;
+        m.prune_modes(60.0);
+        assert_eq!(m.available_modes.len(), 3);
+
+        // A minimum nothing reaches filters nothing
+        m.prune_modes(240.0);
+        assert_eq!(m.available_modes.len(), 3);
+    }
+
+    #[test]
+    fn test_cycle_vrr_wraps() {
+        let mut m = make_monitor(vec![]);
+        let states: Vec<u8> = (0..4).map(|_| { m.cycle_vrr(); m.vrr }).collect();
+        assert_eq!(states, vec![1, 2, 0, 1]);
+    }
 }
@@ -1,17 +1,70 @@
-use crate::monitor::MonitorInfo;
+use crate::clock;
+use crate::monitor::{AvailableMode, MonitorInfo};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Preset {
     pub name: String,
     pub monitors: Vec<MonitorConfig>,
+    /// Known-good preset: loading it in the TUI skips the confirm countdown.
+    #[serde(default)]
+    pub trusted: bool,
+    /// Descriptions (EDID make/model/serial) of the monitors enabled when the preset was
+    /// saved. `--auto` matches on these when present, since connector names can shuffle.
+    #[serde(default)]
+    pub match_descriptions: Vec<String>,
+    /// When the preset was last saved, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    /// Physical monitors the preset enables; 0 in presets saved before this was recorded.
+    #[serde(default)]
+    pub monitor_count: usize,
+}
+
+impl Preset {
+    /// One-line summary for the preset menu, e.g. "3 monitors · saved 2024-01-05".
+    pub fn summary(&self) -> String {
+        let count = if self.monitor_count > 0 {
+            self.monitor_count
+        } else {
+            self.monitors.iter().filter(|m| !m.disabled && !m.name.starts_with("HEADLESS-")).count()
+        };
+        let mut summary = format!("{} monitor{}", count, if count == 1 { "" } else { "s" });
+        if let Some(secs) = self.created_at {
+            summary.push_str(&format!(" · saved {}", clock::date(secs)));
+        }
+        summary
+    }
+}
+
+/// How a preset lines up with the connected monitors, for `--auto`.
+#[derive(Debug, PartialEq)]
+pub struct PresetMatch {
+    /// The preset's monitors that are connected, as "name" or "description".
+    pub matched: Vec<String>,
+    /// The preset's monitors that aren't connected.
+    pub missing: Vec<String>,
+    /// Connected monitors the preset has a setting for, enabled or not.
+    pub covered: usize,
+}
+
+impl PresetMatch {
+    /// Sort key: presets with nothing missing first, then the most monitors matched,
+    /// then the most connected monitors accounted for.
+    fn rank(&self) -> (bool, usize, usize) {
+        (self.missing.is_empty(), self.matched.len(), self.covered)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MonitorConfig {
     pub name: String,
+    /// Make, model and serial, used to find the monitor again on a different connector.
+    #[serde(default)]
+    pub description: String,
     pub width: u32,
     pub height: u32,
     pub refresh_rate: f32,
@@ -23,12 +76,41 @@ pub struct MonitorConfig {
     pub transform: u8,
     #[serde(default)]  // Defaults to empty vec if missing
     pub workspaces: Vec<u32>,
+    /// Index of the explicitly chosen mode when saved; `None` means "preferred".
+    #[serde(default)]
+    pub selected_mode: Option<usize>,
+    /// The explicitly chosen mode itself. Mode lists can change order between
+    /// sessions, so this (not the index) is what gets matched on load.
+    #[serde(default)]
+    pub mode: Option<AvailableMode>,
+    #[serde(default)]
+    pub mirror_of: Option<String>,
+    #[serde(default)]
+    pub primary: bool,
+    /// 0 = off, 1 = on, 2 = fullscreen only.
+    #[serde(default)]
+    pub vrr: u8,
+    /// Whether `vrr` was chosen rather than left to Hyprland's default.
+    #[serde(default)]
+    pub vrr_set: bool,
+    #[serde(default = "default_bitdepth")]
+    pub bitdepth: u8,
+    #[serde(default)]
+    pub cm: Option<String>,
+    /// Extra (top, bottom, left, right) space kept free, in logical pixels.
+    #[serde(default)]
+    pub reserved: Option<(u16, u16, u16, u16)>,
+}
+
+fn default_bitdepth() -> u8 {
+    8
 }
 
 impl From<&MonitorInfo> for MonitorConfig {
     fn from(m: &MonitorInfo) -> Self {
         MonitorConfig {
             name: m.name.clone(),
+            description: m.description.clone(),
             width: m.width,
             height: m.height,
             refresh_rate: m.refresh_rate,
@@ -38,6 +120,17 @@ impl From<&MonitorInfo> for MonitorConfig {
             disabled: m.disabled,
             transform: m.transform,
             workspaces: m.workspaces.clone(),
+            selected_mode: m.selected_mode.filter(|_| !m.custom_mode),
+            mode: m.selected_mode
+                .filter(|_| !m.custom_mode)
+                .and_then(|i| m.available_modes.get(i).cloned()),
+            mirror_of: m.mirror_of.clone(),
+            primary: m.primary,
+            vrr: m.vrr,
+            vrr_set: m.vrr_set,
+            bitdepth: m.bitdepth,
+            cm: m.cm.clone(),
+            reserved: m.reserved,
         }
     }
 }
@@ -59,13 +152,128 @@ fn recent_path() -> PathBuf {
     dir.join("recent.json")
 }
 
+fn last_cli_state_path() -> PathBuf {
+    let dir = dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("~/.config"))
+        .join("monitui");
+    fs::create_dir_all(&dir).ok();
+    dir.join("last_cli_state.json")
+}
+
+fn last_known_good_path() -> PathBuf {
+    let dir = dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("~/.config"))
+        .join("monitui");
+    fs::create_dir_all(&dir).ok();
+    dir.join("last_known_good.json")
+}
+
+/// Identify the set of connected physical monitors, independent of their current layout.
+/// HEADLESS outputs are virtual and come and go, so they are left out.
+pub fn hardware_fingerprint(monitors: &[MonitorInfo]) -> String {
+    let mut ids: Vec<String> = monitors.iter()
+        .filter(|m| !m.name.starts_with("HEADLESS-"))
+        .map(|m| format!("{} ({})", m.name, m.description))
+        .collect();
+    ids.sort();
+    ids.join(" + ")
+}
+
+fn load_last_known_good_all() -> BTreeMap<String, Vec<MonitorConfig>> {
+    fs::read_to_string(last_known_good_path())
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Remember a confirmed-working layout for the hardware it was applied on.
+pub fn save_last_known_good(monitors: &[MonitorInfo]) {
+    let mut all = load_last_known_good_all();
+    all.insert(
+        hardware_fingerprint(monitors),
+        monitors.iter().map(MonitorConfig::from).collect(),
+    );
+    if let Ok(json) = serde_json::to_string_pretty(&all) {
+        fs::write(last_known_good_path(), json).ok();
+    }
+}
+
+/// The last confirmed-working layout for exactly this set of connected monitors.
+pub fn load_last_known_good(monitors: &[MonitorInfo]) -> Option<Vec<MonitorConfig>> {
+    load_last_known_good_all().remove(&hardware_fingerprint(monitors))
+}
+
+/// Save the monitors as a preset. Overwriting a preset keeps its trusted flag.
 pub fn save_preset(name: &str, monitors: &[MonitorInfo]) -> Result<(), String> {
-    let preset = Preset {
+    let trusted = load_preset(name).map(|p| p.trusted).unwrap_or(false);
+    write_preset(&build_preset(name, monitors, trusted))
+}
+
+fn build_preset(name: &str, monitors: &[MonitorInfo], trusted: bool) -> Preset {
+    let physical: Vec<&MonitorInfo> = monitors.iter()
+        .filter(|m| !m.disabled && !m.name.starts_with("HEADLESS-"))
+        .collect();
+    Preset {
         name: name.to_string(),
         monitors: monitors.iter().map(MonitorConfig::from).collect(),
+        trusted,
+        match_descriptions: physical.iter()
+            .filter(|m| !m.description.is_empty())
+            .map(|m| m.description.clone())
+            .collect(),
+        created_at: Some(clock::now()),
+        monitor_count: physical.len(),
+    }
+}
+
+/// Compare a preset against the connected monitors. Physical monitors it enables are
+/// looked up by description if the preset recorded them, else by connector name.
+pub fn match_preset(preset: &Preset, connected: &[MonitorInfo]) -> PresetMatch {
+    let wanted: Vec<&str> = if preset.match_descriptions.is_empty() {
+        preset.monitors.iter()
+            .filter(|c| !c.disabled && !c.name.starts_with("HEADLESS-"))
+            .map(|c| c.name.as_str())
+            .collect()
+    } else {
+        preset.match_descriptions.iter().map(String::as_str).collect()
     };
-    let path = presets_dir().join(format!("{}.json", sanitize_filename(name)));
-    let json = serde_json::to_string_pretty(&preset).map_err(|e| e.to_string())?;
+    let (matched, missing): (Vec<&str>, Vec<&str>) = wanted.into_iter().partition(|w| {
+        connected.iter().any(|m| m.name == *w || m.description == *w)
+    });
+    PresetMatch {
+        matched: matched.into_iter().map(String::from).collect(),
+        missing: missing.into_iter().map(String::from).collect(),
+        covered: connected.iter()
+            .filter(|m| preset.monitors.iter().any(|c| c.name == m.name))
+            .count(),
+    }
+}
+
+/// The preset that best fits the connected monitors, if any preset matches one of them.
+/// Ties go to the first preset.
+pub fn best_match<'a>(presets: &'a [Preset], connected: &[MonitorInfo]) -> Option<(&'a Preset, PresetMatch)> {
+    let mut best: Option<(&Preset, PresetMatch)> = None;
+    for preset in presets {
+        let candidate = match_preset(preset, connected);
+        match &best {
+            _ if candidate.matched.is_empty() => {}
+            Some((_, b)) if candidate.rank() <= b.rank() => {}
+            _ => best = Some((preset, candidate)),
+        }
+    }
+    best
+}
+
+/// Mark a saved preset as trusted (no confirm countdown) or untrusted.
+pub fn set_trusted(name: &str, trusted: bool) -> Result<(), String> {
+    let mut preset = load_preset(name)?;
+    preset.trusted = trusted;
+    write_preset(&preset)
+}
+
+fn write_preset(preset: &Preset) -> Result<(), String> {
+    let path = presets_dir().join(format!("{}.json", sanitize_filename(&preset.name)));
+    let json = serde_json::to_string_pretty(preset).map_err(|e| e.to_string())?;
     fs::write(&path, json).map_err(|e| e.to_string())
 }
 
@@ -75,6 +283,86 @@ pub fn load_preset(name: &str) -> Result<Preset, String> {
     serde_json::from_str(&json).map_err(|e| e.to_string())
 }
 
+/// Rename a saved preset, keeping its monitors and trust. Fails if `new` is taken.
+/// Returns the name the preset is now listed under (its file name).
+pub fn rename_preset(old: &str, new: &str) -> Result<String, String> {
+    rename_preset_in(&presets_dir(), old, new)
+}
+
+fn rename_preset_in(dir: &Path, old: &str, new: &str) -> Result<String, String> {
+    if new.is_empty() {
+        return Err("Preset name can't be empty".to_string());
+    }
+    let old_path = dir.join(format!("{}.json", sanitize_filename(old)));
+    let new_path = dir.join(format!("{}.json", sanitize_filename(new)));
+    // Names that sanitize to the same file (e.g. "a b" -> "a_b") only change the embedded name
+    if new_path != old_path && new_path.exists() {
+        return Err(format!("A preset named {} already exists", new));
+    }
+
+    let json = fs::read_to_string(&old_path).map_err(|e| e.to_string())?;
+    let mut preset: Preset = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    preset.name = new.to_string();
+    let json = serde_json::to_string_pretty(&preset).map_err(|e| e.to_string())?;
+    fs::write(&new_path, json).map_err(|e| e.to_string())?;
+    if new_path != old_path {
+        fs::remove_file(&old_path).map_err(|e| e.to_string())?;
+    }
+    Ok(sanitize_filename(new))
+}
+
+/// Save a copy of a preset as "<name> copy" (or "<name> copy 2", ...), untrusted until
+/// marked otherwise. Returns the name the copy is listed under.
+pub fn duplicate_preset(name: &str) -> Result<String, String> {
+    duplicate_preset_in(&presets_dir(), name)
+}
+
+fn duplicate_preset_in(dir: &Path, name: &str) -> Result<String, String> {
+    let json = fs::read_to_string(dir.join(format!("{}.json", sanitize_filename(name))))
+        .map_err(|e| e.to_string())?;
+    let mut preset: Preset = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    let copy_name = unused_name(dir, &format!("{} copy", name));
+    preset.name = copy_name.clone();
+    preset.trusted = false;
+    let json = serde_json::to_string_pretty(&preset).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.json", sanitize_filename(&copy_name))), json).map_err(|e| e.to_string())?;
+    Ok(sanitize_filename(&copy_name))
+}
+
+/// Write a saved preset to `path` as pretty JSON.
+pub fn export_preset(name: &str, path: &Path) -> Result<(), String> {
+    let preset = load_preset(name)?;
+    let json = serde_json::to_string_pretty(&preset).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Save the preset in the JSON file at `path` under its own name, or "<name> 2", ...
+/// if that is taken. Imported presets start untrusted. Returns the name used.
+pub fn import_preset(path: &Path) -> Result<String, String> {
+    import_preset_in(&presets_dir(), path)
+}
+
+fn import_preset_in(dir: &Path, path: &Path) -> Result<String, String> {
+    let json = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut preset: Preset = serde_json::from_str(&json).map_err(|e| format!("not a monitui preset ({})", e))?;
+    if preset.name.is_empty() {
+        return Err("the preset has no name".to_string());
+    }
+    preset.name = unused_name(dir, &preset.name);
+    preset.trusted = false;
+    let json = serde_json::to_string_pretty(&preset).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.json", sanitize_filename(&preset.name))), json).map_err(|e| e.to_string())?;
+    Ok(preset.name)
+}
+
+/// The first of `base`, "`base` 2", "`base` 3", ... with no preset file in `dir`.
+fn unused_name(dir: &Path, base: &str) -> String {
+    (1..)
+        .map(|n| if n == 1 { base.to_string() } else { format!("{} {}", base, n) })
+        .find(|candidate| !dir.join(format!("{}.json", sanitize_filename(candidate))).exists())
+        .expect("an unused preset name")
+}
+
 pub fn delete_preset(name: &str) -> Result<(), String> {
     let path = presets_dir().join(format!("{}.json", sanitize_filename(name)));
     fs::remove_file(&path).map_err(|e| e.to_string())
@@ -106,22 +394,86 @@ pub fn load_recent() -> Option<Vec<MonitorConfig>> {
     serde_json::from_str(&json).ok()
 }
 
-/// Apply a preset's monitor configs to the current monitor list.
-/// Matches by monitor name; unmatched monitors keep their current state.
-pub fn apply_preset_to_monitors(monitors: &mut Vec<MonitorInfo>, configs: &[MonitorConfig]) {
+/// Snapshot the state from before a CLI apply so `--undo` can restore it.
+pub fn save_last_cli_state(monitors: &[MonitorInfo]) {
+    let configs: Vec<MonitorConfig> = monitors.iter().map(MonitorConfig::from).collect();
+    if let Ok(json) = serde_json::to_string_pretty(&configs) {
+        fs::write(last_cli_state_path(), json).ok();
+    }
+}
+
+pub fn load_last_cli_state() -> Option<Vec<MonitorConfig>> {
+    let json = fs::read_to_string(last_cli_state_path()).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Pair each config with the live monitor it describes, as (monitor index, config).
+/// Descriptions go first, so a monitor is found again on a different connector; among
+/// identical monitors the one on the saved connector wins. Configs without a description
+/// (or whose monitor isn't connected) fall back to the connector name.
+fn pair_configs<'a>(monitors: &[MonitorInfo], configs: &'a [MonitorConfig]) -> Vec<(usize, &'a MonitorConfig)> {
+    let mut pairs: Vec<(usize, &MonitorConfig)> = Vec::new();
+    let claimed = |pairs: &[(usize, &MonitorConfig)], i: usize| pairs.iter().any(|(j, _)| *j == i);
+    let mut unpaired = Vec::new();
     for config in configs {
-        if let Some(m) = monitors.iter_mut().find(|m| m.name == config.name) {
-            m.width = config.width;
-            m.height = config.height;
-            m.refresh_rate = config.refresh_rate;
-            m.x = config.x;
-            m.y = config.y;
-            m.scale = config.scale;
-            m.disabled = config.disabled;
-            m.transform = config.transform;
-            m.workspaces = config.workspaces.clone();
+        let same_description: Vec<usize> = (0..monitors.len())
+            .filter(|&i| !config.description.is_empty() && monitors[i].description == config.description)
+            .filter(|&i| !claimed(&pairs, i))
+            .collect();
+        let found = same_description.iter().copied()
+            .find(|&i| monitors[i].name == config.name)
+            .or(same_description.first().copied());
+        match found {
+            Some(i) => pairs.push((i, config)),
+            None => unpaired.push(config),
         }
     }
+    for config in unpaired {
+        if let Some(i) = (0..monitors.len()).find(|&i| monitors[i].name == config.name && !claimed(&pairs, i)) {
+            pairs.push((i, config));
+        }
+    }
+    pairs
+}
+
+/// Apply a preset's monitor configs to the current monitor list, pairing them up by
+/// description, then connector name. Unmatched monitors keep their current state.
+pub fn apply_preset_to_monitors(monitors: &mut [MonitorInfo], configs: &[MonitorConfig]) {
+    let pairs = pair_configs(monitors, configs);
+    // Saved connector name -> where that monitor is now, for mirror targets
+    let renamed: Vec<(String, String)> = pairs.iter()
+        .map(|(i, config)| (config.name.clone(), monitors[*i].name.clone()))
+        .collect();
+    for (i, config) in pairs {
+        let m = &mut monitors[i];
+        m.width = config.width;
+        m.height = config.height;
+        m.refresh_rate = config.refresh_rate;
+        m.x = config.x;
+        m.y = config.y;
+        m.scale = config.scale;
+        m.disabled = config.disabled;
+        m.transform = config.transform;
+        m.workspaces = config.workspaces.clone();
+        // Restore an explicit mode if the monitor still offers it, else use preferred
+        m.selected_mode = config.mode.as_ref().and_then(|mode| {
+            m.available_modes.iter().position(|a| {
+                a.width == mode.width
+                    && a.height == mode.height
+                    && (a.refresh - mode.refresh).abs() < 0.01
+            })
+        });
+        m.custom_mode = false;
+        m.mirror_of = config.mirror_of.as_ref().map(|target| {
+            renamed.iter().find(|(saved, _)| saved == target).map_or(target, |(_, now)| now).clone()
+        });
+        m.primary = config.primary;
+        m.vrr = config.vrr;
+        m.vrr_set = config.vrr_set;
+        m.bitdepth = config.bitdepth;
+        m.cm = config.cm.clone();
+        m.reserved = config.reserved;
+    }
 }
 
 fn sanitize_filename(name: &str) -> String {
@@ -137,17 +489,9 @@ mod tests {
         MonitorInfo {
             name: name.to_string(),
             description: format!("Test {}", name),
-            width: 1920,
-            height: 1080,
-            refresh_rate: 60.0,
-            x: 0,
-            y: 0,
-            scale: 1.0,
-            disabled: false,
-            transform: 0,
             workspaces: vec![1],
-            available_modes: vec![],
-            selected_mode: None,
+            dpms_status: Some(true),
+            ..Default::default()
         }
     }
 
@@ -169,6 +513,7 @@ mod tests {
         let configs = vec![
             MonitorConfig {
                 name: "DP-1".to_string(),
+                description: String::new(),
                 width: 2560,
                 height: 1440,
                 refresh_rate: 144.0,
@@ -178,14 +523,254 @@ mod tests {
                 disabled: false,
                 transform: 0,
                 workspaces: vec![1, 2],
+                selected_mode: None,
+                mode: None,
+                mirror_of: None,
+                primary: false,
+                vrr: 2,
+                vrr_set: true,
+                bitdepth: 10,
+                cm: Some("hdr".to_string()),
+                reserved: None,
             },
         ];
         apply_preset_to_monitors(&mut monitors, &configs);
         assert_eq!(monitors[0].width, 2560);
+        assert_eq!(monitors[0].vrr, 2);
+        assert_eq!((monitors[0].bitdepth, monitors[0].cm.as_deref()), (10, Some("hdr")));
         assert_eq!(monitors[0].scale, 1.5);
         assert_eq!(monitors[1].width, 1920); // DP-2 unchanged
     }
 
+    #[test]
+    fn test_swapped_connectors_keep_each_monitors_settings() {
+        let mut saved = [make_test_monitor("DP-1"), make_test_monitor("DP-2")];
+        saved[0].description = "Dell U2720Q".to_string();
+        saved[0].scale = 1.5;
+        saved[1].description = "LG 27GL850".to_string();
+        saved[1].x = 2560;
+        saved[1].mirror_of = Some("DP-1".to_string());
+        let configs: Vec<MonitorConfig> = saved.iter().map(MonitorConfig::from).collect();
+
+        // Same two monitors, plugged into each other's ports
+        let mut live = vec![make_test_monitor("DP-1"), make_test_monitor("DP-2")];
+        live[0].description = "LG 27GL850".to_string();
+        live[1].description = "Dell U2720Q".to_string();
+        apply_preset_to_monitors(&mut live, &configs);
+        assert_eq!((live[1].scale, live[1].x), (1.5, 0));
+        assert_eq!((live[0].scale, live[0].x), (1.0, 2560));
+        assert_eq!(live[0].mirror_of.as_deref(), Some("DP-2"));
+
+        // Identical monitors stay on their own connectors
+        let mut twins = [make_test_monitor("DP-1"), make_test_monitor("DP-2")];
+        twins[0].x = 1920;
+        let configs: Vec<MonitorConfig> = twins.iter()
+            .map(|m| MonitorConfig { description: "Twin".to_string(), ..MonitorConfig::from(m) })
+            .rev()
+            .collect();
+        let mut live = vec![make_test_monitor("DP-1"), make_test_monitor("DP-2")];
+        for m in &mut live {
+            m.description = "Twin".to_string();
+        }
+        apply_preset_to_monitors(&mut live, &configs);
+        assert_eq!((live[0].x, live[1].x), (1920, 0));
+    }
+
+    #[test]
+    fn test_presets_without_trusted_flag_are_untrusted() {
+        let preset: Preset = serde_json::from_str(r#"{"name": "desk", "monitors": []}"#).unwrap();
+        assert!(!preset.trusted);
+    }
+
+    #[test]
+    fn test_preset_summary() {
+        let mut monitors = vec![make_test_monitor("eDP-1"), make_test_monitor("DP-1"), make_test_monitor("HEADLESS-2")];
+        monitors[0].disabled = true;
+        let mut preset = build_preset("desk", &monitors, false);
+        assert_eq!(preset.monitor_count, 1);
+        assert!(preset.created_at.is_some());
+
+        preset.created_at = Some(1_704_412_800); // 2024-01-05 00:00 UTC
+        assert_eq!(preset.summary(), "1 monitor · saved 2024-01-05");
+
+        // Presets saved before either field existed count their monitors and leave out the date
+        let old: Preset = serde_json::from_str(&format!(
+            r#"{{"name": "desk", "monitors": {}}}"#,
+            serde_json::to_string(&preset.monitors).unwrap()
+        )).unwrap();
+        assert_eq!((old.created_at, old.monitor_count), (None, 0));
+        assert_eq!(old.summary(), "1 monitor");
+    }
+
+    #[test]
+    fn test_rename_preset() {
+        let dir = std::env::temp_dir().join(format!("monitui-rename-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut preset = build_preset("desk", &[make_test_monitor("DP-1")], false);
+        preset.trusted = true;
+        fs::write(dir.join("desk.json"), serde_json::to_string(&preset).unwrap()).unwrap();
+        fs::write(dir.join("laptop.json"), "{}").unwrap();
+
+        let err = rename_preset_in(&dir, "desk", "laptop").unwrap_err();
+        assert!(err.contains("already exists"));
+        assert!(dir.join("desk.json").exists());
+
+        assert_eq!(rename_preset_in(&dir, "desk", "home office").unwrap(), "home_office");
+        assert!(!dir.join("desk.json").exists());
+        let renamed: Preset = serde_json::from_str(&fs::read_to_string(dir.join("home_office.json")).unwrap()).unwrap();
+        assert_eq!(renamed.name, "home office");
+        assert!(renamed.trusted);
+        assert_eq!(renamed.monitors.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_preset_picks_a_free_name() {
+        let dir = std::env::temp_dir().join(format!("monitui-duplicate-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut preset = build_preset("desk", &[make_test_monitor("DP-1")], false);
+        preset.trusted = true;
+        fs::write(dir.join("desk.json"), serde_json::to_string(&preset).unwrap()).unwrap();
+
+        assert_eq!(duplicate_preset_in(&dir, "desk").unwrap(), "desk_copy");
+        assert_eq!(duplicate_preset_in(&dir, "desk").unwrap(), "desk_copy_2");
+        let copy: Preset = serde_json::from_str(&fs::read_to_string(dir.join("desk_copy_2.json")).unwrap()).unwrap();
+        assert_eq!(copy.name, "desk copy 2");
+        assert!(!copy.trusted);
+        assert_eq!(copy.monitors.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_import_preset_renames_on_collision() {
+        let dir = std::env::temp_dir().join(format!("monitui-import-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut preset = build_preset("desk", &[make_test_monitor("DP-1")], false);
+        preset.trusted = true;
+        let file = dir.join("shared.json");
+        fs::write(&file, serde_json::to_string(&preset).unwrap()).unwrap();
+
+        assert_eq!(import_preset_in(&dir, &file).unwrap(), "desk");
+        assert_eq!(import_preset_in(&dir, &file).unwrap(), "desk 2");
+        let imported: Preset = serde_json::from_str(&fs::read_to_string(dir.join("desk_2.json")).unwrap()).unwrap();
+        assert!(!imported.trusted);
+
+        fs::write(&file, "{\"monitors\": []}").unwrap();
+        assert!(import_preset_in(&dir, &file).unwrap_err().contains("not a monitui preset"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_configs_without_color_settings_default_to_8_bit() {
+        let mut json = serde_json::to_value(MonitorConfig::from(&make_test_monitor("DP-1"))).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("bitdepth");
+        fields.remove("cm");
+        let config: MonitorConfig = serde_json::from_value(json).unwrap();
+        assert_eq!((config.bitdepth, config.cm), (8, None));
+    }
+
+    #[test]
+    fn test_reserved_area_roundtrips_through_a_preset() {
+        let mut monitor = make_test_monitor("DP-1");
+        monitor.reserved = Some((30, 0, 12, 0));
+        let json = serde_json::to_string(&MonitorConfig::from(&monitor)).unwrap();
+        let config: MonitorConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config.reserved, Some((30, 0, 12, 0)));
+
+        let mut monitors = vec![make_test_monitor("DP-1")];
+        apply_preset_to_monitors(&mut monitors, &[config]);
+        assert_eq!(monitors[0].reserved, Some((30, 0, 12, 0)));
+
+        // Presets saved before the field existed have no reserved area
+        let mut json = serde_json::to_value(MonitorConfig::from(&make_test_monitor("DP-1"))).unwrap();
+        json.as_object_mut().unwrap().remove("reserved");
+        assert_eq!(serde_json::from_value::<MonitorConfig>(json).unwrap().reserved, None);
+    }
+
+    #[test]
+    fn test_best_match_prefers_preset_covering_everything() {
+        let mut docked_monitors = vec![make_test_monitor("eDP-1"), make_test_monitor("DP-1")];
+        docked_monitors[0].disabled = true;
+        let presets = vec![
+            build_preset("docked", &docked_monitors, false),
+            build_preset("laptop", &[make_test_monitor("eDP-1")], false),
+        ];
+        assert_eq!(presets[0].match_descriptions, vec!["Test DP-1".to_string()]);
+
+        // Docked: both presets match one monitor, but only "docked" knows about both
+        let connected = vec![make_test_monitor("eDP-1"), make_test_monitor("DP-1")];
+        let (chosen, found) = best_match(&presets, &connected).unwrap();
+        assert_eq!(chosen.name, "docked");
+        assert_eq!(found.matched, vec!["Test DP-1".to_string()]);
+
+        // Undocked: "docked" is missing its monitor
+        let (chosen, _) = best_match(&presets, &connected[..1]).unwrap();
+        assert_eq!(chosen.name, "laptop");
+
+        assert!(best_match(&presets, &[make_test_monitor("HDMI-A-1")]).is_none());
+    }
+
+    #[test]
+    fn test_match_by_description_survives_renamed_connector() {
+        let preset = build_preset("desk", &[make_test_monitor("DP-1")], false);
+        let mut renamed = make_test_monitor("DP-3");
+        renamed.description = "Test DP-1".to_string();
+        assert_eq!(match_preset(&preset, &[renamed]).matched, vec!["Test DP-1".to_string()]);
+
+        // Presets saved before descriptions were recorded fall back to names
+        let mut old = preset.clone();
+        old.match_descriptions.clear();
+        assert_eq!(match_preset(&old, &[make_test_monitor("DP-1")]).matched, vec!["DP-1".to_string()]);
+    }
+
+    #[test]
+    fn test_hardware_fingerprint_ignores_layout_and_headless() {
+        let mut moved = vec![make_test_monitor("eDP-1"), make_test_monitor("DP-1")];
+        let original = vec![make_test_monitor("DP-1"), make_test_monitor("eDP-1")];
+        moved[0].x = 1920;
+        moved[1].disabled = true;
+        moved.push(make_test_monitor("HEADLESS-2"));
+        assert_eq!(hardware_fingerprint(&moved), hardware_fingerprint(&original));
+        assert_ne!(hardware_fingerprint(&original), hardware_fingerprint(&original[..1]));
+    }
+
+    #[test]
+    fn test_preset_restores_selected_mode() {
+        let modes = vec![
+            AvailableMode { width: 3840, height: 2160, refresh: 60.0 },
+            AvailableMode { width: 2560, height: 1440, refresh: 143.97 },
+        ];
+        let mut saved = make_test_monitor("DP-1");
+        saved.available_modes = modes.clone();
+        saved.select_mode(1);
+        let json = serde_json::to_string(&MonitorConfig::from(&saved)).unwrap();
+        let config: MonitorConfig = serde_json::from_str(&json).unwrap();
+
+        let mut monitors = vec![make_test_monitor("DP-1")];
+        monitors[0].available_modes = modes;
+        apply_preset_to_monitors(&mut monitors, &[config]);
+        assert_eq!(monitors[0].selected_mode, Some(1));
+        assert_eq!(monitors[0].mode_string(), "2560x1440@143.97");
+    }
+
+    #[test]
+    fn test_preset_mode_falls_back_to_preferred() {
+        let mut saved = make_test_monitor("DP-1");
+        saved.available_modes = vec![AvailableMode { width: 2560, height: 1440, refresh: 144.0 }];
+        saved.select_mode(0);
+        let config = MonitorConfig::from(&saved);
+
+        // The monitor no longer offers that mode
+        let mut monitors = vec![make_test_monitor("DP-1")];
+        apply_preset_to_monitors(&mut monitors, &[config]);
+        assert_eq!(monitors[0].selected_mode, None);
+        assert_eq!(monitors[0].mode_string(), "preferred");
+    }
+
     #[test]
     fn test_sanitize_filename() {
         assert_eq!(sanitize_filename("my preset!"), "my_preset_");
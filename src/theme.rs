@@ -0,0 +1,78 @@
+use ratatui::style::Color;
+use std::str::FromStr;
+
+use crate::config::ThemeConfig;
+
+/// Colors the TUI draws with, each overridable in config.toml's `[theme]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Pane and menu borders, and the monitors that aren't selected.
+    pub border: Color,
+    /// The selected monitor, and the highlighted row in menus.
+    pub selected: Color,
+    /// Workspace assignments in the monitor list.
+    pub workspace: Color,
+    /// Disabled monitors in the list.
+    pub disabled: Color,
+    /// Things asking for attention: the confirm countdown, prompts, primary and headless markers.
+    pub accent: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            border: Color::Cyan,
+            selected: Color::Yellow,
+            workspace: Color::Magenta,
+            disabled: Color::DarkGray,
+            accent: Color::Yellow,
+        }
+    }
+}
+
+impl Theme {
+    /// The default palette with the colors set in `[theme]` on top. Colors are names
+    /// ("cyan", "light blue", "dark gray"), `#rrggbb` hex or a 0-255 terminal palette index.
+    pub fn from_config(config: &ThemeConfig) -> Result<Self, String> {
+        let mut theme = Theme::default();
+        for (name, value, color) in [
+            ("border", &config.border, &mut theme.border),
+            ("selected", &config.selected, &mut theme.selected),
+            ("workspace", &config.workspace, &mut theme.workspace),
+            ("disabled", &config.disabled, &mut theme.disabled),
+            ("accent", &config.accent, &mut theme.accent),
+        ] {
+            if let Some(value) = value {
+                *color = Color::from_str(value.trim())
+                    .map_err(|_| format!("can't read color \"{}\" for {}", value, name))?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_theme_is_the_default_palette() {
+        assert_eq!(Theme::from_config(&ThemeConfig::default()).unwrap(), Theme::default());
+    }
+
+    #[test]
+    fn test_named_and_hex_colors() {
+        let config: ThemeConfig = toml::from_str("border = \"light blue\"\nselected = \"#ff8800\"\naccent = \"208\"").unwrap();
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.border, Color::LightBlue);
+        assert_eq!(theme.selected, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.accent, Color::Indexed(208));
+        assert_eq!(theme.workspace, Color::Magenta);
+    }
+
+    #[test]
+    fn test_bad_color_is_an_error() {
+        let config: ThemeConfig = toml::from_str("disabled = \"#12345\"").unwrap();
+        assert!(Theme::from_config(&config).unwrap_err().contains("\"#12345\" for disabled"));
+    }
+}
@@ -1,34 +1,36 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     symbols::Marker,
     widgets::{
-        canvas::{Canvas, Rectangle},
+        canvas::{Canvas, Context, Line as CanvasLine},
         Block, Borders,
     },
     Frame,
 };
 
 use crate::app::App;
+use crate::layout;
+use crate::monitor::MonitorInfo;
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    // (index, 1-based position in the list pane, monitor) for visible, enabled monitors
     let enabled: Vec<_> = app.monitors.iter().enumerate()
         .filter(|(_, m)| {
-            // Filter for visible monitors (based on show_all_monitors flag) and enabled
-            let visible = if app.show_all_monitors {
-                true
-            } else {
-                !m.name.starts_with("HEADLESS-")
-            };
-            visible && !m.disabled
+            // Filter for visible monitors (based on show_all_monitors flag)
+            app.show_all_monitors || !m.name.starts_with("HEADLESS-")
         })
+        .enumerate()
+        .filter(|(_, (_, m))| !m.disabled)
+        .map(|(pos, (i, m))| (i, pos + 1, m))
         .collect();
 
+    let theme = app.theme;
     if enabled.is_empty() {
         let block = Block::default()
             .title(" Layout ")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
+            .border_style(Style::default().fg(theme.border));
         let msg = ratatui::widgets::Paragraph::new("No enabled monitors")
             .block(block)
             .style(Style::default().fg(Color::DarkGray));
@@ -36,94 +38,467 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let min_x = enabled.iter().map(|(_, m)| m.x).min().unwrap_or(0);
-    let max_x = enabled.iter().map(|(_, m)| m.x + m.logical_width()).max().unwrap_or(1920);
-    let min_y = enabled.iter().map(|(_, m)| m.y).min().unwrap_or(0);
-    let max_y = enabled.iter().map(|(_, m)| m.y + m.logical_height()).max().unwrap_or(1080);
-
-    let content_w = (max_x - min_x) as f64;
-    let content_h = (max_y - min_y) as f64;
-
-    if content_w <= 0.0 || content_h <= 0.0 { return; }
-
-    // Available drawing area (inside borders, with padding)
-    // Canvas uses ~2 braille dots per character width, ~4 per character height
-    let canvas_chars_w = (area.width.saturating_sub(2)) as f64;
-    let canvas_chars_h = (area.height.saturating_sub(2)) as f64;
-
-    // Aspect ratio fitting: scale content to fit canvas while preserving proportions.
-    // Braille cells are roughly 2:1 aspect (each cell is ~2px wide x 4px tall in dots,
-    // but characters are taller than wide), so we compensate.
-    let char_aspect = 2.0; // approximate width:height ratio of terminal characters
-    let effective_canvas_w = canvas_chars_w;
-    let effective_canvas_h = canvas_chars_h * char_aspect;
-
-    let scale_x = effective_canvas_w / content_w;
-    let scale_y = effective_canvas_h / content_h;
-    let scale = scale_x.min(scale_y);
-
-    // Scaled content dimensions in "character units"
-    let scaled_w = content_w * scale;
-    let scaled_h = content_h * scale;
+    // Mirrored monitors are drawn over their target instead of at their own position
+    let (mirrored, enabled): (Vec<_>, Vec<_>) = enabled.into_iter()
+        .partition(|(_, _, m)| m.mirror_of.is_some());
+    let monitors: Vec<_> = enabled.iter().map(|&(_, _, m)| m).collect();
+    let Some(transform) = CanvasTransform::new(&monitors, area, app.equalize_canvas, app.canvas_view, app.config.cell_aspect) else { return; };
 
-    // Center the content: compute padding
-    let pad_x = (effective_canvas_w - scaled_w) / 2.0;
-    let pad_y = (effective_canvas_h - scaled_h) / 2.0;
-
-    // Canvas coordinate space: we set bounds such that monitors map proportionally.
-    // We work in a uniform coordinate system and offset by padding.
-    let x_lo = min_x as f64 - pad_x / scale;
-    let x_hi = max_x as f64 + pad_x / scale;
-    let y_lo = min_y as f64 - pad_y / scale;
-    let y_hi = max_y as f64 + pad_y / scale;
+    // Overlaps and unintended gaps are drawn in red so they show before applying
+    let layout_rects: Vec<_> = monitors.iter().map(|m| m.layout_rect()).collect();
+    let overlaps = layout::overlaps(&layout_rects);
+    let gaps = layout::gaps(&layout_rects, app.config.gap);
+    let snap_targets = app.drag_snap_targets();
 
     let selected = app.selected;
+    let show_badges = app.config.show_order_badges;
+    let mut title = if app.equalize_canvas { " Layout (equalized, not to scale) ".to_string() } else { " Layout ".to_string() };
+    if !app.canvas_view.is_default() {
+        title.push_str(&format!("(zoom {:.2}x, Alt+0 resets) ", app.canvas_view.zoom));
+    }
+    let (x_bounds, y_bounds) = transform.bounds();
 
     let canvas = Canvas::default()
         .block(
             Block::default()
-                .title(" Layout ")
+                .title(title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(theme.border)),
         )
         .marker(Marker::Braille)
-        .x_bounds([x_lo, x_hi])
-        .y_bounds([y_lo, y_hi])
+        .x_bounds(x_bounds)
+        .y_bounds(y_bounds)
         .paint(move |ctx| {
-            for &(i, ref m) in &enabled {
-                let lw = m.logical_width() as f64;
-                let lh = m.logical_height() as f64;
+            for &(i, pos, m) in &enabled {
+                let (x, y, w, h) = transform.rect(m);
 
                 let color = if i == selected {
-                    Color::Yellow
+                    theme.selected
                 } else {
-                    Color::Cyan
+                    theme.border
                 };
 
-                // Flip y: canvas y increases upward, we want it downward
-                let flipped_y = (y_hi + y_lo) - m.y as f64 - lh;
-
-                ctx.draw(&Rectangle {
-                    x: m.x as f64,
-                    y: flipped_y,
-                    width: lw,
-                    height: lh,
-                    color,
-                });
+                draw_rect(ctx, &transform, x, y, w, h, color);
 
-                let cx = m.x as f64 + lw / 2.0;
-                let cy = flipped_y + lh / 2.0;
-                ctx.print(cx, cy + lh * 0.12, ratatui::text::Line::from(
+                let cx = x + w / 2.0;
+                let cy = y + h / 2.0;
+                ctx.print(cx, cy + h * 0.12, ratatui::text::Line::from(
                     ratatui::text::Span::styled(m.name.clone(), Style::default().fg(color))
                 ));
-                ctx.print(cx, cy - lh * 0.12, ratatui::text::Line::from(
+                ctx.print(cx, cy - h * 0.12, ratatui::text::Line::from(
                     ratatui::text::Span::styled(
                         format!("{}x{}", m.width, m.height),
                         Style::default().fg(Color::DarkGray),
                     )
                 ));
+
+                if show_badges {
+                    // Top-left corner, nudged inside the border
+                    ctx.print(x + w * 0.04, y + h * 0.8, ratatui::text::Line::from(
+                        ratatui::text::Span::styled(
+                            format!("{}", pos),
+                            Style::default().fg(color).add_modifier(Modifier::BOLD),
+                        )
+                    ));
+                }
+            }
+
+            for &(x, y, w, h) in &overlaps {
+                let (x, y, w, h) = transform.region(x, y, w, h);
+                draw_rect(ctx, &transform, x, y, w, h, Color::Red);
+                draw_line(ctx, &transform, (x, y), (x + w, y + h), Color::Red);
+                draw_line(ctx, &transform, (x, y + h), (x + w, y), Color::Red);
+            }
+            for &((x1, y1), (x2, y2)) in &gaps {
+                draw_line(ctx, &transform, transform.point(x1, y1), transform.point(x2, y2), Color::Red);
+            }
+
+            // While dragging, faint outlines show where monitors will snap on release
+            for &(x, y, w, h) in &snap_targets {
+                let (x, y, w, h) = transform.region(x, y, w, h);
+                draw_dashed_rect(ctx, &transform, x, y, w, h, Color::DarkGray);
+            }
+
+            for &(i, _, m) in &mirrored {
+                let Some(target) = monitors.iter().find(|t| Some(&t.name) == m.mirror_of.as_ref()) else {
+                    continue;
+                };
+                let (x, y, w, h) = transform.rect(target);
+                // Inset a little so the dashes don't sit on the target's border
+                let (x, y, w, h) = (x + 1.0, y + 1.0, w - 2.0, h - 2.0);
+                let color = if i == selected { theme.selected } else { Color::Magenta };
+                draw_dashed_rect(ctx, &transform, x, y, w, h, color);
+                ctx.print(x + w / 2.0, y + h * 0.15, ratatui::text::Line::from(
+                    ratatui::text::Span::styled(format!("⧉ {}", m.name), Style::default().fg(color))
+                ));
             }
         });
 
     f.render_widget(canvas, area);
 }
+
+/// Draw a canvas-space line, clipped to the pane. The `Canvas` widget drops lines with an
+/// end outside its bounds, which would make edges vanish once the view is zoomed or panned.
+fn draw_line(ctx: &mut Context, transform: &CanvasTransform, from: (f64, f64), to: (f64, f64), color: Color) {
+    if let Some(((x1, y1), (x2, y2))) = transform.clip(from, to) {
+        ctx.draw(&CanvasLine { x1, y1, x2, y2, color });
+    }
+}
+
+/// Outline a canvas-space rectangle, clipped to the pane.
+fn draw_rect(ctx: &mut Context, transform: &CanvasTransform, x: f64, y: f64, w: f64, h: f64, color: Color) {
+    draw_line(ctx, transform, (x, y), (x, y + h), color);
+    draw_line(ctx, transform, (x, y + h), (x + w, y + h), color);
+    draw_line(ctx, transform, (x + w, y + h), (x + w, y), color);
+    draw_line(ctx, transform, (x + w, y), (x, y), color);
+}
+
+/// Outline a rectangle with dashes, marking a monitor that mirrors another.
+fn draw_dashed_rect(ctx: &mut Context, transform: &CanvasTransform, x: f64, y: f64, w: f64, h: f64, color: Color) {
+    const DASH: f64 = 2.0;
+    let mut edge = |x1: f64, y1: f64, x2: f64, y2: f64| {
+        let len = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+        let mut t = 0.0;
+        while t < len {
+            let end = (t + DASH).min(len);
+            draw_line(
+                ctx,
+                transform,
+                (x1 + (x2 - x1) * t / len, y1 + (y2 - y1) * t / len),
+                (x1 + (x2 - x1) * end / len, y1 + (y2 - y1) * end / len),
+                color,
+            );
+            t += DASH * 2.0;
+        }
+    };
+    edge(x, y, x + w, y);
+    edge(x + w, y, x + w, y + h);
+    edge(x + w, y + h, x, y + h);
+    edge(x, y + h, x, y);
+}
+
+/// Display-only zoom and pan for the layout pane, applied on top of the automatic fit.
+/// Pan is measured in fractions of the pane, so a step moves the same distance at any zoom.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasView {
+    pub zoom: f64,
+    pub pan_x: f64,
+    pub pan_y: f64,
+}
+
+impl Default for CanvasView {
+    fn default() -> Self {
+        CanvasView { zoom: 1.0, pan_x: 0.0, pan_y: 0.0 }
+    }
+}
+
+impl CanvasView {
+    const ZOOM_STEP: f64 = 1.25;
+    const MIN_ZOOM: f64 = 0.25;
+    const MAX_ZOOM: f64 = 16.0;
+    const PAN_STEP: f64 = 0.1;
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom * Self::ZOOM_STEP).min(Self::MAX_ZOOM);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom / Self::ZOOM_STEP).max(Self::MIN_ZOOM);
+    }
+
+    /// Move the view one step; positive `dy` moves it down the layout.
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        self.pan_x += dx as f64 * Self::PAN_STEP;
+        self.pan_y += dy as f64 * Self::PAN_STEP;
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == CanvasView::default()
+    }
+}
+
+/// Maps between layout space (logical pixels) and the layout pane. Drawing and mouse
+/// hit-testing both go through this so they always agree on where a monitor is.
+///
+/// Canvas space spans the pane's inner area in character columns, with rows counted
+/// as `cell_aspect` units (config.toml's height:width ratio of a character cell) so the
+/// layout comes out to scale; y grows upward.
+pub struct CanvasTransform {
+    area: Rect,
+    cell_aspect: f64,
+    xs: Axis,
+    ys: Axis,
+    scale: f64,
+    pad_x: f64,
+    pad_y: f64,
+}
+
+impl CanvasTransform {
+    /// Fit `monitors` into `area`. With `equalize`, each span between monitor edges is
+    /// drawn at the square root of its size, so a huge monitor doesn't shrink its
+    /// neighbours to slivers. `view` then zooms around the pane's centre and pans.
+    /// Returns `None` if there is nothing to draw.
+    pub fn new(monitors: &[&MonitorInfo], area: Rect, equalize: bool, view: CanvasView, cell_aspect: f64) -> Option<Self> {
+        let xs = Axis::new(monitors.iter().flat_map(|m| [m.x, m.x + m.logical_width()]), equalize)?;
+        let ys = Axis::new(monitors.iter().flat_map(|m| [m.y, m.y + m.logical_height()]), equalize)?;
+
+        let (canvas_w, canvas_h) = Self::canvas_size(area, cell_aspect);
+        let fit = (canvas_w / xs.len()).min(canvas_h / ys.len());
+        let scale = fit * view.zoom;
+        Some(CanvasTransform {
+            area,
+            cell_aspect,
+            pad_x: (canvas_w - xs.len() * scale) / 2.0 - view.pan_x * canvas_w,
+            pad_y: (canvas_h - ys.len() * scale) / 2.0 - view.pan_y * canvas_h,
+            xs,
+            ys,
+            scale,
+        })
+    }
+
+    fn canvas_size(area: Rect, cell_aspect: f64) -> (f64, f64) {
+        let w = area.width.saturating_sub(2) as f64;
+        let h = area.height.saturating_sub(2) as f64 * cell_aspect;
+        (w, h)
+    }
+
+    /// Canvas x and y bounds to hand to the `Canvas` widget.
+    pub fn bounds(&self) -> ([f64; 2], [f64; 2]) {
+        let (w, h) = Self::canvas_size(self.area, self.cell_aspect);
+        ([0.0, w], [0.0, h])
+    }
+
+    /// Clip a canvas-space line to the bounds (Liang–Barsky), or `None` if it lies outside.
+    pub fn clip(&self, from: (f64, f64), to: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+        let ([left, right], [bottom, top]) = self.bounds();
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+        for (p, q) in [(-dx, from.0 - left), (dx, right - from.0), (-dy, from.1 - bottom), (dy, top - from.1)] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+        // Clamp away rounding error so the ends land inside the bounds
+        let at = |t: f64| ((from.0 + t * dx).clamp(left, right), (from.1 + t * dy).clamp(bottom, top));
+        Some((at(t0), at(t1)))
+    }
+
+    /// A monitor's rectangle in canvas space, as (x, y, width, height) from its bottom-left.
+    pub fn rect(&self, m: &MonitorInfo) -> (f64, f64, f64, f64) {
+        self.region(m.x, m.y, m.logical_width(), m.logical_height())
+    }
+
+    /// A layout-space rectangle in canvas space, as (x, y, width, height) from its bottom-left.
+    pub fn region(&self, x: i32, y: i32, w: i32, h: i32) -> (f64, f64, f64, f64) {
+        let (left, bottom) = self.point(x, y + h);
+        let (right, top) = self.point(x + w, y);
+        (left, bottom, right - left, top - bottom)
+    }
+
+    /// A layout-space point in canvas space.
+    pub fn point(&self, x: i32, y: i32) -> (f64, f64) {
+        let (_, canvas_h) = Self::canvas_size(self.area, self.cell_aspect);
+        (
+            self.pad_x + self.xs.to_display(x as f64) * self.scale,
+            canvas_h - (self.pad_y + self.ys.to_display(y as f64) * self.scale),
+        )
+    }
+
+    /// Layout coordinates under a terminal cell, or `None` if it is outside the pane.
+    pub fn terminal_to_layout(&self, col: u16, row: u16) -> Option<(f64, f64)> {
+        let area = self.area;
+        if col < area.x || col >= area.x + area.width || row < area.y || row >= area.y + area.height {
+            return None;
+        }
+        let click_x = (col - area.x).saturating_sub(1) as f64;
+        let click_y = (row - area.y).saturating_sub(1) as f64 * self.cell_aspect;
+        let x = self.xs.to_layout((click_x - self.pad_x) / self.scale);
+        let y = self.ys.to_layout((click_y - self.pad_y) / self.scale);
+        Some((x, y))
+    }
+}
+
+/// One axis of a `CanvasTransform`: a piecewise-linear map from layout coordinates to
+/// display coordinates, with a stop at every monitor edge. Mapping between stops is
+/// linear, so adjacent monitors stay adjacent and nothing starts to overlap.
+struct Axis {
+    /// Monitor edges in layout space, strictly increasing.
+    layout: Vec<f64>,
+    /// Where each edge lands in display space, starting from 0.
+    display: Vec<f64>,
+}
+
+impl Axis {
+    fn new(edges: impl Iterator<Item = i32>, equalize: bool) -> Option<Self> {
+        let mut edges: Vec<i32> = edges.collect();
+        edges.sort_unstable();
+        edges.dedup();
+        if edges.len() < 2 { return None; }
+
+        let mut display = vec![0.0];
+        for pair in edges.windows(2) {
+            let span = (pair[1] - pair[0]) as f64;
+            let shown = if equalize { span.sqrt() } else { span };
+            display.push(display[display.len() - 1] + shown);
+        }
+        let layout = edges.into_iter().map(|e| e as f64).collect();
+        Some(Axis { layout, display })
+    }
+
+    fn len(&self) -> f64 {
+        self.display[self.display.len() - 1]
+    }
+
+    fn to_display(&self, v: f64) -> f64 {
+        interpolate(&self.layout, &self.display, v)
+    }
+
+    fn to_layout(&self, v: f64) -> f64 {
+        interpolate(&self.display, &self.layout, v)
+    }
+}
+
+/// Map `v` from the `from` stops onto the `to` stops, linearly between neighbouring stops.
+/// Values outside the stops extend the first or last segment.
+fn interpolate(from: &[f64], to: &[f64], v: f64) -> f64 {
+    let i = from.windows(2)
+        .position(|w| v < w[1])
+        .unwrap_or(from.len() - 2);
+    to[i] + (v - from[i]) * (to[i + 1] - to[i]) / (from[i + 1] - from[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_monitor(name: &str, x: i32, width: u32, height: u32) -> MonitorInfo {
+        MonitorInfo { name: name.to_string(), x, width, height, ..Default::default() }
+    }
+
+    /// An 80x25 inner area, away from the terminal's origin.
+    const AREA: Rect = Rect { x: 10, y: 5, width: 82, height: 27 };
+
+    fn close(a: (f64, f64), b: (f64, f64)) -> bool {
+        (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9
+    }
+
+    #[test]
+    fn point_maps_back_to_its_cell() {
+        let monitors = [test_monitor("DP-1", 0, 1920, 1080), test_monitor("DP-2", 1920, 2560, 1440)];
+        let refs: Vec<&MonitorInfo> = monitors.iter().collect();
+        let mut view = CanvasView::default();
+        view.zoom_in();
+        view.zoom_in();
+        view.pan(1, 0);
+        let cell_aspect = 2.5;
+        let transform = CanvasTransform::new(&refs, AREA, false, view, cell_aspect).unwrap();
+        let ([_, canvas_w], [_, canvas_h]) = transform.bounds();
+
+        for (x, y) in [(2240, 720), (2000, 300), (3000, 1000)] {
+            // The terminal cell the canvas draws this point in
+            let (cx, cy) = transform.point(x, y);
+            assert!((0.0..canvas_w).contains(&cx) && (0.0..canvas_h).contains(&cy));
+            let col = AREA.x + 1 + cx.floor() as u16;
+            let row = AREA.y + 1 + ((canvas_h - cy) / cell_aspect).floor() as u16;
+
+            // Clicking it gives the cell's top-left corner: at most one cell's worth of
+            // layout pixels (1 column wide, `cell_aspect` units tall) before the point
+            let (lx, ly) = transform.terminal_to_layout(col, row).unwrap();
+            let (dx, dy) = (x as f64 - lx, y as f64 - ly);
+            assert!((0.0..1.0 / transform.scale).contains(&dx), "{} at {}x{}", dx, x, y);
+            assert!((0.0..cell_aspect / transform.scale).contains(&dy), "{} at {}x{}", dy, x, y);
+        }
+    }
+
+    #[test]
+    fn clicking_a_monitor_centre_hits_it_when_equalized() {
+        let monitors = [test_monitor("DP-1", 0, 1280, 720), test_monitor("DP-2", 1280, 7680, 4320)];
+        let refs: Vec<&MonitorInfo> = monitors.iter().collect();
+        let transform = CanvasTransform::new(&refs, AREA, true, CanvasView::default(), 1.6).unwrap();
+        let (_, [_, canvas_h]) = transform.bounds();
+        for m in &monitors {
+            let (x, y, w, h) = transform.rect(m);
+            let col = AREA.x + 1 + (x + w / 2.0).floor() as u16;
+            let row = AREA.y + 1 + ((canvas_h - (y + h / 2.0)) / 1.6).floor() as u16;
+            let (lx, ly) = transform.terminal_to_layout(col, row).unwrap();
+            assert!((m.x as f64..(m.x + m.logical_width()) as f64).contains(&lx), "{} {}", m.name, lx);
+            assert!((m.y as f64..(m.y + m.logical_height()) as f64).contains(&ly), "{} {}", m.name, ly);
+        }
+    }
+
+    #[test]
+    fn clicks_outside_the_pane_miss() {
+        let monitors = [test_monitor("DP-1", 0, 1920, 1080)];
+        let transform = CanvasTransform::new(&[&monitors[0]], AREA, false, CanvasView::default(), 2.0).unwrap();
+        assert!(transform.terminal_to_layout(AREA.x - 1, AREA.y + 3).is_none());
+        assert!(transform.terminal_to_layout(AREA.x + 3, AREA.y + AREA.height).is_none());
+        assert!(transform.terminal_to_layout(AREA.x + 3, AREA.y + 3).is_some());
+    }
+
+    #[test]
+    fn zoom_keeps_the_centre_and_pan_moves_by_a_fraction_of_the_pane() {
+        let monitors = [test_monitor("DP-1", 0, 1920, 1080)];
+        let (w, h) = (80.0, 25.0 * 2.5);
+        let mut view = CanvasView::default();
+        for _ in 0..3 {
+            let transform = CanvasTransform::new(&[&monitors[0]], AREA, false, view, 2.5).unwrap();
+            assert!(close(transform.point(960, 540), (w / 2.0, h / 2.0)));
+            view.zoom_in();
+        }
+
+        // At the default zoom the monitor fills the pane's width or its height
+        let transform = CanvasTransform::new(&[&monitors[0]], AREA, false, CanvasView::default(), 2.5).unwrap();
+        let (x, y, rw, rh) = transform.rect(&monitors[0]);
+        assert!((rw - w).abs() < 1e-9 || (rh - h).abs() < 1e-9);
+        assert!(x >= -1e-9 && y >= -1e-9 && x + rw <= w + 1e-9 && y + rh <= h + 1e-9);
+
+        // Panning right and down slides the layout left and up by a tenth of the pane, at any zoom
+        let mut panned = CanvasView { zoom: 4.0, ..CanvasView::default() };
+        let before = CanvasTransform::new(&[&monitors[0]], AREA, false, panned, 2.5).unwrap().point(960, 540);
+        panned.pan(1, 1);
+        let after = CanvasTransform::new(&[&monitors[0]], AREA, false, panned, 2.5).unwrap().point(960, 540);
+        assert!(close(after, (before.0 - w * 0.1, before.1 + h * 0.1)));
+
+        // Zoom stays within its limits
+        let mut view = CanvasView::default();
+        (0..50).for_each(|_| view.zoom_in());
+        assert_eq!(view.zoom, CanvasView::MAX_ZOOM);
+        (0..50).for_each(|_| view.zoom_out());
+        assert_eq!(view.zoom, CanvasView::MIN_ZOOM);
+    }
+
+    #[test]
+    fn clip_edge_cases() {
+        let monitors = [test_monitor("DP-1", 0, 1920, 1080)];
+        let transform = CanvasTransform::new(&[&monitors[0]], AREA, false, CanvasView::default(), 2.5).unwrap();
+        let (_, [_, h]) = transform.bounds();
+        let w = 80.0;
+
+        // Inside: unchanged
+        assert_eq!(transform.clip((1.0, 2.0), (30.0, 40.0)), Some(((1.0, 2.0), (30.0, 40.0))));
+        // Crossing one edge: cut at it
+        assert_eq!(transform.clip((-10.0, 5.0), (10.0, 5.0)), Some(((0.0, 5.0), (10.0, 5.0))));
+        // Through the whole pane corner to corner, from beyond both ends
+        assert_eq!(transform.clip((-w, -h), (2.0 * w, 2.0 * h)), Some(((0.0, 0.0), (w, h))));
+        // Entirely on one side, or parallel to an edge and outside it
+        assert_eq!(transform.clip((-10.0, 5.0), (-1.0, 50.0)), None);
+        assert_eq!(transform.clip((5.0, h + 1.0), (60.0, h + 1.0)), None);
+        assert_eq!(transform.clip((w + 0.5, 1.0), (w + 0.5, 30.0)), None);
+        // Passing by a corner without entering
+        assert_eq!(transform.clip((-5.0, 2.0), (2.0, -5.0)), None);
+        // Along an edge counts as inside
+        assert_eq!(transform.clip((0.0, -5.0), (0.0, 5.0)), Some(((0.0, 0.0), (0.0, 5.0))));
+        // A single point
+        assert_eq!(transform.clip((3.0, 3.0), (3.0, 3.0)), Some(((3.0, 3.0), (3.0, 3.0))));
+        assert_eq!(transform.clip((-3.0, 3.0), (-3.0, 3.0)), None);
+    }
+}
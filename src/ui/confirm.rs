@@ -1,5 +1,5 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
@@ -7,17 +7,21 @@ use ratatui::{
 };
 use std::time::Duration;
 
-pub fn draw(f: &mut Frame, remaining: Duration, area: Rect) {
-    let popup = centered_rect_with_min_size(50, 14, area);
+use super::centered_rect_with_min_size;
+use crate::theme::Theme;
+
+pub fn draw(f: &mut Frame, remaining: Duration, total: Duration, theme: &Theme, area: Rect) {
+    let popup = centered_rect_with_min_size(50, 25, 50, 14, area);
     f.render_widget(Clear, popup);
 
     let secs = remaining.as_secs();
     let bar_width = 20u16;
-    let filled = ((secs as f64 / 10.0) * bar_width as f64).ceil() as usize;
+    let total_secs = total.as_secs().max(1);
+    let filled = ((secs as f64 / total_secs as f64) * bar_width as f64).ceil() as usize;
     let empty = bar_width as usize - filled;
     let bar = format!("[{}{}]", "█".repeat(filled), "░".repeat(empty));
 
-    let color = if secs <= 3 { Color::Red } else { Color::Yellow };
+    let color = if secs <= 3 { Color::Red } else { theme.accent };
 
     let lines = vec![
         Line::from(""),
@@ -36,6 +40,10 @@ pub fn draw(f: &mut Frame, remaining: Duration, area: Rect) {
             "[Y / Space] Keep   [N / Esc] Revert",
             Style::default().fg(Color::DarkGray),
         )),
+        Line::from(Span::styled(
+            "[S] Keep and save as a preset",
+            Style::default().fg(Color::DarkGray),
+        )),
     ];
 
     let para = Paragraph::new(lines)
@@ -49,27 +57,3 @@ pub fn draw(f: &mut Frame, remaining: Duration, area: Rect) {
 
     f.render_widget(para, popup);
 }
-
-/// Create a centered popup rect with minimum dimensions
-fn centered_rect_with_min_size(min_width: u16, min_height: u16, area: Rect) -> Rect {
-    let width = min_width.max((area.width * 50) / 100);
-    let height = min_height.max((area.height * 25) / 100);
-
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length((area.height.saturating_sub(height)) / 2),
-            Constraint::Length(height),
-            Constraint::Length((area.height.saturating_sub(height)) / 2),
-        ])
-        .split(area);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Length((area.width.saturating_sub(width)) / 2),
-            Constraint::Length(width),
-            Constraint::Length((area.width.saturating_sub(width)) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
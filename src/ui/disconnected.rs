@@ -0,0 +1,48 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::theme::Theme;
+
+pub fn draw(f: &mut Frame, names: &[String], theme: &Theme, area: Rect) {
+    let popup = centered_rect(50, 35, area);
+    f.render_widget(Clear, popup);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("⚠ {} appears disconnected", names.join(", ")),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Hyprland no longer reports it, so enabling it may fail",
+            Style::default().fg(Color::White),
+        )),
+        Line::from(Span::styled(
+            "or do nothing. Check the cable and try again.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "[Y] Apply anyway   [N / Esc] Cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Monitor Disconnected ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent)),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(para, popup);
+}
@@ -1,20 +1,32 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-pub fn draw(f: &mut Frame, area: Rect) {
-    let popup = centered_rect_with_min_size(60, 16, area);
+use super::centered_rect_with_min_size;
+use crate::theme::Theme;
+
+/// Most change lines listed before the rest are summed up as "…and N more".
+const MAX_CHANGES: usize = 5;
+
+pub fn draw(f: &mut Frame, confirming_pull: bool, changes: &[String], theme: &Theme, area: Rect) {
+    let shown = changes.len().min(MAX_CHANGES) + usize::from(changes.len() > MAX_CHANGES);
+    let popup = centered_rect_with_min_size(60, 30, 60, 16 + shown as u16, area);
     f.render_widget(Clear, popup);
 
-    let lines = vec![
+    if confirming_pull {
+        draw_pull_confirm(f, popup);
+        return;
+    }
+
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "⚠ External Configuration Change Detected",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(Span::styled(
@@ -26,6 +38,20 @@ pub fn draw(f: &mut Frame, area: Rect) {
             Style::default().fg(Color::DarkGray),
         )),
         Line::from(""),
+    ];
+    for change in changes.iter().take(MAX_CHANGES) {
+        lines.push(Line::from(Span::styled(change.clone(), Style::default().fg(Color::Cyan))));
+    }
+    if changes.len() > MAX_CHANGES {
+        lines.push(Line::from(Span::styled(
+            format!("…and {} more", changes.len() - MAX_CHANGES),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    if !changes.is_empty() {
+        lines.push(Line::from(""));
+    }
+    lines.extend([
         Line::from(Span::styled(
             "What would you like to do?",
             Style::default().fg(Color::White),
@@ -43,40 +69,47 @@ pub fn draw(f: &mut Frame, area: Rect) {
             "[Q/Esc] Quit application",
             Style::default().fg(Color::Red),
         )),
-    ];
+    ]);
 
     let para = Paragraph::new(lines)
         .block(
             Block::default()
                 .title(" Configuration Change ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow)),
+                .border_style(Style::default().fg(theme.accent)),
         )
         .alignment(Alignment::Center);
 
     f.render_widget(para, popup);
 }
 
-/// Create a centered popup rect with minimum dimensions
-fn centered_rect_with_min_size(min_width: u16, min_height: u16, area: Rect) -> Rect {
-    let width = min_width.max((area.width * 60) / 100);
-    let height = min_height.max((area.height * 30) / 100);
+fn draw_pull_confirm(f: &mut Frame, area: Rect) {
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "⚠ Discard Unsaved Edits?",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Pulling will discard your unsaved edits — continue?",
+            Style::default().fg(Color::White),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "[Y] Pull and discard   [N / Esc] Back",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
 
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length((area.height.saturating_sub(height)) / 2),
-            Constraint::Length(height),
-            Constraint::Length((area.height.saturating_sub(height)) / 2),
-        ])
-        .split(area);
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Configuration Change ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        )
+        .alignment(Alignment::Center);
 
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Length((area.width.saturating_sub(width)) / 2),
-            Constraint::Length(width),
-            Constraint::Length((area.width.saturating_sub(width)) / 2),
-        ])
-        .split(popup_layout[1])[1]
+    f.render_widget(para, area);
 }
@@ -7,33 +7,29 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::monitor::MonitorInfo;
 
-pub fn draw(f: &mut Frame, app: &App, area: Rect) {
-    let visible: Vec<(usize, _)> = app
-        .monitors
-        .iter()
-        .enumerate()
-        .filter(|(_, m)| {
-            // Filter based on show_all_monitors flag
-            if app.show_all_monitors {
-                true
-            } else {
-                !m.name.starts_with("HEADLESS-")
-            }
-        })
-        .collect();
+/// Rows each listed monitor takes up, as `draw` lays them out, for scrolling and clicks.
+pub fn item_heights(app: &App) -> Vec<u16> {
+    items(app).iter().map(|item| item.height() as u16).collect()
+}
 
-    let items: Vec<ListItem> = visible
-        .iter()
-        .map(|(i, m)| {
-            let is_selected = *i == app.selected;
+/// One item per listed monitor: name and status when disabled, otherwise name, mode,
+/// logical size, position, DPI and workspaces.
+fn items(app: &App) -> Vec<ListItem<'_>> {
+    let theme = &app.theme;
+    app.listed_monitors()
+        .into_iter()
+        .map(|i| {
+            let m: &MonitorInfo = &app.monitors[i];
+            let is_selected = i == app.selected;
 
             let name_style = if m.disabled {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.disabled)
             } else if is_selected {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.selected).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme.border)
             };
 
             let marker = if is_selected { "▸ " } else { "  " };
@@ -42,7 +38,10 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
             let mut name_spans = vec![Span::styled(marker, name_style)];
 
             if is_headless {
-                name_spans.push(Span::styled("[HEADLESS] ", Style::default().fg(Color::Yellow)));
+                name_spans.push(Span::styled("[HEADLESS] ", Style::default().fg(theme.accent)));
+            }
+            if m.primary {
+                name_spans.push(Span::styled("★ ", Style::default().fg(theme.accent)));
             }
 
             name_spans.push(Span::styled(
@@ -64,14 +63,59 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
             } else {
                 lines.push(Line::from(vec![
                     Span::raw("    "),
-                    Span::styled(m.resolution_string(), Style::default().fg(Color::Green)),
+                    Span::styled(m.resolution_string(app.config.refresh_decimals), Style::default().fg(Color::Green)),
                     Span::styled(format!("  {:.2}x", m.scale), Style::default().fg(Color::Green)),
+                    Span::styled(
+                        if app.config.scale_locked.contains(&m.name) { " 🔒" } else { "" },
+                        Style::default().fg(Color::Yellow),
+                    ),
                     Span::styled(format!("  {}", m.rotation_string()), Style::default().fg(Color::Green)),
+                    Span::styled(
+                        if m.vrr == 0 { String::new() } else { format!("  {}", m.vrr_string()) },
+                        Style::default().fg(Color::Green),
+                    ),
                 ]));
-                lines.push(Line::from(vec![
+                // The size it takes up in the layout, after scale and rotation
+                let mut logical_spans = vec![
+                    Span::raw("    "),
+                    Span::styled(
+                        format!("Logical: {}x{}", m.logical_width(), m.logical_height()),
+                        Style::default().fg(Color::Green),
+                    ),
+                ];
+                if let Some((top, bottom, left, right)) = m.reserved {
+                    logical_spans.push(Span::styled(
+                        format!("  Reserved: {} {} {} {}", top, bottom, left, right),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                lines.push(Line::from(logical_spans));
+                let mut pos_spans = vec![
                     Span::raw("    "),
                     Span::styled(format!("Pos: {}x{}", m.x, m.y), Style::default().fg(Color::Blue)),
                     Span::styled(format!("  {}", m.name), Style::default().fg(Color::DarkGray)),
+                ];
+                if let Some(target) = &m.mirror_of {
+                    pos_spans.push(Span::styled(format!("  ⧉ mirrors {}", target), Style::default().fg(Color::Magenta)));
+                }
+                if m.bitdepth == 10 || m.cm.is_some() {
+                    pos_spans.push(Span::styled(format!("  {}", m.color_string()), Style::default().fg(Color::Magenta)));
+                }
+                if app.dpms_off.contains(&m.name) {
+                    pos_spans.push(Span::styled("  (screen off)", Style::default().fg(Color::DarkGray)));
+                } else if m.no_signal() {
+                    pos_spans.push(Span::styled("  ⚠ NO SIGNAL", Style::default().fg(Color::Red)));
+                } else if let Some(format) = &m.current_format {
+                    pos_spans.push(Span::styled(format!("  {}", format), Style::default().fg(Color::DarkGray)));
+                }
+                lines.push(Line::from(pos_spans));
+                let dpi_text = match m.dpi() {
+                    Some(dpi) => format!("DPI: {:.0} ({:.0} effective at {:.2}x)", dpi, dpi / m.scale, m.scale),
+                    None => "DPI: n/a".to_string(),
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(dpi_text, Style::default().fg(Color::DarkGray)),
                 ]));
                 let ws_text = if m.workspaces.is_empty() {
                     "WS: -".to_string()
@@ -83,26 +127,36 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
                 };
                 lines.push(Line::from(vec![
                     Span::raw("    "),
-                    Span::styled(ws_text, Style::default().fg(Color::Magenta)),
+                    Span::styled(ws_text, Style::default().fg(theme.workspace)),
                 ]));
             }
 
             ListItem::new(lines)
         })
-        .collect();
+        .collect()
+}
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let items = items(app);
 
-    let title = if app.changed { " Monitors * " } else { " Monitors " };
+    let changed = if app.changed { " *" } else { "" };
+    let title = if app.filter.is_empty() {
+        format!(" Monitors{} ", changed)
+    } else {
+        format!(" Monitors{} /{} ", changed, app.filter)
+    };
 
     let list = List::new(items)
         .block(
             Block::default()
                 .title(title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(theme.border)),
         )
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
 
-    let mut state = ListState::default();
-    state.select(visible.iter().position(|(i, _)| *i == app.selected));
+    let mut state = ListState::default().with_offset(app.list_offset);
+    state.select(app.listed_monitors().iter().position(|&i| i == app.selected));
     f.render_stateful_widget(list, area, &mut state);
 }
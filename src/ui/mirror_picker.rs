@@ -0,0 +1,47 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::theme::Theme;
+
+pub fn draw(f: &mut Frame, name: &str, current: Option<&str>, selected: usize, targets: &[String], theme: &Theme, area: Rect) {
+    let popup = centered_rect(40, 40, area);
+    f.render_widget(Clear, popup);
+
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::styled(marker(current.is_none()), Style::default().fg(Color::Green)),
+        Span::styled("Don't mirror", Style::default().fg(Color::White)),
+    ]))];
+    for target in targets {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(marker(current == Some(target.as_str())), Style::default().fg(Color::Green)),
+            Span::styled(format!("Mirror {}", target), Style::default().fg(Color::Cyan)),
+        ])));
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" Mirror: {} ", name))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(theme.selected)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        );
+
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+fn marker(current: bool) -> &'static str {
+    if current { " ● " } else { "   " }
+}
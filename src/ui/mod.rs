@@ -4,6 +4,13 @@ pub mod preset_menu;
 pub mod status_bar;
 pub mod confirm;
 pub mod external_change;
+pub mod verify_monitor;
+pub mod mode_picker;
+pub mod mirror_picker;
+pub mod disconnected;
+pub mod scale_input;
+pub mod workspace_input;
+pub mod preset_preview;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -46,6 +53,8 @@ pub fn draw(f: &mut Frame, app: &mut App) {
 
     app.list_area = list_area;
     app.canvas_area = canvas_area;
+    app.list_heights = list_pane::item_heights(app);
+    app.scroll_list_to_selected();
     list_pane::draw(f, app, list_area);
     canvas_pane::draw(f, app, canvas_area);
     status_bar::draw(f, app, outer[1]);
@@ -55,15 +64,42 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         Overlay::Confirm { countdown_start, duration, .. } => {
             let elapsed = countdown_start.elapsed();
             let remaining = duration.saturating_sub(elapsed);
-            confirm::draw(f, remaining, size);
+            confirm::draw(f, remaining, *duration, &app.theme, size);
         }
-        Overlay::ExternalChange => {
-            external_change::draw(f, size);
+        Overlay::VerifyMonitor { pending, countdown_start, duration, .. } => {
+            let remaining = duration.saturating_sub(countdown_start.elapsed());
+            verify_monitor::draw(f, &pending[0], remaining, &app.theme, size);
         }
-        Overlay::Presets { selected, names, saving, input } => {
-            preset_menu::draw(f, *selected, names, *saving, input, size);
+        Overlay::ExternalChange { confirming_pull, changes, .. } => {
+            external_change::draw(f, *confirming_pull, changes, &app.theme, size);
         }
-        Overlay::None => {}
+        Overlay::Disconnected { names, .. } => {
+            disconnected::draw(f, names, &app.theme, size);
+        }
+        Overlay::PresetPreview { changes, preset, .. } => {
+            preset_preview::draw(f, preset.as_ref().map(|(_, name)| name.as_str()), changes, &app.theme, size);
+        }
+        Overlay::MirrorPicker { selected, targets } => {
+            let monitor = &app.monitors[app.selected];
+            mirror_picker::draw(f, &monitor.name, monitor.mirror_of.as_deref(), *selected, targets, &app.theme, size);
+        }
+        Overlay::ScaleInput { input } => {
+            let monitor = &app.monitors[app.selected];
+            scale_input::draw(f, &monitor.name, monitor.scale, input, &app.theme, size);
+        }
+        Overlay::WorkspaceInput { input } => {
+            let monitor = &app.monitors[app.selected];
+            workspace_input::draw(f, &monitor.name, &monitor.workspaces, input, &app.theme, size);
+        }
+        Overlay::ModePicker { selected } => {
+            let monitor = &app.monitors[app.selected];
+            mode_picker::draw(f, monitor, *selected, app.config.refresh_decimals, &app.theme, size);
+        }
+        Overlay::Presets { selected, names, trusted, details, saving, input, renaming } => {
+            preset_menu::draw(f, *selected, names, trusted, details, *saving, input, renaming.as_deref(), &app.theme, size);
+        }
+        // The jump, filter and reserved area prompts live in the status bar
+        Overlay::Jump { .. } | Overlay::Filter | Overlay::ReservedInput { .. } | Overlay::Align | Overlay::Color | Overlay::None => {}
     }
 }
 
@@ -87,3 +123,27 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+/// Like `centered_rect`, but never smaller than `min_width` x `min_height` cells.
+pub fn centered_rect_with_min_size(percent_x: u16, percent_y: u16, min_width: u16, min_height: u16, area: Rect) -> Rect {
+    let width = min_width.max((area.width * percent_x) / 100);
+    let height = min_height.max((area.height * percent_y) / 100);
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length((area.height.saturating_sub(height)) / 2),
+            Constraint::Length(height),
+            Constraint::Length((area.height.saturating_sub(height)) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length((area.width.saturating_sub(width)) / 2),
+            Constraint::Length(width),
+            Constraint::Length((area.width.saturating_sub(width)) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,55 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::monitor::MonitorInfo;
+use crate::theme::Theme;
+
+pub fn draw(f: &mut Frame, monitor: &MonitorInfo, selected: usize, refresh_decimals: u8, theme: &Theme, area: Rect) {
+    let popup = centered_rect(40, 60, area);
+    f.render_widget(Clear, popup);
+
+    let current = monitor.current_mode_index();
+    let items: Vec<ListItem> = monitor.available_modes.iter().enumerate()
+        .map(|(idx, mode)| {
+            let (marker, color) = if Some(idx) == current {
+                ("● ", Color::Green)
+            } else {
+                ("  ", Color::White)
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {}", marker), Style::default().fg(Color::Green)),
+                Span::styled(
+                    format!("{:.1$}", mode, refresh_decimals as usize),
+                    Style::default().fg(color),
+                ),
+            ];
+            if Some(idx) == current {
+                spans.push(Span::styled(" (current)", Style::default().fg(Color::DarkGray)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" Modes: {} ", monitor.name))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(theme.selected)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        );
+
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(list, popup, &mut state);
+}
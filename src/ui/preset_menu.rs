@@ -7,19 +7,21 @@ use ratatui::{
 };
 
 use super::centered_rect;
+use crate::theme::Theme;
 
-pub fn draw(f: &mut Frame, selected: usize, names: &[String], saving: bool, input: &str, area: Rect) {
+#[allow(clippy::too_many_arguments)]
+pub fn draw(f: &mut Frame, selected: usize, names: &[String], trusted: &[String], details: &[String], saving: bool, input: &str, renaming: Option<&str>, theme: &Theme, area: Rect) {
     let popup = centered_rect(50, 60, area);
     f.render_widget(Clear, popup);
 
     if saving {
-        draw_save_dialog(f, input, popup);
+        draw_save_dialog(f, input, renaming, theme, popup);
     } else {
-        draw_preset_list(f, selected, names, popup);
+        draw_preset_list(f, selected, names, trusted, details, theme, popup);
     }
 }
 
-fn draw_preset_list(f: &mut Frame, selected: usize, names: &[String], area: Rect) {
+fn draw_preset_list(f: &mut Frame, selected: usize, names: &[String], trusted: &[String], details: &[String], theme: &Theme, area: Rect) {
     let mut items = Vec::new();
 
     // "Most Recent Apply" entry - now [0]
@@ -37,11 +39,22 @@ fn draw_preset_list(f: &mut Frame, selected: usize, names: &[String], area: Rect
             "     ".to_string()  // No number for 10+
         };
 
-        items.push(ListItem::new(Line::from(vec![
+        let mut spans = vec![
             Span::styled(num, Style::default().fg(Color::DarkGray)),
             Span::styled("● ", Style::default().fg(Color::Cyan)),
             Span::styled(name.clone(), Style::default().fg(Color::White)),
-        ])));
+        ];
+        if trusted.contains(name) {
+            spans.push(Span::styled("  ✓ trusted", Style::default().fg(Color::Green)));
+        }
+        let mut lines = vec![Line::from(spans)];
+        if let Some(detail) = details.get(idx).filter(|d| !d.is_empty()) {
+            lines.push(Line::from(Span::styled(
+                format!("       {}", detail),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        items.push(ListItem::new(lines));
     }
 
     if items.len() == 1 {
@@ -56,11 +69,11 @@ fn draw_preset_list(f: &mut Frame, selected: usize, names: &[String], area: Rect
             Block::default()
                 .title(" Presets ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Magenta)),
+                .border_style(Style::default().fg(theme.border)),
         )
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.selected)
                 .add_modifier(Modifier::BOLD | Modifier::REVERSED),
         );
 
@@ -69,23 +82,27 @@ fn draw_preset_list(f: &mut Frame, selected: usize, names: &[String], area: Rect
     f.render_stateful_widget(list, area, &mut state);
 }
 
-fn draw_save_dialog(f: &mut Frame, input: &str, area: Rect) {
+fn draw_save_dialog(f: &mut Frame, input: &str, renaming: Option<&str>, theme: &Theme, area: Rect) {
     let inner = centered_rect(80, 30, area);
+    let (prompt, action, title) = match renaming {
+        Some(old) => (format!("New name for {}:", old), "[Enter] Rename  [Esc] Cancel", " Rename Preset "),
+        None => ("Enter preset name:".to_string(), "[Enter] Save  [Esc] Cancel", " Save Preset "),
+    };
 
     let lines = vec![
         Line::from(""),
         Line::from(Span::styled(
-            "Enter preset name:",
+            prompt,
             Style::default().fg(Color::White),
         )),
         Line::from(""),
         Line::from(Span::styled(
             format!("▸ {}_", input),
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(Span::styled(
-            "[Enter] Save  [Esc] Cancel",
+            action,
             Style::default().fg(Color::DarkGray),
         )),
     ];
@@ -93,7 +110,7 @@ fn draw_save_dialog(f: &mut Frame, input: &str, area: Rect) {
     let para = Paragraph::new(lines)
         .block(
             Block::default()
-                .title(" Save Preset ")
+                .title(title)
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Green)),
         )
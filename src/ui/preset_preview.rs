@@ -0,0 +1,50 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::theme::Theme;
+
+pub fn draw(f: &mut Frame, name: Option<&str>, changes: &[String], theme: &Theme, area: Rect) {
+    let popup = centered_rect(60, 50, area);
+    f.render_widget(Clear, popup);
+
+    let mut lines = vec![Line::from("")];
+    if changes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "(no changes)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for change in changes {
+        let (monitor, what) = change.split_once(": ").unwrap_or((change, ""));
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", monitor), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(what.to_string(), Style::default().fg(Color::White)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "[Y / Enter] Apply   [N / Esc] Cancel",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let title = match name {
+        Some(name) => format!(" Load preset: {} ", name),
+        None => " Load recent configuration ".to_string(),
+    };
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(para, popup);
+}
@@ -0,0 +1,44 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::theme::Theme;
+
+pub fn draw(f: &mut Frame, name: &str, current: f32, input: &str, theme: &Theme, area: Rect) {
+    let popup = centered_rect(40, 30, area);
+    f.render_widget(Clear, popup);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Scale for {} (now {:.2}x):", name, current),
+            Style::default().fg(Color::White),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("▸ {}_", input),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "[Enter] Set  [Esc] Cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Exact Scale ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(para, popup);
+}
@@ -1,51 +1,157 @@
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
 
 use crate::app::{App, Overlay};
+use crate::monitor;
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let mut lines = Vec::new();
 
-    let msg_color = if app.status_msg.contains("Error") || app.status_msg.contains("revert") {
+    let msg_color = if app.status_msg.contains("Error") || app.status_msg.contains("revert") || app.status_msg.contains("disconnected") {
         Color::Red
     } else if app.status_msg.contains("saved") || app.status_msg.contains("Saved") {
         Color::Green
     } else {
         Color::White
     };
-    lines.push(Line::from(Span::styled(&app.status_msg, Style::default().fg(msg_color))));
+    let mut status = Vec::new();
+    if app.read_only {
+        status.push(Span::styled(
+            " READ-ONLY ",
+            Style::default().fg(Color::Black).bg(Color::Blue).add_modifier(Modifier::BOLD),
+        ));
+        status.push(Span::raw("  "));
+    }
+    if !app.require_confirm {
+        status.push(Span::styled(
+            " CONFIRM OFF ",
+            Style::default().fg(Color::Black).bg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+        status.push(Span::raw("  "));
+    }
+    status.push(Span::styled(app.status_msg.as_str(), Style::default().fg(msg_color)));
+    if let Some((_, name)) = &app.current_preset {
+        status.push(Span::styled(format!("  [preset: {}]", name), Style::default().fg(theme.border)));
+    }
+    if let Some(e) = &app.hook_error {
+        status.push(Span::styled(format!("  ⚠ {}", e), Style::default().fg(Color::Red)));
+    }
+    if let Some((min, max)) = monitor::refresh_mismatch(&app.monitors) {
+        let decimals = app.config.refresh_decimals;
+        status.push(Span::styled(
+            format!(
+                "  ⚠ {} / {} without VRR can stutter — enable VRR or use a common rate",
+                monitor::format_refresh(min, decimals),
+                monitor::format_refresh(max, decimals)
+            ),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    if let (Overlay::None, Some((at, clock))) = (&app.overlay, &app.last_applied_at) {
+        status.push(Span::styled(
+            format!("  · last applied {} ({})", ago(at.elapsed().as_secs()), clock),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines.push(Line::from(status));
 
     match &app.overlay {
         Overlay::Confirm { .. } => {
-            lines.push(Line::from(Span::styled("[Y/Space] Keep  [N] Revert  [Esc] Revert", Style::default().fg(Color::DarkGray))));
+            lines.push(Line::from(Span::styled("[Y/Space] Keep  [S] Keep & save as preset  [N] Revert  [Esc] Revert", Style::default().fg(Color::DarkGray))));
         }
-        Overlay::ExternalChange => {
+        Overlay::VerifyMonitor { .. } => {
+            lines.push(Line::from(Span::styled("[Y] I can see it  [N/Esc] Disable it again", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::ExternalChange { confirming_pull: true, .. } => {
+            lines.push(Line::from(Span::styled("[Y] Pull and discard edits  [N/Esc] Back", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::ExternalChange { .. } => {
             lines.push(Line::from(Span::styled("[O] Override (keep edits)  [P] Pull (reload from system)  [Q] Quit", Style::default().fg(Color::DarkGray))));
         }
+        Overlay::Disconnected { .. } => {
+            lines.push(Line::from(Span::styled("[Y] Apply anyway  [N/Esc] Cancel", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::PresetPreview { .. } => {
+            lines.push(Line::from(Span::styled("[Y/Enter] Apply  [N/Esc] Cancel", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::MirrorPicker { .. } => {
+            lines.push(Line::from(Span::styled("[j/k] Nav  [Enter] Choose  [Esc] Cancel", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::ScaleInput { .. } => {
+            lines.push(Line::from(Span::styled("Type a scale (0.1-8.0), [Enter] Set  [Esc] Cancel", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::Align => {
+            lines.push(Line::from(vec![
+                Span::styled("Align ", Style::default().fg(theme.accent)),
+                Span::styled("[h] Left  [j] Bottom  [k] Top  [l] Right edge with the nearest monitor that way  [Esc] Cancel", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        Overlay::Color => {
+            let m = &app.monitors[app.selected];
+            lines.push(Line::from(vec![
+                Span::styled(format!("Color {}: {} ", m.name, m.color_string()), Style::default().fg(theme.accent)),
+                Span::styled("[b] 8/10-bit  [c] Color preset  [Esc/Enter] Done", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        Overlay::WorkspaceInput { .. } => {
+            lines.push(Line::from(Span::styled("Type workspaces (1-99, e.g. 1-5,10,12), [Enter] Set  [Esc] Cancel", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::ReservedInput { input } => {
+            lines.push(Line::from(vec![
+                Span::styled(format!("Reserve on {}: {}_", app.monitors[app.selected].name, input), Style::default().fg(theme.accent)),
+                Span::styled("  Pixels at top bottom left right, e.g. 30 0 0 0 (empty for none)  [Enter] Set  [Esc] Cancel", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        Overlay::Jump { input } => {
+            lines.push(Line::from(vec![
+                Span::styled(format!(":{}_", input), Style::default().fg(theme.accent)),
+                Span::styled("  Type a monitor name  [Enter/Esc] Done", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        Overlay::Filter => {
+            lines.push(Line::from(vec![
+                Span::styled(format!("/{}_", app.filter), Style::default().fg(theme.accent)),
+                Span::styled("  Type part of a name or description  [Enter] Keep filter  [Esc] Clear", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        Overlay::ModePicker { .. } => {
+            lines.push(Line::from(Span::styled("[j/k] Nav  [PgUp/PgDn] Page  [Enter] Use mode  [Esc] Cancel", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::Presets { saving: true, renaming: Some(_), .. } => {
+            lines.push(Line::from(Span::styled("Type new name, [Enter] Rename  [Esc] Cancel", Style::default().fg(Color::DarkGray))));
+        }
         Overlay::Presets { saving: true, .. } => {
             lines.push(Line::from(Span::styled("Type name, [Enter] Save  [Esc] Cancel", Style::default().fg(Color::DarkGray))));
         }
         Overlay::Presets { .. } => {
-            lines.push(Line::from(Span::styled("[j/k] Nav  [Enter] Load  [s] Save  [d] Delete  [Esc] Close", Style::default().fg(Color::DarkGray))));
+            lines.push(Line::from(Span::styled("[j/k] Nav  [Enter] Load  [s] Save  [r] Rename  [c] Copy  [d] Delete  [t] Trust  [Esc] Close", Style::default().fg(Color::DarkGray))));
+        }
+        Overlay::None if app.read_only => {
+            lines.push(Line::from(Span::styled(
+                "[Tab] Select  [:/] Jump/Filter  [t] Toggle All  [v] View  [Alt+=/-] Zoom  [Ctrl+hjkl] Pan  [q] Quit",
+                Style::default().fg(Color::DarkGray)
+            )));
         }
         Overlay::None => {
+            // Two rows of room: the rest of the keys are listed in the README
             lines.push(Line::from(Span::styled(
-                "[Tab] Select  [hjkl] Move  [HJKL] Snap  [d/e] Dis/En  [s] Scale  [z] Res  [r] Rotate  [1-9] WS",
+                "[Tab] Select  [hjkl/HJKL] Move/Snap  [A] Align  [d/e] Dis/En  [s] Scale  [z/Z] Res/Pick  [r] Rotate  [V/C] VRR/Color  [m] Mirror  [1-9] WS  [B] Reserve",
                 Style::default().fg(Color::DarkGray)
             )));
             if app.changed {
                 lines.push(Line::from(Span::styled(
-                    "[t] Toggle All  [y] Apply  [p] Presets  [q] Quit",
+                    "[:/] Jump/Filter  [t] Toggle All  [v] View  [c] Confirm  [b] Baseline  [Ctrl+z] Discard  [y/Y] Apply/Keep now  [p/n/N] Presets  [q] Quit",
                     Style::default().fg(Color::DarkGray)
                 )));
             } else {
                 lines.push(Line::from(Span::styled(
-                    "[t] Toggle All  [p] Presets  [q] Quit",
+                    "[:/] Jump/Filter  [t] Toggle All  [v] View  [c] Confirm  [b] Baseline  [p/n/N] Presets  [q] Quit",
                     Style::default().fg(Color::DarkGray)
                 )));
             }
@@ -55,3 +161,12 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     let para = Paragraph::new(lines).alignment(Alignment::Center);
     f.render_widget(para, area);
 }
+
+/// A short "how long ago" for the last-applied stamp: seconds, then minutes, then hours.
+fn ago(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        _ => format!("{}h ago", secs / 3600),
+    }
+}
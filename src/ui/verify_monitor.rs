@@ -0,0 +1,52 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::Duration;
+
+use super::centered_rect_with_min_size;
+use crate::theme::Theme;
+
+pub fn draw(f: &mut Frame, name: &str, remaining: Duration, theme: &Theme, area: Rect) {
+    let popup = centered_rect_with_min_size(50, 25, 50, 12, area);
+    f.render_widget(Clear, popup);
+
+    let secs = remaining.as_secs();
+    let color = if secs <= 3 { Color::Red } else { theme.accent };
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Can you see {}?", name),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "A notification was flashed on it",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Disabling it again in {}s", secs),
+            Style::default().fg(color),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "[Y] Yes   [N / Esc] No, disable it",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Verify Monitor ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(para, popup);
+}